@@ -0,0 +1,14 @@
+#![no_main]
+
+use lang::{common::source::Source, lexer::Lexer};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(code) = std::str::from_utf8(data) {
+        let source = Source {
+            code,
+            path: "fuzz.lang",
+        };
+        Lexer::new(source).for_each(drop);
+    }
+});
@@ -0,0 +1,22 @@
+#![no_main]
+
+use lang::{
+    common::{intern::Interner, source::Source},
+    lexer::Lexer,
+    parser::{cursor::Cursor, Parser},
+    syntax,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(code) = std::str::from_utf8(data) {
+        let source = Source {
+            code,
+            path: "fuzz.lang",
+        };
+        let names = Interner::new();
+        let cursor = Cursor::new(Lexer::new(source).peekable(), source);
+        Parser::new(source, cursor, &names).parse_program().ok();
+        syntax::parse(source, &names).ok();
+    }
+});
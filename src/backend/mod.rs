@@ -0,0 +1,66 @@
+use std::{
+    fmt::{self, Display},
+    fs,
+    path::Path,
+    process::Command,
+};
+
+use crate::ir::Module;
+
+pub mod x86_64;
+
+#[derive(Clone, Debug)]
+pub struct BackendError {
+    pub function: Option<String>,
+    pub message: String,
+}
+
+impl BackendError {
+    pub fn new(function: Option<&str>, message: impl Into<String>) -> Self {
+        Self {
+            function: function.map(str::to_string),
+            message: message.into(),
+        }
+    }
+}
+impl Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.function {
+            Some(function) => write!(f, "cannot compile @{}: {}", function, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+pub fn compile(module: &Module, output: &Path) -> Result<(), BackendError> {
+    let assembly = x86_64::generate(module)?;
+    let assembly_path = output.with_extension("s");
+    let object_path = output.with_extension("o");
+    fs::write(&assembly_path, assembly).map_err(|err| BackendError::new(None, err.to_string()))?;
+    run(Command::new("as")
+        .arg("-o")
+        .arg(&object_path)
+        .arg(&assembly_path))?;
+    run(Command::new("ld").arg("-o").arg(output).arg(&object_path))?;
+    let _ = fs::remove_file(&object_path);
+    let _ = fs::remove_file(&assembly_path);
+    Ok(())
+}
+
+fn run(command: &mut Command) -> Result<(), BackendError> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .output()
+        .map_err(|err| BackendError::new(None, format!("failed to run `{}`: {}", program, err)))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(BackendError::new(
+        None,
+        format!(
+            "`{}` failed:\n{}",
+            program,
+            String::from_utf8_lossy(&output.stderr)
+        ),
+    ))
+}
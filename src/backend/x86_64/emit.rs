@@ -0,0 +1,512 @@
+use std::fmt::Write;
+
+use crate::{backend::BackendError, ir::BinaryOperator};
+
+use super::{
+    lir::{Callee, Class, Function, Intrinsic, Label, Op, Vreg},
+    regalloc::{Allocation, Location},
+};
+
+const INTEGER_ARGUMENTS: [&str; 6] = ["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
+const FLOAT_ARGUMENTS: [&str; 8] = [
+    "xmm0", "xmm1", "xmm2", "xmm3", "xmm4", "xmm5", "xmm6", "xmm7",
+];
+
+pub struct Emitter<'f> {
+    function: &'f Function,
+    allocation: &'f Allocation,
+    output: String,
+    labels: usize,
+}
+
+impl<'f> Emitter<'f> {
+    pub fn new(function: &'f Function, allocation: &'f Allocation) -> Self {
+        Self {
+            function,
+            allocation,
+            output: String::new(),
+            labels: 0,
+        }
+    }
+    fn line(&mut self, line: impl AsRef<str>) {
+        let _ = writeln!(self.output, "    {}", line.as_ref());
+    }
+    fn label(&self, label: Label) -> String {
+        format!(".L{}_{}", self.function.name, label.0)
+    }
+    fn local(&mut self) -> String {
+        self.labels += 1;
+        format!(".L{}_local{}", self.function.name, self.labels)
+    }
+    fn exit(&self) -> String {
+        format!(".L{}_exit", self.function.name)
+    }
+    fn class(&self, vreg: Vreg) -> Class {
+        self.function.classes[vreg.0]
+    }
+    fn location(&self, vreg: Vreg) -> Result<Location, BackendError> {
+        self.allocation.location(vreg).ok_or_else(|| {
+            BackendError::new(
+                Some(&self.function.name),
+                format!("virtual register %{} has no location", vreg.0),
+            )
+        })
+    }
+    fn operand(&self, vreg: Vreg) -> Result<String, BackendError> {
+        Ok(match self.location(vreg)? {
+            Location::Register(register) => register.to_string(),
+            Location::Stack(slot) => format!(
+                "qword ptr [rbp - {}]",
+                8 * (self.allocation.callee_saved.len() + 1 + slot)
+            ),
+        })
+    }
+    fn is_float_register(&self, vreg: Vreg) -> Result<bool, BackendError> {
+        Ok(
+            matches!(self.location(vreg)?, Location::Register(register) if register.starts_with("xmm")),
+        )
+    }
+    fn get_integer(&mut self, register: &str, vreg: Vreg) -> Result<(), BackendError> {
+        let operand = self.operand(vreg)?;
+        if operand == register {
+            return Ok(());
+        }
+        if self.is_float_register(vreg)? {
+            self.line(format!("movq {}, {}", register, operand));
+        } else {
+            self.line(format!("mov {}, {}", register, operand));
+        }
+        Ok(())
+    }
+    fn set_integer(&mut self, vreg: Vreg, register: &str) -> Result<(), BackendError> {
+        let operand = self.operand(vreg)?;
+        if operand == register {
+            return Ok(());
+        }
+        if self.is_float_register(vreg)? {
+            self.line(format!("movq {}, {}", operand, register));
+        } else {
+            self.line(format!("mov {}, {}", operand, register));
+        }
+        Ok(())
+    }
+    fn float_move(&self, vreg: Vreg) -> Result<&'static str, BackendError> {
+        Ok(match self.location(vreg)? {
+            Location::Register(register) if register.starts_with("xmm") => "movapd",
+            Location::Register(_) => "movq",
+            Location::Stack(_) => "movsd",
+        })
+    }
+    fn get_float(&mut self, register: &str, vreg: Vreg) -> Result<(), BackendError> {
+        let operand = self.operand(vreg)?;
+        if operand != register {
+            let instruction = self.float_move(vreg)?;
+            self.line(format!("{} {}, {}", instruction, register, operand));
+        }
+        Ok(())
+    }
+    fn set_float(&mut self, vreg: Vreg, register: &str) -> Result<(), BackendError> {
+        let operand = self.operand(vreg)?;
+        if operand != register {
+            let instruction = self.float_move(vreg)?;
+            self.line(format!("{} {}, {}", instruction, operand, register));
+        }
+        Ok(())
+    }
+    fn frame_size(&self) -> usize {
+        let words = self.allocation.callee_saved.len() + self.allocation.slots;
+        8 * (self.allocation.slots + words % 2)
+    }
+    pub fn emit(mut self) -> Result<String, BackendError> {
+        let name = self.function.name.clone();
+        let _ = writeln!(self.output, "    .globl {}\n{}:", name, name);
+        self.line("push rbp");
+        self.line("mov rbp, rsp");
+        for register in self.allocation.callee_saved.clone() {
+            self.line(format!("push {}", register));
+        }
+        let frame = self.frame_size();
+        if frame > 0 {
+            self.line(format!("sub rsp, {}", frame));
+        }
+        self.parameters()?;
+        let blocks = &self.function.blocks;
+        for (index, block) in blocks.iter().enumerate() {
+            let label = self.label(block.label);
+            let _ = writeln!(self.output, "{}:", label);
+            let next = blocks.get(index + 1).map(|block| block.label);
+            for op in &block.ops {
+                self.op(op, next)?;
+            }
+        }
+        let exit = self.exit();
+        let _ = writeln!(self.output, "{}:", exit);
+        self.line(format!(
+            "lea rsp, [rbp - {}]",
+            8 * self.allocation.callee_saved.len()
+        ));
+        for register in self.allocation.callee_saved.clone().iter().rev() {
+            self.line(format!("pop {}", register));
+        }
+        self.line("pop rbp");
+        self.line("ret");
+        Ok(self.output)
+    }
+    fn parameters(&mut self) -> Result<(), BackendError> {
+        let mut integers = INTEGER_ARGUMENTS.iter();
+        let mut floats = FLOAT_ARGUMENTS.iter();
+        let mut registers = vec![];
+        let mut stack = vec![];
+        for parameter in self.function.parameters.clone() {
+            let register = match self.class(parameter) {
+                Class::Integer => integers.next(),
+                Class::Float => floats.next(),
+            };
+            match register {
+                Some(register) => registers.push((parameter, *register)),
+                None => stack.push(parameter),
+            }
+        }
+        for (_, register) in &registers {
+            if register.starts_with("xmm") {
+                self.line(format!("movq rax, {}", register));
+                self.line("push rax");
+            } else {
+                self.line(format!("push {}", register));
+            }
+        }
+        for (index, parameter) in stack.into_iter().enumerate() {
+            self.line(format!("mov rax, qword ptr [rbp + {}]", 16 + 8 * index));
+            self.set_integer(parameter, "rax")?;
+        }
+        for (parameter, _) in registers.into_iter().rev() {
+            self.line("pop rax");
+            self.set_integer(parameter, "rax")?;
+        }
+        Ok(())
+    }
+    fn call(
+        &mut self,
+        callee: &Callee,
+        arguments: &[Vreg],
+        result: Option<Vreg>,
+    ) -> Result<(), BackendError> {
+        let mut integers = INTEGER_ARGUMENTS.iter();
+        let mut floats = FLOAT_ARGUMENTS.iter();
+        let mut registers = vec![];
+        let mut stack = vec![];
+        for argument in arguments {
+            let register = match (callee, self.class(*argument)) {
+                (Callee::Indirect, _) | (_, Class::Integer) => integers.next(),
+                (_, Class::Float) => floats.next(),
+            };
+            match register {
+                Some(register) => registers.push((*argument, *register)),
+                None => stack.push(*argument),
+            }
+        }
+        let padding = stack.len() % 2;
+        if padding > 0 {
+            self.line("sub rsp, 8");
+        }
+        for argument in stack.iter().rev() {
+            self.get_integer("rax", *argument)?;
+            self.line("push rax");
+        }
+        for (argument, _) in &registers {
+            self.get_integer("rax", *argument)?;
+            self.line("push rax");
+        }
+        for (_, register) in registers.iter().rev() {
+            if register.starts_with("xmm") {
+                self.line("pop rax");
+                self.line(format!("movq {}, rax", register));
+            } else {
+                self.line(format!("pop {}", register));
+            }
+        }
+        match callee {
+            Callee::Direct(name) => self.line(format!("call {}", name)),
+            Callee::Indirect => self.line("call qword ptr [rdi]"),
+        }
+        if !stack.is_empty() {
+            self.line(format!("add rsp, {}", 8 * (stack.len() + padding)));
+        }
+        if let Some(result) = result {
+            match (callee, self.class(result)) {
+                (Callee::Direct(_), Class::Float) => self.set_float(result, "xmm0")?,
+                _ => self.set_integer(result, "rax")?,
+            }
+        }
+        Ok(())
+    }
+    fn op(&mut self, op: &Op, next: Option<Label>) -> Result<(), BackendError> {
+        match op {
+            Op::Move { dst, src } => match (self.class(*dst), self.class(*src)) {
+                (Class::Float, Class::Float) if self.is_float_register(*dst)? => {
+                    let operand = self.operand(*dst)?;
+                    self.get_float(&operand, *src)?
+                }
+                (Class::Float, Class::Float) => {
+                    self.get_float("xmm15", *src)?;
+                    self.set_float(*dst, "xmm15")?;
+                }
+                _ => {
+                    self.get_integer("rax", *src)?;
+                    self.set_integer(*dst, "rax")?;
+                }
+            },
+            Op::Integer { dst, value } => {
+                self.line(format!("mov rax, {}", value));
+                self.set_integer(*dst, "rax")?;
+            }
+            Op::Float { dst, value } => {
+                self.line(format!("mov rax, {}", value.to_bits() as i64));
+                self.set_integer(*dst, "rax")?;
+            }
+            Op::Address { dst, label } => {
+                self.line(format!("lea rax, [rip + {}]", label));
+                self.set_integer(*dst, "rax")?;
+            }
+            Op::Binary {
+                operator,
+                dst,
+                lhs,
+                rhs,
+            } => match self.class(*lhs) {
+                Class::Integer => self.integer_binary(*operator, *dst, *lhs, *rhs)?,
+                Class::Float => self.float_binary(*operator, *dst, *lhs, *rhs)?,
+            },
+            Op::Negate { dst, src } => match self.class(*src) {
+                Class::Integer => {
+                    self.get_integer("rax", *src)?;
+                    self.line("neg rax");
+                    self.line("jo __lang_overflow");
+                    self.set_integer(*dst, "rax")?;
+                }
+                Class::Float => {
+                    self.get_integer("rax", *src)?;
+                    self.line("btc rax, 63");
+                    self.set_integer(*dst, "rax")?;
+                }
+            },
+            Op::Intrinsic {
+                intrinsic,
+                dst,
+                arguments,
+            } => self.intrinsic(*intrinsic, *dst, arguments)?,
+            Op::Load { dst, base, offset } => {
+                self.get_integer("rax", *base)?;
+                self.line(format!("mov r11, qword ptr [rax + {}]", offset));
+                self.set_integer(*dst, "r11")?;
+            }
+            Op::LoadIndexed { dst, base, index } => {
+                self.get_integer("rax", *base)?;
+                self.get_integer("r11", *index)?;
+                self.line("mov r11, qword ptr [rax + r11 * 8 + 8]");
+                self.set_integer(*dst, "r11")?;
+            }
+            Op::Store { base, offset, src } => {
+                self.get_integer("rax", *base)?;
+                self.get_integer("r11", *src)?;
+                self.line(format!("mov qword ptr [rax + {}], r11", offset));
+            }
+            Op::StoreIndexed { base, index, src } => {
+                self.get_integer("rax", *base)?;
+                self.get_integer("r10", *index)?;
+                self.get_integer("r11", *src)?;
+                self.line("mov qword ptr [rax + r10 * 8 + 8], r11");
+            }
+            Op::CheckBounds { array, index } => {
+                self.get_integer("rax", *array)?;
+                self.get_integer("r11", *index)?;
+                self.line("cmp r11, 0");
+                self.line("jl __lang_out_of_bounds");
+                self.line("cmp r11, qword ptr [rax]");
+                self.line("jge __lang_out_of_bounds");
+            }
+            Op::Call {
+                callee,
+                arguments,
+                result,
+            } => self.call(callee, arguments, *result)?,
+            Op::Jump(target) => {
+                if next != Some(*target) {
+                    self.line(format!("jmp {}", self.label(*target)));
+                }
+            }
+            Op::Branch {
+                condition,
+                consequent,
+                alternative,
+            } => {
+                self.get_integer("rax", *condition)?;
+                self.line("test rax, rax");
+                if next == Some(*consequent) {
+                    self.line(format!("jz {}", self.label(*alternative)));
+                    return Ok(());
+                }
+                self.line(format!("jnz {}", self.label(*consequent)));
+                if next != Some(*alternative) {
+                    self.line(format!("jmp {}", self.label(*alternative)));
+                }
+            }
+            Op::Return(value) => {
+                match self.class(*value) {
+                    Class::Integer => self.get_integer("rax", *value)?,
+                    Class::Float => self.get_float("xmm0", *value)?,
+                }
+                self.line(format!("jmp {}", self.exit()));
+            }
+            Op::Trap(routine) => self.line(format!("jmp {}", routine)),
+        }
+        Ok(())
+    }
+    fn integer_binary(
+        &mut self,
+        operator: BinaryOperator,
+        dst: Vreg,
+        lhs: Vreg,
+        rhs: Vreg,
+    ) -> Result<(), BackendError> {
+        self.get_integer("rax", lhs)?;
+        self.get_integer("r11", rhs)?;
+        let condition = match operator {
+            BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply => {
+                let instruction = match operator {
+                    BinaryOperator::Add => "add rax, r11",
+                    BinaryOperator::Subtract => "sub rax, r11",
+                    _ => "imul rax, r11",
+                };
+                self.line(instruction);
+                self.line("jo __lang_overflow");
+                return self.set_integer(dst, "rax");
+            }
+            BinaryOperator::Divide => {
+                let divide = self.local();
+                self.line("test r11, r11");
+                self.line("jz __lang_division_by_zero");
+                self.line("cmp r11, -1");
+                self.line(format!("jne {}", divide));
+                self.line(format!("mov r10, {}", i64::MIN));
+                self.line("cmp rax, r10");
+                self.line("je __lang_overflow");
+                let _ = writeln!(self.output, "{}:", divide);
+                self.line("cqo");
+                self.line("idiv r11");
+                return self.set_integer(dst, "rax");
+            }
+            BinaryOperator::Equal => "sete",
+            BinaryOperator::LessThen => "setl",
+            BinaryOperator::GreaterThen => "setg",
+            BinaryOperator::LessThenEqual => "setle",
+            BinaryOperator::GreaterThenEqual => "setge",
+        };
+        self.line("cmp rax, r11");
+        self.line(format!("{} al", condition));
+        self.line("movzx rax, al");
+        self.set_integer(dst, "rax")?;
+        Ok(())
+    }
+    fn float_binary(
+        &mut self,
+        operator: BinaryOperator,
+        dst: Vreg,
+        lhs: Vreg,
+        rhs: Vreg,
+    ) -> Result<(), BackendError> {
+        self.get_float("xmm14", lhs)?;
+        self.get_float("xmm15", rhs)?;
+        let (comparison, condition) = match operator {
+            BinaryOperator::Add
+            | BinaryOperator::Subtract
+            | BinaryOperator::Multiply
+            | BinaryOperator::Divide => {
+                let instruction = match operator {
+                    BinaryOperator::Add => "addsd",
+                    BinaryOperator::Subtract => "subsd",
+                    BinaryOperator::Multiply => "mulsd",
+                    _ => "divsd",
+                };
+                self.line(format!("{} xmm14, xmm15", instruction));
+                return self.set_float(dst, "xmm14");
+            }
+            BinaryOperator::Equal => {
+                self.line("ucomisd xmm14, xmm15");
+                self.line("sete al");
+                self.line("setnp r11b");
+                self.line("and al, r11b");
+                self.line("movzx rax, al");
+                return self.set_integer(dst, "rax");
+            }
+            BinaryOperator::LessThen => ("ucomisd xmm15, xmm14", "seta"),
+            BinaryOperator::LessThenEqual => ("ucomisd xmm15, xmm14", "setae"),
+            BinaryOperator::GreaterThen => ("ucomisd xmm14, xmm15", "seta"),
+            BinaryOperator::GreaterThenEqual => ("ucomisd xmm14, xmm15", "setae"),
+        };
+        self.line(comparison);
+        self.line(format!("{} al", condition));
+        self.line("movzx rax, al");
+        self.set_integer(dst, "rax")?;
+        Ok(())
+    }
+    fn intrinsic(
+        &mut self,
+        intrinsic: Intrinsic,
+        dst: Vreg,
+        arguments: &[Vreg],
+    ) -> Result<(), BackendError> {
+        let class = self.class(dst);
+        match (intrinsic, class) {
+            (Intrinsic::Sqrt, _) => {
+                self.get_float("xmm14", arguments[0])?;
+                self.line("sqrtsd xmm14, xmm14");
+                self.set_float(dst, "xmm14")?;
+            }
+            (Intrinsic::Abs, Class::Integer) => {
+                self.get_integer("rax", arguments[0])?;
+                self.line("mov r11, rax");
+                self.line("neg r11");
+                self.line("jo __lang_overflow");
+                self.line("test rax, rax");
+                self.line("cmovl rax, r11");
+                self.set_integer(dst, "rax")?;
+            }
+            (Intrinsic::Abs, Class::Float) => {
+                self.get_integer("rax", arguments[0])?;
+                self.line("btr rax, 63");
+                self.set_integer(dst, "rax")?;
+            }
+            (Intrinsic::Min | Intrinsic::Max, Class::Integer) => {
+                self.get_integer("rax", arguments[0])?;
+                self.get_integer("r11", arguments[1])?;
+                self.line("cmp rax, r11");
+                match intrinsic {
+                    Intrinsic::Min => self.line("cmovg rax, r11"),
+                    _ => self.line("cmovl rax, r11"),
+                }
+                self.set_integer(dst, "rax")?;
+            }
+            (Intrinsic::Min | Intrinsic::Max, Class::Float) => {
+                self.get_float("xmm14", arguments[0])?;
+                self.get_float("xmm15", arguments[1])?;
+                match intrinsic {
+                    Intrinsic::Min => self.line("minsd xmm14, xmm15"),
+                    _ => self.line("maxsd xmm14, xmm15"),
+                }
+                self.set_float(dst, "xmm14")?;
+            }
+            (Intrinsic::IntegerToFloat, _) => {
+                self.get_integer("rax", arguments[0])?;
+                self.line("cvtsi2sd xmm14, rax");
+                self.set_float(dst, "xmm14")?;
+            }
+            (Intrinsic::FloatToInteger, _) => {
+                self.get_float("xmm14", arguments[0])?;
+                self.line("cvttsd2si rax, xmm14");
+                self.set_integer(dst, "rax")?;
+            }
+        }
+        Ok(())
+    }
+}
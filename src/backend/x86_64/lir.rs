@@ -0,0 +1,158 @@
+use crate::ir::BinaryOperator;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vreg(pub usize);
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Label(pub usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Class {
+    Integer,
+    Float,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Intrinsic {
+    Sqrt,
+    Abs,
+    Min,
+    Max,
+    IntegerToFloat,
+    FloatToInteger,
+}
+#[derive(Clone, Debug)]
+pub enum Callee {
+    Direct(String),
+    Indirect,
+}
+#[derive(Clone, Debug)]
+pub enum Op {
+    Move {
+        dst: Vreg,
+        src: Vreg,
+    },
+    Integer {
+        dst: Vreg,
+        value: i64,
+    },
+    Float {
+        dst: Vreg,
+        value: f64,
+    },
+    Address {
+        dst: Vreg,
+        label: String,
+    },
+    Binary {
+        operator: BinaryOperator,
+        dst: Vreg,
+        lhs: Vreg,
+        rhs: Vreg,
+    },
+    Negate {
+        dst: Vreg,
+        src: Vreg,
+    },
+    Intrinsic {
+        intrinsic: Intrinsic,
+        dst: Vreg,
+        arguments: Vec<Vreg>,
+    },
+    Load {
+        dst: Vreg,
+        base: Vreg,
+        offset: i32,
+    },
+    LoadIndexed {
+        dst: Vreg,
+        base: Vreg,
+        index: Vreg,
+    },
+    Store {
+        base: Vreg,
+        offset: i32,
+        src: Vreg,
+    },
+    StoreIndexed {
+        base: Vreg,
+        index: Vreg,
+        src: Vreg,
+    },
+    CheckBounds {
+        array: Vreg,
+        index: Vreg,
+    },
+    Call {
+        callee: Callee,
+        arguments: Vec<Vreg>,
+        result: Option<Vreg>,
+    },
+    Jump(Label),
+    Branch {
+        condition: Vreg,
+        consequent: Label,
+        alternative: Label,
+    },
+    Return(Vreg),
+    Trap(&'static str),
+}
+#[derive(Clone, Debug)]
+pub struct Block {
+    pub label: Label,
+    pub ops: Vec<Op>,
+}
+#[derive(Clone, Debug)]
+pub struct Function {
+    pub name: String,
+    pub parameters: Vec<Vreg>,
+    pub classes: Vec<Class>,
+    pub blocks: Vec<Block>,
+}
+
+impl Op {
+    pub fn uses(&self) -> Vec<Vreg> {
+        match self {
+            Op::Move { src, .. } | Op::Negate { src, .. } => vec![*src],
+            Op::Integer { .. } | Op::Float { .. } | Op::Address { .. } => vec![],
+            Op::Binary { lhs, rhs, .. } => vec![*lhs, *rhs],
+            Op::Intrinsic { arguments, .. } => arguments.clone(),
+            Op::Load { base, .. } => vec![*base],
+            Op::LoadIndexed { base, index, .. } => vec![*base, *index],
+            Op::Store { base, src, .. } => vec![*base, *src],
+            Op::StoreIndexed { base, index, src } => vec![*base, *index, *src],
+            Op::CheckBounds { array, index } => vec![*array, *index],
+            Op::Call { arguments, .. } => arguments.clone(),
+            Op::Branch { condition, .. } => vec![*condition],
+            Op::Return(value) => vec![*value],
+            Op::Jump(_) | Op::Trap(_) => vec![],
+        }
+    }
+    pub fn definition(&self) -> Option<Vreg> {
+        match self {
+            Op::Move { dst, .. }
+            | Op::Integer { dst, .. }
+            | Op::Float { dst, .. }
+            | Op::Address { dst, .. }
+            | Op::Binary { dst, .. }
+            | Op::Negate { dst, .. }
+            | Op::Intrinsic { dst, .. }
+            | Op::Load { dst, .. }
+            | Op::LoadIndexed { dst, .. } => Some(*dst),
+            Op::Call { result, .. } => *result,
+            _ => None,
+        }
+    }
+    pub fn successors(&self) -> Vec<Label> {
+        match self {
+            Op::Jump(target) => vec![*target],
+            Op::Branch {
+                consequent,
+                alternative,
+                ..
+            } => vec![*consequent, *alternative],
+            _ => vec![],
+        }
+    }
+    pub fn is_call(&self) -> bool {
+        matches!(self, Op::Call { .. })
+    }
+}
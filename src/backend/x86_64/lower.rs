@@ -0,0 +1,665 @@
+use std::collections::HashMap;
+
+use crate::{
+    backend::BackendError,
+    ir::{self, BlockId, Constant, InstructionKind, Terminator, Type, ValueId},
+};
+
+use super::lir::{Block, Callee, Class, Function, Intrinsic, Label, Op, Vreg};
+
+pub struct Thunk {
+    pub function: String,
+    pub captures: usize,
+    pub parameters: Vec<Class>,
+    pub return_class: Class,
+}
+pub struct Program {
+    pub functions: Vec<Function>,
+    pub strings: Vec<String>,
+    pub thunks: Vec<Thunk>,
+    pub closures: Vec<String>,
+}
+
+pub const INDIRECT_ARGUMENTS: usize = 5;
+
+pub fn mangle(name: &str) -> String {
+    let mut mangled = String::from("lang_");
+    for char in name.chars() {
+        match char {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => mangled.push(char),
+            '_' => mangled.push_str("__"),
+            _ => mangled.push_str(&format!("_{:x}_", char as u32)),
+        }
+    }
+    mangled
+}
+pub fn class_of(value_type: &Type) -> Class {
+    match value_type {
+        Type::Float => Class::Float,
+        _ => Class::Integer,
+    }
+}
+
+pub fn lower(module: &ir::Module) -> Result<Program, BackendError> {
+    let functions = module
+        .functions
+        .iter()
+        .map(|function| (function.name, function))
+        .collect::<HashMap<_, _>>();
+    let mut program = Program {
+        functions: vec![],
+        strings: vec![],
+        thunks: vec![],
+        closures: vec![],
+    };
+    for function in &module.functions {
+        let lowered = Lowerer::new(function, &functions, &mut program).lower()?;
+        program.functions.push(lowered);
+    }
+    Ok(program)
+}
+
+struct Lowerer<'a, 'm, 'p> {
+    function: &'m ir::Function<'a>,
+    functions: &'m HashMap<&'a str, &'m ir::Function<'a>>,
+    program: &'p mut Program,
+    types: HashMap<ValueId, Type<'a>>,
+    classes: Vec<Class>,
+    blocks: Vec<Block>,
+    edges: Vec<Block>,
+    labels: usize,
+}
+
+impl<'a, 'm, 'p> Lowerer<'a, 'm, 'p> {
+    fn new(
+        function: &'m ir::Function<'a>,
+        functions: &'m HashMap<&'a str, &'m ir::Function<'a>>,
+        program: &'p mut Program,
+    ) -> Self {
+        let mut types = HashMap::new();
+        for (value, value_type) in &function.parameters {
+            types.insert(*value, value_type.clone());
+        }
+        for block in &function.blocks {
+            for phi in &block.phis {
+                types.insert(phi.value, phi.value_type.clone());
+            }
+            for instruction in &block.instructions {
+                types.insert(instruction.value, instruction.value_type.clone());
+            }
+        }
+        let values = types.keys().map(|value| value.0 + 1).max().unwrap_or(0);
+        let mut classes = vec![Class::Integer; values];
+        for (value, value_type) in &types {
+            classes[value.0] = class_of(value_type);
+        }
+        Self {
+            function,
+            functions,
+            program,
+            types,
+            classes,
+            blocks: vec![],
+            edges: vec![],
+            labels: function
+                .blocks
+                .iter()
+                .map(|block| block.id.0 + 1)
+                .max()
+                .unwrap_or(0),
+        }
+    }
+    fn error(&self, message: impl Into<String>) -> BackendError {
+        BackendError::new(Some(self.function.name), message)
+    }
+    fn temporary(&mut self, class: Class) -> Vreg {
+        self.classes.push(class);
+        Vreg(self.classes.len() - 1)
+    }
+    fn type_of(&self, value: ValueId) -> Type<'a> {
+        self.types.get(&value).cloned().unwrap_or(Type::Dynamic)
+    }
+    fn class(&self, vreg: Vreg) -> Class {
+        self.classes[vreg.0]
+    }
+    fn convert(&mut self, ops: &mut Vec<Op>, value: Vreg, class: Class) -> Vreg {
+        if self.class(value) == class {
+            return value;
+        }
+        let converted = self.temporary(class);
+        ops.push(Op::Move {
+            dst: converted,
+            src: value,
+        });
+        converted
+    }
+    fn lower(mut self) -> Result<Function, BackendError> {
+        for block in &self.function.blocks {
+            let mut ops = vec![];
+            for instruction in &block.instructions {
+                self.lower_instruction(&mut ops, instruction)?;
+            }
+            match &block.terminator {
+                Terminator::Jump(target) => {
+                    self.phi_copies(&mut ops, block.id, *target);
+                    ops.push(Op::Jump(Label(target.0)));
+                }
+                Terminator::Branch {
+                    condition,
+                    consequent,
+                    alternative,
+                } => {
+                    let consequent = self.edge(block.id, *consequent);
+                    let alternative = self.edge(block.id, *alternative);
+                    ops.push(Op::Branch {
+                        condition: Vreg(condition.0),
+                        consequent,
+                        alternative,
+                    });
+                }
+                Terminator::Return(value) => {
+                    let class = class_of(&self.function.return_type);
+                    let value = self.convert(&mut ops, Vreg(value.0), class);
+                    ops.push(Op::Return(value));
+                }
+                Terminator::Unreachable => ops.push(Op::Trap("__lang_unreachable")),
+            }
+            self.blocks.push(Block {
+                label: Label(block.id.0),
+                ops,
+            });
+        }
+        self.blocks.append(&mut self.edges);
+        Ok(Function {
+            name: mangle(self.function.name),
+            parameters: self
+                .function
+                .parameters
+                .iter()
+                .map(|(value, _)| Vreg(value.0))
+                .collect(),
+            classes: self.classes,
+            blocks: self.blocks,
+        })
+    }
+    fn edge(&mut self, from: BlockId, to: BlockId) -> Label {
+        let has_phis = self
+            .function
+            .block(to)
+            .is_some_and(|block| !block.phis.is_empty());
+        if !has_phis {
+            return Label(to.0);
+        }
+        let label = Label(self.labels);
+        self.labels += 1;
+        let mut ops = vec![];
+        self.phi_copies(&mut ops, from, to);
+        ops.push(Op::Jump(Label(to.0)));
+        self.edges.push(Block { label, ops });
+        label
+    }
+    fn phi_copies(&mut self, ops: &mut Vec<Op>, from: BlockId, to: BlockId) {
+        let Some(target) = self.function.block(to) else {
+            return;
+        };
+        let mut copies = vec![];
+        for phi in &target.phis {
+            if let Some((_, value)) = phi.incoming.iter().find(|(block, _)| *block == from) {
+                let destination = Vreg(phi.value.0);
+                let temporary = self.temporary(self.class(destination));
+                ops.push(Op::Move {
+                    dst: temporary,
+                    src: Vreg(value.0),
+                });
+                copies.push((destination, temporary));
+            }
+        }
+        for (destination, temporary) in copies {
+            ops.push(Op::Move {
+                dst: destination,
+                src: temporary,
+            });
+        }
+    }
+    fn string(&mut self, string: &str) -> String {
+        let index = match self
+            .program
+            .strings
+            .iter()
+            .position(|known| known == string)
+        {
+            Some(index) => index,
+            None => {
+                self.program.strings.push(string.to_string());
+                self.program.strings.len() - 1
+            }
+        };
+        format!("__lang_string_{}", index)
+    }
+    fn thunk(&mut self, name: &str, captures: usize) -> Result<String, BackendError> {
+        let function = self.functions.get(name).ok_or_else(|| {
+            self.error(format!("function values of `{}` are not supported", name))
+        })?;
+        let parameters = function
+            .parameters
+            .iter()
+            .map(|(_, value_type)| class_of(value_type))
+            .collect::<Vec<_>>();
+        let integers = parameters
+            .iter()
+            .filter(|class| **class == Class::Integer)
+            .count();
+        if parameters.len() - captures > INDIRECT_ARGUMENTS
+            || integers > 6
+            || parameters.len() - integers > 8
+        {
+            return Err(self.error(format!(
+                "`{}` has too many parameters to be called indirectly",
+                name
+            )));
+        }
+        let label = mangle(name);
+        if !self
+            .program
+            .thunks
+            .iter()
+            .any(|thunk| thunk.function == label)
+        {
+            self.program.thunks.push(Thunk {
+                function: label.clone(),
+                captures,
+                parameters,
+                return_class: class_of(&function.return_type),
+            });
+        }
+        Ok(format!("{}_thunk", label))
+    }
+    fn allocate(&mut self, ops: &mut Vec<Op>, words: usize) -> Vreg {
+        let size = self.temporary(Class::Integer);
+        ops.push(Op::Integer {
+            dst: size,
+            value: (words * 8) as i64,
+        });
+        let pointer = self.temporary(Class::Integer);
+        ops.push(Op::Call {
+            callee: Callee::Direct("__lang_alloc".to_string()),
+            arguments: vec![size],
+            result: Some(pointer),
+        });
+        pointer
+    }
+    fn lower_instruction(
+        &mut self,
+        ops: &mut Vec<Op>,
+        instruction: &ir::Instruction<'a>,
+    ) -> Result<(), BackendError> {
+        let dst = Vreg(instruction.value.0);
+        match &instruction.kind {
+            InstructionKind::Constant(constant) => match constant {
+                Constant::Unit => ops.push(Op::Integer { dst, value: 0 }),
+                Constant::Bool(boolean) => ops.push(Op::Integer {
+                    dst,
+                    value: *boolean as i64,
+                }),
+                Constant::Int(integer) => ops.push(Op::Integer {
+                    dst,
+                    value: *integer,
+                }),
+                Constant::Float(float) => ops.push(Op::Float { dst, value: *float }),
+                Constant::String(string) => {
+                    let label = self.string(string);
+                    ops.push(Op::Address { dst, label });
+                }
+                Constant::Function(name) => {
+                    self.thunk(name, 0)?;
+                    let function = mangle(name);
+                    let label = format!("{}_closure", function);
+                    if !self.program.closures.contains(&function) {
+                        self.program.closures.push(function);
+                    }
+                    ops.push(Op::Address { dst, label });
+                }
+            },
+            InstructionKind::Binary { operator, lhs, rhs } => {
+                let operand_type = match (self.type_of(*lhs), self.type_of(*rhs)) {
+                    (Type::Dynamic, rhs) => rhs,
+                    (lhs, _) => lhs,
+                };
+                if operand_type == Type::String && *operator == ir::BinaryOperator::Equal {
+                    ops.push(Op::Call {
+                        callee: Callee::Direct("__lang_string_equal".to_string()),
+                        arguments: vec![Vreg(lhs.0), Vreg(rhs.0)],
+                        result: Some(dst),
+                    });
+                    return Ok(());
+                }
+                match operand_type {
+                    Type::Int | Type::Bool | Type::Unit | Type::Float => {}
+                    Type::Dynamic => {
+                        return Err(self.error(format!(
+                            "the operands of `{}` have no static type",
+                            instruction
+                        )))
+                    }
+                    other => {
+                        return Err(self.error(format!(
+                            "`{}` on values of type {} is not supported",
+                            operator, other
+                        )))
+                    }
+                }
+                let class = class_of(&operand_type);
+                let lhs = self.convert(ops, Vreg(lhs.0), class);
+                let rhs = self.convert(ops, Vreg(rhs.0), class);
+                ops.push(Op::Binary {
+                    operator: *operator,
+                    dst,
+                    lhs,
+                    rhs,
+                });
+            }
+            InstructionKind::Negate(value) => {
+                if self.type_of(*value) == Type::Dynamic {
+                    return Err(self.error(format!(
+                        "the operand of `{}` has no static type",
+                        instruction
+                    )));
+                }
+                ops.push(Op::Negate {
+                    dst,
+                    src: Vreg(value.0),
+                });
+            }
+            InstructionKind::Call {
+                function,
+                arguments,
+            } => match self.functions.get(function).copied() {
+                Some(callee) => {
+                    let mut values = vec![];
+                    for (argument, (_, parameter_type)) in arguments.iter().zip(&callee.parameters)
+                    {
+                        values.push(self.convert(ops, Vreg(argument.0), class_of(parameter_type)));
+                    }
+                    let result = self.temporary(class_of(&callee.return_type));
+                    ops.push(Op::Call {
+                        callee: Callee::Direct(mangle(function)),
+                        arguments: values,
+                        result: Some(result),
+                    });
+                    ops.push(Op::Move { dst, src: result });
+                }
+                None => self.lower_builtin(ops, function, arguments, dst)?,
+            },
+            InstructionKind::CallIndirect { callee, arguments } => {
+                if arguments.len() > INDIRECT_ARGUMENTS {
+                    return Err(self.error("too many arguments for an indirect call"));
+                }
+                let mut values = vec![Vreg(callee.0)];
+                for argument in arguments {
+                    values.push(self.convert(ops, Vreg(argument.0), Class::Integer));
+                }
+                let result = self.temporary(Class::Integer);
+                ops.push(Op::Call {
+                    callee: Callee::Indirect,
+                    arguments: values,
+                    result: Some(result),
+                });
+                ops.push(Op::Move { dst, src: result });
+            }
+            InstructionKind::Closure { function, captures } => {
+                let thunk = self.thunk(function, captures.len())?;
+                let pointer = self.allocate(ops, captures.len() + 1);
+                let address = self.temporary(Class::Integer);
+                ops.push(Op::Address {
+                    dst: address,
+                    label: thunk,
+                });
+                ops.push(Op::Store {
+                    base: pointer,
+                    offset: 0,
+                    src: address,
+                });
+                for (index, capture) in captures.iter().enumerate() {
+                    ops.push(Op::Store {
+                        base: pointer,
+                        offset: 8 * (index as i32 + 1),
+                        src: Vreg(capture.0),
+                    });
+                }
+                ops.push(Op::Move { dst, src: pointer });
+            }
+            InstructionKind::Array(elements) => {
+                let pointer = self.allocate(ops, elements.len() + 1);
+                let length = self.temporary(Class::Integer);
+                ops.push(Op::Integer {
+                    dst: length,
+                    value: elements.len() as i64,
+                });
+                ops.push(Op::Store {
+                    base: pointer,
+                    offset: 0,
+                    src: length,
+                });
+                for (index, element) in elements.iter().enumerate() {
+                    ops.push(Op::Store {
+                        base: pointer,
+                        offset: 8 * (index as i32 + 1),
+                        src: Vreg(element.0),
+                    });
+                }
+                ops.push(Op::Move { dst, src: pointer });
+            }
+            InstructionKind::Extract { array, index } => {
+                ops.push(Op::CheckBounds {
+                    array: Vreg(array.0),
+                    index: Vreg(index.0),
+                });
+                ops.push(Op::LoadIndexed {
+                    dst,
+                    base: Vreg(array.0),
+                    index: Vreg(index.0),
+                });
+            }
+            InstructionKind::Insert {
+                array,
+                index,
+                value,
+            } => {
+                ops.push(Op::CheckBounds {
+                    array: Vreg(array.0),
+                    index: Vreg(index.0),
+                });
+                let copy = self.temporary(Class::Integer);
+                ops.push(Op::Call {
+                    callee: Callee::Direct("__lang_array_copy".to_string()),
+                    arguments: vec![Vreg(array.0)],
+                    result: Some(copy),
+                });
+                ops.push(Op::StoreIndexed {
+                    base: copy,
+                    index: Vreg(index.0),
+                    src: Vreg(value.0),
+                });
+                ops.push(Op::Move { dst, src: copy });
+            }
+            InstructionKind::Variant { tag, fields, .. } => {
+                let pointer = self.allocate(ops, fields.len() + 1);
+                let value = self.temporary(Class::Integer);
+                ops.push(Op::Integer {
+                    dst: value,
+                    value: *tag as i64,
+                });
+                ops.push(Op::Store {
+                    base: pointer,
+                    offset: 0,
+                    src: value,
+                });
+                for (index, field) in fields.iter().enumerate() {
+                    ops.push(Op::Store {
+                        base: pointer,
+                        offset: 8 * (index as i32 + 1),
+                        src: Vreg(field.0),
+                    });
+                }
+                ops.push(Op::Move { dst, src: pointer });
+            }
+            InstructionKind::Tag(value) => ops.push(Op::Load {
+                dst,
+                base: Vreg(value.0),
+                offset: 0,
+            }),
+            InstructionKind::Field { value, index } => ops.push(Op::Load {
+                dst,
+                base: Vreg(value.0),
+                offset: 8 * (*index as i32 + 1),
+            }),
+        }
+        Ok(())
+    }
+    fn lower_builtin(
+        &mut self,
+        ops: &mut Vec<Op>,
+        name: &str,
+        arguments: &[ValueId],
+        dst: Vreg,
+    ) -> Result<(), BackendError> {
+        let types = arguments
+            .iter()
+            .map(|argument| self.type_of(*argument))
+            .collect::<Vec<_>>();
+        let values = arguments
+            .iter()
+            .map(|argument| Vreg(argument.0))
+            .collect::<Vec<_>>();
+        let call = |routine: &str, arguments: Vec<Vreg>, result: Option<Vreg>| Op::Call {
+            callee: Callee::Direct(routine.to_string()),
+            arguments,
+            result,
+        };
+        let intrinsic = |intrinsic| Op::Intrinsic {
+            intrinsic,
+            dst,
+            arguments: values.clone(),
+        };
+        match (name, types.as_slice()) {
+            ("print" | "println", _) => {
+                for (index, (value, value_type)) in values.iter().zip(&types).enumerate() {
+                    if index > 0 {
+                        ops.push(call("__lang_print_space", vec![], None));
+                    }
+                    let routine = self.print_routine(value_type)?;
+                    ops.push(call(routine, vec![*value], None));
+                }
+                if name == "println" {
+                    ops.push(call("__lang_print_newline", vec![], None));
+                }
+                ops.push(Op::Integer { dst, value: 0 });
+            }
+            ("format", [Type::String, arguments @ ..]) => {
+                ops.push(call("__lang_capture_begin", vec![], None));
+                let mut offset = self.temporary(Class::Integer);
+                ops.push(Op::Integer {
+                    dst: offset,
+                    value: 0,
+                });
+                for (value, value_type) in values[1..].iter().zip(arguments) {
+                    let routine = self.print_routine(value_type)?;
+                    let next = self.temporary(Class::Integer);
+                    ops.push(call(
+                        "__lang_format_next",
+                        vec![values[0], offset],
+                        Some(next),
+                    ));
+                    ops.push(call(routine, vec![*value], None));
+                    offset = next;
+                }
+                ops.push(call(
+                    "__lang_format_rest",
+                    vec![values[0], offset],
+                    Some(dst),
+                ));
+            }
+            ("to_string", [value_type]) => {
+                let routine = self.print_routine(value_type)?;
+                ops.push(call("__lang_capture_begin", vec![], None));
+                ops.push(call(routine, values, None));
+                ops.push(call("__lang_capture_end", vec![], Some(dst)));
+            }
+            ("concat", [Type::String, Type::String]) => {
+                ops.push(call("__lang_capture_begin", vec![], None));
+                for value in values {
+                    ops.push(call("__lang_print_string", vec![value], None));
+                }
+                ops.push(call("__lang_capture_end", vec![], Some(dst)));
+            }
+            ("upper", [Type::String]) => ops.push(call("__lang_string_upper", values, Some(dst))),
+            ("lower", [Type::String]) => ops.push(call("__lang_string_lower", values, Some(dst))),
+            ("len", [Type::Array(_) | Type::String]) => ops.push(Op::Load {
+                dst,
+                base: values[0],
+                offset: 0,
+            }),
+            ("range", [Type::Int, Type::Int]) => ops.push(call("__lang_range", values, Some(dst))),
+            ("slice", [Type::Array(_), Type::Int, Type::Int]) => {
+                ops.push(call("__lang_array_slice", values, Some(dst)))
+            }
+            ("slice", [Type::String, Type::Int, Type::Int]) => {
+                ops.push(call("__lang_string_slice", values, Some(dst)))
+            }
+            ("assert", [Type::Bool]) => {
+                ops.push(call("__lang_assert", values, None));
+                ops.push(Op::Integer { dst, value: 0 });
+            }
+            ("exit", [Type::Int]) => {
+                ops.push(call("__lang_exit", values, None));
+                ops.push(Op::Integer { dst, value: 0 });
+            }
+            ("sqrt", [Type::Float]) => ops.push(intrinsic(Intrinsic::Sqrt)),
+            ("abs", [Type::Int | Type::Float]) => ops.push(intrinsic(Intrinsic::Abs)),
+            ("min", [Type::Int, Type::Int] | [Type::Float, Type::Float]) => {
+                ops.push(intrinsic(Intrinsic::Min))
+            }
+            ("max", [Type::Int, Type::Int] | [Type::Float, Type::Float]) => {
+                ops.push(intrinsic(Intrinsic::Max))
+            }
+            ("to_float", [Type::Int]) => ops.push(intrinsic(Intrinsic::IntegerToFloat)),
+            ("to_int", [Type::Float]) => ops.push(intrinsic(Intrinsic::FloatToInteger)),
+            ("to_float", [Type::Float]) | ("to_int", [Type::Int]) => ops.push(Op::Move {
+                dst,
+                src: values[0],
+            }),
+            _ => {
+                return Err(self.error(format!(
+                    "the builtin `{}` is not supported for arguments ({})",
+                    name,
+                    types
+                        .iter()
+                        .map(|value_type| value_type.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )))
+            }
+        }
+        Ok(())
+    }
+    fn print_routine(&self, value_type: &Type) -> Result<&'static str, BackendError> {
+        let routine = match value_type {
+            Type::Int => "__lang_print_int",
+            Type::Float => "__lang_print_float",
+            Type::Bool => "__lang_print_bool",
+            Type::String => "__lang_print_string",
+            Type::Unit => "__lang_print_unit",
+            Type::Array(element) => match element.as_ref() {
+                Type::Int => "__lang_print_int_array",
+                Type::Float => "__lang_print_float_array",
+                Type::Bool => "__lang_print_bool_array",
+                _ => return Err(self.unsupported_print(value_type)),
+            },
+            _ => return Err(self.unsupported_print(value_type)),
+        };
+        Ok(routine)
+    }
+    fn unsupported_print(&self, value_type: &Type) -> BackendError {
+        self.error(format!("values of type {} cannot be printed", value_type))
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt::Write;
+
+use crate::ir::Module;
+
+use self::{
+    emit::Emitter,
+    lir::Class,
+    lower::{lower, Thunk},
+};
+
+use super::BackendError;
+
+pub mod emit;
+pub mod lir;
+pub mod lower;
+pub mod regalloc;
+pub mod runtime;
+
+const INTEGER_PARAMETERS: [&str; 6] = ["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
+const INDIRECT_PARAMETERS: [&str; 5] = ["rsi", "rdx", "rcx", "r8", "r9"];
+
+pub fn generate(module: &Module) -> Result<String, BackendError> {
+    if !module
+        .functions
+        .iter()
+        .any(|function| function.name == "main")
+    {
+        return Err(BackendError::new(None, "no `main` function to compile"));
+    }
+    let program = lower(module)?;
+    let mut output = String::from("    .intel_syntax noprefix\n    .text\n");
+    for function in &program.functions {
+        let allocation = regalloc::allocate(function);
+        output.push_str(&Emitter::new(function, &allocation).emit()?);
+    }
+    for thunk in &program.thunks {
+        emit_thunk(&mut output, thunk);
+    }
+    output.push_str(&runtime::text());
+    output.push_str("    .data\n    .align 8\n");
+    for function in &program.closures {
+        let _ = writeln!(output, "{0}_closure: .quad {0}_thunk", function);
+    }
+    output.push_str("    .section .rodata\n    .align 8\n");
+    for (index, string) in program.strings.iter().enumerate() {
+        let _ = writeln!(
+            output,
+            "__lang_string_{}: .quad {}\n    .byte {}",
+            index,
+            string.len(),
+            string
+                .bytes()
+                .map(|byte| byte.to_string())
+                .chain(std::iter::once("0".to_string()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        output.push_str("    .align 8\n");
+    }
+    output.push_str(&runtime::data());
+    output.push_str("    .bss\n");
+    output.push_str(&runtime::bss());
+    Ok(output)
+}
+
+fn emit_thunk(output: &mut String, thunk: &Thunk) {
+    let _ = writeln!(output, "{}_thunk:", thunk.function);
+    output.push_str("    push rbp\n    mov rbp, rsp\n");
+    for capture in 0..thunk.captures {
+        let _ = writeln!(output, "    push qword ptr [rdi + {}]", 8 * (capture + 1));
+    }
+    for register in INDIRECT_PARAMETERS
+        .iter()
+        .take(thunk.parameters.len() - thunk.captures)
+    {
+        let _ = writeln!(output, "    push {}", register);
+    }
+    let mut integers = INTEGER_PARAMETERS.iter();
+    let mut floats = 0..8;
+    let destinations = thunk
+        .parameters
+        .iter()
+        .map(|class| match class {
+            Class::Integer => integers.next().map(|register| register.to_string()),
+            Class::Float => floats.next().map(|register| format!("xmm{}", register)),
+        })
+        .collect::<Vec<_>>();
+    for destination in destinations.into_iter().rev().flatten() {
+        if destination.starts_with("xmm") {
+            let _ = writeln!(output, "    pop rax\n    movq {}, rax", destination);
+        } else {
+            let _ = writeln!(output, "    pop {}", destination);
+        }
+    }
+    let _ = writeln!(output, "    call {}", thunk.function);
+    if thunk.return_class == Class::Float {
+        output.push_str("    movq rax, xmm0\n");
+    }
+    output.push_str("    pop rbp\n    ret\n");
+}
@@ -0,0 +1,209 @@
+use std::collections::{HashMap, HashSet};
+
+use super::lir::{Class, Function, Vreg};
+
+pub const CALLEE_SAVED: [&str; 5] = ["rbx", "r12", "r13", "r14", "r15"];
+pub const CALLER_SAVED: [&str; 5] = ["rsi", "rdi", "r8", "r9", "rcx"];
+pub const FLOAT_REGISTERS: [&str; 6] = ["xmm8", "xmm9", "xmm10", "xmm11", "xmm12", "xmm13"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Location {
+    Register(&'static str),
+    Stack(usize),
+}
+pub struct Allocation {
+    pub locations: HashMap<Vreg, Location>,
+    pub slots: usize,
+    pub callee_saved: Vec<&'static str>,
+}
+#[derive(Clone, Copy, Debug)]
+struct Interval {
+    vreg: Vreg,
+    start: usize,
+    end: usize,
+    crosses_call: bool,
+}
+
+impl Allocation {
+    pub fn location(&self, vreg: Vreg) -> Option<Location> {
+        self.locations.get(&vreg).copied()
+    }
+}
+
+pub fn allocate(function: &Function) -> Allocation {
+    let mut intervals = intervals(function);
+    intervals.sort_by_key(|interval| (interval.start, interval.vreg));
+    let mut locations = HashMap::new();
+    let mut slots = 0;
+    let mut active: Vec<Interval> = vec![];
+    let mut free_integers = CALLER_SAVED
+        .iter()
+        .chain(CALLEE_SAVED.iter())
+        .copied()
+        .collect::<Vec<_>>();
+    let mut free_floats = FLOAT_REGISTERS.to_vec();
+    let mut used = HashSet::new();
+    for interval in intervals {
+        active.retain(|candidate| {
+            if candidate.end >= interval.start {
+                return true;
+            }
+            if let Some(Location::Register(register)) = locations.get(&candidate.vreg) {
+                match function.classes[candidate.vreg.0] {
+                    Class::Integer => free_integers.push(*register),
+                    Class::Float => free_floats.push(*register),
+                }
+            }
+            false
+        });
+        let class = function.classes[interval.vreg.0];
+        let acceptable = |register: &&str| match class {
+            Class::Integer => !interval.crosses_call || CALLEE_SAVED.contains(register),
+            Class::Float => !interval.crosses_call,
+        };
+        let pool = match class {
+            Class::Integer => &mut free_integers,
+            Class::Float => &mut free_floats,
+        };
+        if let Some(position) = pool.iter().position(acceptable) {
+            let register = pool.remove(position);
+            used.insert(register);
+            locations.insert(interval.vreg, Location::Register(register));
+            active.push(interval);
+            continue;
+        }
+        let victim = active
+            .iter()
+            .enumerate()
+            .filter(|(_, candidate)| {
+                function.classes[candidate.vreg.0] == class && candidate.end > interval.end
+            })
+            .filter(|(_, candidate)| match locations.get(&candidate.vreg) {
+                Some(Location::Register(register)) => acceptable(register),
+                _ => false,
+            })
+            .max_by_key(|(_, candidate)| candidate.end)
+            .map(|(index, _)| index);
+        match victim {
+            Some(index) => {
+                let victim = active.remove(index);
+                let register = locations[&victim.vreg];
+                locations.insert(victim.vreg, Location::Stack(slots));
+                slots += 1;
+                locations.insert(interval.vreg, register);
+                active.push(interval);
+            }
+            None => {
+                locations.insert(interval.vreg, Location::Stack(slots));
+                slots += 1;
+            }
+        }
+    }
+    Allocation {
+        locations,
+        slots,
+        callee_saved: CALLEE_SAVED
+            .iter()
+            .copied()
+            .filter(|register| used.contains(register))
+            .collect(),
+    }
+}
+
+fn intervals(function: &Function) -> Vec<Interval> {
+    let mut ranges: HashMap<Vreg, (usize, usize)> = HashMap::new();
+    let mut extend = |vreg: Vreg, position: usize| {
+        let range = ranges.entry(vreg).or_insert((position, position));
+        range.0 = range.0.min(position);
+        range.1 = range.1.max(position);
+    };
+    for parameter in &function.parameters {
+        extend(*parameter, 0);
+    }
+    let labels = function
+        .blocks
+        .iter()
+        .enumerate()
+        .map(|(index, block)| (block.label, index))
+        .collect::<HashMap<_, _>>();
+    let mut bounds = vec![];
+    let mut calls = vec![];
+    let mut position = 1;
+    for block in &function.blocks {
+        let start = position;
+        for op in &block.ops {
+            for vreg in op.uses() {
+                extend(vreg, position);
+            }
+            if let Some(vreg) = op.definition() {
+                extend(vreg, position);
+            }
+            if op.is_call() {
+                calls.push(position);
+            }
+            position += 1;
+        }
+        bounds.push((start, position - 1));
+    }
+    let (live_in, live_out) = liveness(function, &labels);
+    for (index, (start, end)) in bounds.iter().enumerate() {
+        for vreg in &live_in[index] {
+            extend(*vreg, *start);
+        }
+        for vreg in &live_out[index] {
+            extend(*vreg, *end);
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|(vreg, (start, end))| Interval {
+            vreg,
+            start,
+            end,
+            crosses_call: calls.iter().any(|call| start < *call && *call < end),
+        })
+        .collect()
+}
+type LiveSets = Vec<HashSet<Vreg>>;
+fn liveness(
+    function: &Function,
+    labels: &HashMap<super::lir::Label, usize>,
+) -> (LiveSets, LiveSets) {
+    let count = function.blocks.len();
+    let mut uses = vec![HashSet::new(); count];
+    let mut definitions = vec![HashSet::new(); count];
+    let mut successors = vec![vec![]; count];
+    for (index, block) in function.blocks.iter().enumerate() {
+        for op in &block.ops {
+            for vreg in op.uses() {
+                if !definitions[index].contains(&vreg) {
+                    uses[index].insert(vreg);
+                }
+            }
+            if let Some(vreg) = op.definition() {
+                definitions[index].insert(vreg);
+            }
+            successors[index].extend(op.successors().iter().filter_map(|label| labels.get(label)));
+        }
+    }
+    let mut live_in = vec![HashSet::new(); count];
+    let mut live_out = vec![HashSet::new(); count];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for index in (0..count).rev() {
+            let out = successors[index]
+                .iter()
+                .flat_map(|successor: &usize| live_in[*successor].iter().copied())
+                .collect::<HashSet<_>>();
+            let mut input = uses[index].clone();
+            input.extend(out.difference(&definitions[index]).copied());
+            if input != live_in[index] || out != live_out[index] {
+                live_in[index] = input;
+                live_out[index] = out;
+                changed = true;
+            }
+        }
+    }
+    (live_in, live_out)
+}
@@ -0,0 +1,837 @@
+use std::fmt::Write;
+
+use crate::stdlib::runtime::MAX_RANGE_LENGTH;
+
+const HEAP_SIZE: usize = 64 * 1024 * 1024;
+const CAPTURE_SIZE: usize = 1024 * 1024;
+const BIG_WORDS: usize = 20;
+const FLOAT_BIGNUMS: [&str; 5] = ["r", "s", "plus", "minus", "sum"];
+
+const PANICS: [(&str, &str); 9] = [
+    ("__lang_overflow", "integer overflow"),
+    ("__lang_division_by_zero", "division by zero"),
+    ("__lang_out_of_bounds", "index out of bounds"),
+    ("__lang_unreachable", "entered unreachable code"),
+    ("__lang_assertion_failed", "assertion failed"),
+    ("__lang_out_of_memory", "out of memory"),
+    ("__lang_range_too_large", "range too large"),
+    ("__lang_string_too_long", "string too long"),
+    ("__lang_format_mismatch", "wrong number of format arguments"),
+];
+
+const TEXT: &str = r#"    .globl _start
+_start:
+    call lang_main
+    xor edi, edi
+    mov eax, 60
+    syscall
+__lang_panic:
+    mov eax, 1
+    mov edi, 2
+    syscall
+    mov eax, 60
+    mov edi, 101
+    syscall
+__lang_exit:
+    mov eax, 60
+    syscall
+__lang_assert:
+    test rdi, rdi
+    jz __lang_assertion_failed
+    ret
+__lang_alloc:
+    mov rax, qword ptr [rip + __lang_heap_pointer]
+    test rax, rax
+    jnz .L__lang_alloc_ready
+    lea rax, [rip + __lang_heap]
+.L__lang_alloc_ready:
+    lea rdx, [rax + rdi]
+    lea rcx, [rip + __lang_heap + HEAP_SIZE]
+    cmp rdx, rcx
+    ja __lang_out_of_memory
+    mov qword ptr [rip + __lang_heap_pointer], rdx
+    ret
+__lang_array_copy:
+    push rbx
+    mov rbx, rdi
+    mov rdi, qword ptr [rbx]
+    lea rdi, [rdi * 8 + 8]
+    call __lang_alloc
+    mov rcx, qword ptr [rbx]
+    inc rcx
+    xor edx, edx
+.L__lang_array_copy_loop:
+    cmp rdx, rcx
+    jge .L__lang_array_copy_done
+    mov r8, qword ptr [rbx + rdx * 8]
+    mov qword ptr [rax + rdx * 8], r8
+    inc rdx
+    jmp .L__lang_array_copy_loop
+.L__lang_array_copy_done:
+    pop rbx
+    ret
+__lang_range:
+    push rbx
+    push r12
+    mov rbx, rdi
+    xor r12d, r12d
+    cmp rsi, rdi
+    jle .L__lang_range_allocate
+    mov r12, rsi
+    sub r12, rdi
+    cmp r12, MAX_RANGE_LENGTH
+    ja __lang_range_too_large
+.L__lang_range_allocate:
+    lea rdi, [r12 * 8 + 8]
+    call __lang_alloc
+    mov qword ptr [rax], r12
+    xor ecx, ecx
+.L__lang_range_loop:
+    cmp rcx, r12
+    jge .L__lang_range_done
+    mov qword ptr [rax + rcx * 8 + 8], rbx
+    inc rbx
+    inc rcx
+    jmp .L__lang_range_loop
+.L__lang_range_done:
+    pop r12
+    pop rbx
+    ret
+__lang_array_slice:
+    push rbx
+    push r12
+    push r13
+    mov rbx, rdi
+    mov rcx, qword ptr [rdi]
+    cmp rsi, rcx
+    ja __lang_out_of_bounds
+    cmp rdx, rcx
+    ja __lang_out_of_bounds
+    cmp rdx, rsi
+    jge .L__lang_array_slice_ordered
+    mov rdx, rsi
+.L__lang_array_slice_ordered:
+    mov r12, rsi
+    mov r13, rdx
+    sub r13, rsi
+    lea rdi, [r13 * 8 + 8]
+    call __lang_alloc
+    mov qword ptr [rax], r13
+    lea rsi, [rbx + r12 * 8 + 8]
+    xor ecx, ecx
+.L__lang_array_slice_loop:
+    cmp rcx, r13
+    jge .L__lang_array_slice_done
+    mov rdx, qword ptr [rsi + rcx * 8]
+    mov qword ptr [rax + rcx * 8 + 8], rdx
+    inc rcx
+    jmp .L__lang_array_slice_loop
+.L__lang_array_slice_done:
+    pop r13
+    pop r12
+    pop rbx
+    ret
+__lang_char_offset:
+    mov r8, qword ptr [rdi]
+    xor eax, eax
+    xor ecx, ecx
+.L__lang_char_offset_loop:
+    cmp rax, r8
+    jge .L__lang_char_offset_done
+    movzx edx, byte ptr [rdi + rax + 8]
+    and edx, 192
+    cmp edx, 128
+    je .L__lang_char_offset_next
+    cmp rcx, rsi
+    je .L__lang_char_offset_done
+    inc rcx
+.L__lang_char_offset_next:
+    inc rax
+    jmp .L__lang_char_offset_loop
+.L__lang_char_offset_done:
+    ret
+__lang_string_slice:
+    push rbx
+    push r12
+    push r13
+    mov rbx, rdi
+    mov r12, rsi
+    mov r13, rdx
+    mov rsi, -1
+    call __lang_char_offset
+    cmp r12, rcx
+    ja __lang_out_of_bounds
+    cmp r13, rcx
+    ja __lang_out_of_bounds
+    cmp r13, r12
+    jge .L__lang_string_slice_ordered
+    mov r13, r12
+.L__lang_string_slice_ordered:
+    mov rdi, rbx
+    mov rsi, r12
+    call __lang_char_offset
+    mov r12, rax
+    mov rdi, rbx
+    mov rsi, r13
+    call __lang_char_offset
+    mov r13, rax
+    sub r13, r12
+    lea rdi, [r13 + 15]
+    and rdi, -8
+    call __lang_alloc
+    mov qword ptr [rax], r13
+    lea rsi, [rbx + r12 + 8]
+    xor ecx, ecx
+.L__lang_string_slice_loop:
+    cmp rcx, r13
+    jge .L__lang_string_slice_done
+    movzx edx, byte ptr [rsi + rcx]
+    mov byte ptr [rax + rcx + 8], dl
+    inc rcx
+    jmp .L__lang_string_slice_loop
+.L__lang_string_slice_done:
+    pop r13
+    pop r12
+    pop rbx
+    ret
+__lang_write:
+    cmp qword ptr [rip + __lang_capturing], 0
+    jne .L__lang_write_capture
+    mov eax, 1
+    mov edi, 1
+    syscall
+    ret
+.L__lang_write_capture:
+    mov rax, qword ptr [rip + __lang_capture_length]
+    lea rcx, [rax + rdx]
+    cmp rcx, CAPTURE_SIZE
+    ja __lang_string_too_long
+    mov qword ptr [rip + __lang_capture_length], rcx
+    lea rdi, [rip + __lang_capture_buffer]
+    add rdi, rax
+    mov rcx, rdx
+    rep movsb
+    ret
+__lang_capture_begin:
+    mov qword ptr [rip + __lang_capturing], 1
+    mov qword ptr [rip + __lang_capture_length], 0
+    ret
+__lang_capture_end:
+    mov qword ptr [rip + __lang_capturing], 0
+    mov rdi, qword ptr [rip + __lang_capture_length]
+    add rdi, 15
+    and rdi, -8
+    call __lang_alloc
+    mov rcx, qword ptr [rip + __lang_capture_length]
+    mov qword ptr [rax], rcx
+    lea rdi, [rax + 8]
+    lea rsi, [rip + __lang_capture_buffer]
+    rep movsb
+    ret
+__lang_format_text:
+    push rbx
+    push r12
+    push r13
+    mov rbx, rdi
+    mov r12, rsi
+    mov r13, qword ptr [rdi]
+.L__lang_format_text_loop:
+    cmp r12, r13
+    jge .L__lang_format_text_end
+    lea rsi, [rbx + r12 + 8]
+    movzx eax, byte ptr [rsi]
+    lea rcx, [r12 + 1]
+    cmp rcx, r13
+    jge .L__lang_format_text_char
+    movzx ecx, byte ptr [rsi + 1]
+    cmp al, 123
+    jne .L__lang_format_text_close
+    cmp cl, 125
+    je .L__lang_format_text_placeholder
+    cmp cl, 123
+    je .L__lang_format_text_escape
+    jmp .L__lang_format_text_char
+.L__lang_format_text_close:
+    cmp al, 125
+    jne .L__lang_format_text_char
+    cmp cl, 125
+    jne .L__lang_format_text_char
+.L__lang_format_text_escape:
+    inc r12
+.L__lang_format_text_char:
+    mov edx, 1
+    call __lang_write
+    inc r12
+    jmp .L__lang_format_text_loop
+.L__lang_format_text_placeholder:
+    lea rax, [r12 + 2]
+    jmp .L__lang_format_text_done
+.L__lang_format_text_end:
+    mov rax, -1
+.L__lang_format_text_done:
+    pop r13
+    pop r12
+    pop rbx
+    ret
+__lang_format_next:
+    call __lang_format_text
+    test rax, rax
+    js __lang_format_mismatch
+    ret
+__lang_format_rest:
+    call __lang_format_text
+    test rax, rax
+    jns __lang_format_mismatch
+    jmp __lang_capture_end
+__lang_string_equal:
+    mov rcx, qword ptr [rdi]
+    xor eax, eax
+    cmp rcx, qword ptr [rsi]
+    jne .L__lang_string_equal_done
+    xor edx, edx
+.L__lang_string_equal_loop:
+    cmp rdx, rcx
+    jge .L__lang_string_equal_same
+    movzx r8d, byte ptr [rdi + rdx + 8]
+    cmp r8b, byte ptr [rsi + rdx + 8]
+    jne .L__lang_string_equal_done
+    inc rdx
+    jmp .L__lang_string_equal_loop
+.L__lang_string_equal_same:
+    mov eax, 1
+.L__lang_string_equal_done:
+    ret
+__lang_print_string:
+    lea rsi, [rdi + 8]
+    mov rdx, qword ptr [rdi]
+    jmp __lang_write
+__lang_print_unit:
+    lea rsi, [rip + __lang_unit_text]
+    mov edx, 2
+    jmp __lang_write
+__lang_print_space:
+    lea rsi, [rip + __lang_space_text]
+    mov edx, 1
+    jmp __lang_write
+__lang_print_newline:
+    lea rsi, [rip + __lang_newline_text]
+    mov edx, 1
+    jmp __lang_write
+__lang_print_bool:
+    test rdi, rdi
+    jz .L__lang_print_bool_false
+    lea rsi, [rip + __lang_true_text]
+    mov edx, 4
+    jmp __lang_write
+.L__lang_print_bool_false:
+    lea rsi, [rip + __lang_false_text]
+    mov edx, 5
+    jmp __lang_write
+__lang_print_int:
+    sub rsp, 40
+    mov rax, rdi
+    mov r8, rdi
+    lea rsi, [rsp + 32]
+    mov ecx, 10
+    test rax, rax
+    jns .L__lang_print_int_loop
+    neg rax
+.L__lang_print_int_loop:
+    xor edx, edx
+    div rcx
+    add dl, 48
+    dec rsi
+    mov byte ptr [rsi], dl
+    test rax, rax
+    jnz .L__lang_print_int_loop
+    test r8, r8
+    jns .L__lang_print_int_write
+    dec rsi
+    mov byte ptr [rsi], 45
+.L__lang_print_int_write:
+    lea rdx, [rsp + 32]
+    sub rdx, rsi
+    call __lang_write
+    add rsp, 40
+    ret
+__lang_big_set:
+    mov r8, rdi
+    xor eax, eax
+    mov ecx, BIG_WORDS
+    rep stosq
+    mov qword ptr [r8], rsi
+    ret
+__lang_big_shl:
+    test rsi, rsi
+    jz .L__lang_big_shl_done
+.L__lang_big_shl_bit:
+    mov rdx, rdi
+    mov ecx, BIG_WORDS
+    clc
+.L__lang_big_shl_word:
+    rcl qword ptr [rdx], 1
+    lea rdx, [rdx + 8]
+    dec ecx
+    jnz .L__lang_big_shl_word
+    dec rsi
+    jnz .L__lang_big_shl_bit
+.L__lang_big_shl_done:
+    ret
+__lang_big_mul:
+    mov r8, rsi
+    xor r9d, r9d
+    xor ecx, ecx
+.L__lang_big_mul_word:
+    mov rax, qword ptr [rdi + rcx * 8]
+    mul r8
+    add rax, r9
+    adc rdx, 0
+    mov qword ptr [rdi + rcx * 8], rax
+    mov r9, rdx
+    inc rcx
+    cmp rcx, BIG_WORDS
+    jl .L__lang_big_mul_word
+    ret
+__lang_big_add:
+    mov ecx, BIG_WORDS
+    xor r8d, r8d
+.L__lang_big_add_word:
+    mov rax, qword ptr [rsi + r8]
+    adc rax, qword ptr [rdx + r8]
+    mov qword ptr [rdi + r8], rax
+    lea r8, [r8 + 8]
+    dec ecx
+    jnz .L__lang_big_add_word
+    ret
+__lang_big_sub:
+    mov ecx, BIG_WORDS
+    xor r8d, r8d
+.L__lang_big_sub_word:
+    mov rax, qword ptr [rsi + r8]
+    sbb qword ptr [rdi + r8], rax
+    lea r8, [r8 + 8]
+    dec ecx
+    jnz .L__lang_big_sub_word
+    ret
+__lang_big_cmp:
+    mov ecx, BIG_WORDS - 1
+.L__lang_big_cmp_word:
+    mov rax, qword ptr [rdi + rcx * 8]
+    cmp rax, qword ptr [rsi + rcx * 8]
+    ja .L__lang_big_cmp_greater
+    jb .L__lang_big_cmp_less
+    dec rcx
+    jns .L__lang_big_cmp_word
+    xor eax, eax
+    ret
+.L__lang_big_cmp_greater:
+    mov eax, 1
+    ret
+.L__lang_big_cmp_less:
+    mov eax, -1
+    ret
+__lang_float_scale:
+    lea rdi, [rip + __lang_float_r]
+    mov esi, 10
+    call __lang_big_mul
+    lea rdi, [rip + __lang_float_plus]
+    mov esi, 10
+    call __lang_big_mul
+    lea rdi, [rip + __lang_float_minus]
+    mov esi, 10
+    call __lang_big_mul
+    ret
+__lang_float_high:
+    lea rdi, [rip + __lang_float_sum]
+    lea rsi, [rip + __lang_float_r]
+    lea rdx, [rip + __lang_float_plus]
+    call __lang_big_add
+    lea rdi, [rip + __lang_float_sum]
+    lea rsi, [rip + __lang_float_s]
+    call __lang_big_cmp
+    add eax, r15d
+    ret
+__lang_print_float:
+    push rbx
+    push r12
+    push r13
+    push r14
+    push r15
+    sub rsp, 96
+    ucomisd xmm0, xmm0
+    jp .L__lang_print_float_nan
+    movq rax, xmm0
+    btr rax, 63
+    jnc .L__lang_print_float_positive
+    mov rbx, rax
+    mov byte ptr [rsp], 45
+    mov rsi, rsp
+    mov edx, 1
+    call __lang_write
+    mov rax, rbx
+.L__lang_print_float_positive:
+    mov rcx, 0x7ff0000000000000
+    cmp rax, rcx
+    je .L__lang_print_float_infinity
+    test rax, rax
+    jz .L__lang_print_float_zero
+    mov rbx, rax
+    shr rbx, 52
+    mov r12, 0xfffffffffffff
+    and r12, rax
+    mov r13, -1074
+    test rbx, rbx
+    jz .L__lang_print_float_decoded
+    bts r12, 52
+    lea r13, [rbx - 1075]
+.L__lang_print_float_decoded:
+    xor r14d, r14d
+    mov rax, 0x10000000000000
+    cmp r12, rax
+    jne .L__lang_print_float_interior
+    cmp rbx, 1
+    jbe .L__lang_print_float_interior
+    mov r14d, 1
+.L__lang_print_float_interior:
+    mov r15, r12
+    not r15
+    and r15, 1
+    xor ecx, ecx
+    mov rax, r13
+    test rax, rax
+    cmovs rax, rcx
+    mov qword ptr [rsp + 80], rax
+    mov rax, r13
+    neg rax
+    cmovs rax, rcx
+    mov qword ptr [rsp + 88], rax
+    bsr rcx, r12
+    lea rax, [r13 + rcx]
+    imul rbx, rax, 78913
+    sar rbx, 18
+    lea rdi, [rip + __lang_float_r]
+    mov rsi, r12
+    call __lang_big_set
+    lea rdi, [rip + __lang_float_r]
+    mov rsi, qword ptr [rsp + 80]
+    lea rsi, [rsi + r14 + 1]
+    call __lang_big_shl
+    lea rdi, [rip + __lang_float_s]
+    mov esi, 1
+    call __lang_big_set
+    lea rdi, [rip + __lang_float_s]
+    mov rsi, qword ptr [rsp + 88]
+    lea rsi, [rsi + r14 + 1]
+    call __lang_big_shl
+    lea rdi, [rip + __lang_float_plus]
+    mov esi, 1
+    call __lang_big_set
+    lea rdi, [rip + __lang_float_plus]
+    mov rsi, qword ptr [rsp + 80]
+    add rsi, r14
+    call __lang_big_shl
+    lea rdi, [rip + __lang_float_minus]
+    mov esi, 1
+    call __lang_big_set
+    lea rdi, [rip + __lang_float_minus]
+    mov rsi, qword ptr [rsp + 80]
+    call __lang_big_shl
+    mov r12, rbx
+    test r12, r12
+    js .L__lang_print_float_scale_value
+.L__lang_print_float_scale_s:
+    test r12, r12
+    jz .L__lang_print_float_fixup
+    lea rdi, [rip + __lang_float_s]
+    mov esi, 10
+    call __lang_big_mul
+    dec r12
+    jmp .L__lang_print_float_scale_s
+.L__lang_print_float_scale_value:
+    neg r12
+.L__lang_print_float_scale_value_loop:
+    call __lang_float_scale
+    dec r12
+    jnz .L__lang_print_float_scale_value_loop
+.L__lang_print_float_fixup:
+    call __lang_float_high
+    test eax, eax
+    jle .L__lang_print_float_generate
+    lea rdi, [rip + __lang_float_s]
+    mov esi, 10
+    call __lang_big_mul
+    inc rbx
+    jmp .L__lang_print_float_fixup
+.L__lang_print_float_generate:
+    xor r13d, r13d
+.L__lang_print_float_digit:
+    call __lang_float_scale
+    xor r12d, r12d
+.L__lang_print_float_divide:
+    lea rdi, [rip + __lang_float_r]
+    lea rsi, [rip + __lang_float_s]
+    call __lang_big_cmp
+    test eax, eax
+    js .L__lang_print_float_divided
+    lea rdi, [rip + __lang_float_r]
+    lea rsi, [rip + __lang_float_s]
+    call __lang_big_sub
+    inc r12
+    jmp .L__lang_print_float_divide
+.L__lang_print_float_divided:
+    lea rdi, [rip + __lang_float_r]
+    lea rsi, [rip + __lang_float_minus]
+    call __lang_big_cmp
+    xor r14d, r14d
+    cmp eax, r15d
+    setl r14b
+    call __lang_float_high
+    test eax, eax
+    jg .L__lang_print_float_high
+    test r14, r14
+    jnz .L__lang_print_float_last
+    lea eax, [r12 + 48]
+    mov byte ptr [rsp + r13 + 48], al
+    inc r13
+    jmp .L__lang_print_float_digit
+.L__lang_print_float_high:
+    test r14, r14
+    jz .L__lang_print_float_round_up
+    lea rdi, [rip + __lang_float_sum]
+    lea rsi, [rip + __lang_float_r]
+    lea rdx, [rip + __lang_float_r]
+    call __lang_big_add
+    lea rdi, [rip + __lang_float_sum]
+    lea rsi, [rip + __lang_float_s]
+    call __lang_big_cmp
+    test eax, eax
+    js .L__lang_print_float_last
+.L__lang_print_float_round_up:
+    inc r12
+.L__lang_print_float_last:
+    lea eax, [r12 + 48]
+    mov byte ptr [rsp + r13 + 48], al
+    inc r13
+    xor r8d, r8d
+    cmp rbx, -3
+    jl .L__lang_print_float_scientific
+    cmp rbx, 16
+    jg .L__lang_print_float_scientific
+    xor ecx, ecx
+    test rbx, rbx
+    jg .L__lang_print_float_integer
+    mov word ptr [rsp], 0x2e30
+    mov r8d, 2
+    mov rdx, rbx
+.L__lang_print_float_leading:
+    test rdx, rdx
+    jz .L__lang_print_float_fraction
+    mov byte ptr [rsp + r8], 48
+    inc r8
+    inc rdx
+    jmp .L__lang_print_float_leading
+.L__lang_print_float_integer:
+    cmp rcx, rbx
+    jge .L__lang_print_float_point
+    mov al, 48
+    cmp rcx, r13
+    jge .L__lang_print_float_integer_store
+    mov al, byte ptr [rsp + rcx + 48]
+.L__lang_print_float_integer_store:
+    mov byte ptr [rsp + r8], al
+    inc r8
+    inc rcx
+    jmp .L__lang_print_float_integer
+.L__lang_print_float_point:
+    mov byte ptr [rsp + r8], 46
+    inc r8
+    cmp rcx, r13
+    jl .L__lang_print_float_fraction
+    mov byte ptr [rsp + r8], 48
+    inc r8
+.L__lang_print_float_fraction:
+    cmp rcx, r13
+    jge .L__lang_print_float_write
+    mov al, byte ptr [rsp + rcx + 48]
+    mov byte ptr [rsp + r8], al
+    inc r8
+    inc rcx
+    jmp .L__lang_print_float_fraction
+.L__lang_print_float_write:
+    mov rsi, rsp
+    mov rdx, r8
+    call __lang_write
+    jmp .L__lang_print_float_done
+.L__lang_print_float_scientific:
+    mov al, byte ptr [rsp + 48]
+    mov byte ptr [rsp], al
+    mov r8d, 1
+    mov ecx, 1
+    cmp r13, 1
+    jle .L__lang_print_float_exponent
+    mov byte ptr [rsp + 1], 46
+    mov r8d, 2
+.L__lang_print_float_mantissa:
+    cmp rcx, r13
+    jge .L__lang_print_float_exponent
+    mov al, byte ptr [rsp + rcx + 48]
+    mov byte ptr [rsp + r8], al
+    inc r8
+    inc rcx
+    jmp .L__lang_print_float_mantissa
+.L__lang_print_float_exponent:
+    mov byte ptr [rsp + r8], 101
+    inc r8
+    mov rsi, rsp
+    mov rdx, r8
+    call __lang_write
+    lea rdi, [rbx - 1]
+    call __lang_print_int
+    jmp .L__lang_print_float_done
+.L__lang_print_float_zero:
+    lea rsi, [rip + __lang_zero_text]
+    mov edx, 3
+    call __lang_write
+    jmp .L__lang_print_float_done
+.L__lang_print_float_nan:
+    lea rsi, [rip + __lang_nan_text]
+    mov edx, 3
+    call __lang_write
+    jmp .L__lang_print_float_done
+.L__lang_print_float_infinity:
+    lea rsi, [rip + __lang_infinity_text]
+    mov edx, 3
+    call __lang_write
+.L__lang_print_float_done:
+    add rsp, 96
+    pop r15
+    pop r14
+    pop r13
+    pop r12
+    pop rbx
+    ret
+"#;
+
+const DATA: &str = r#"__lang_unit_text: .ascii "()"
+__lang_space_text: .ascii " "
+__lang_newline_text: .ascii "\n"
+__lang_true_text: .ascii "true"
+__lang_false_text: .ascii "false"
+__lang_zero_text: .ascii "0.0"
+__lang_nan_text: .ascii "NaN"
+__lang_infinity_text: .ascii "inf"
+__lang_open_text: .ascii "["
+__lang_separator_text: .ascii ", "
+__lang_close_text: .ascii "]"
+"#;
+
+fn case_mapper(output: &mut String, name: &str, first: u8) {
+    let _ = write!(
+        output,
+        r#"__lang_string_{name}:
+    push rbx
+    mov rbx, rdi
+    mov rdi, qword ptr [rdi]
+    add rdi, 15
+    and rdi, -8
+    call __lang_alloc
+    mov rcx, qword ptr [rbx]
+    mov qword ptr [rax], rcx
+    xor edx, edx
+.L__lang_string_{name}_loop:
+    cmp rdx, rcx
+    jge .L__lang_string_{name}_done
+    movzx r8d, byte ptr [rbx + rdx + 8]
+    lea r9d, [r8 - {first}]
+    cmp r9d, 25
+    ja .L__lang_string_{name}_store
+    xor r8d, 32
+.L__lang_string_{name}_store:
+    mov byte ptr [rax + rdx + 8], r8b
+    inc rdx
+    jmp .L__lang_string_{name}_loop
+.L__lang_string_{name}_done:
+    pop rbx
+    ret
+"#
+    );
+}
+
+fn array_printer(output: &mut String, name: &str, load: &str, routine: &str) {
+    let _ = write!(
+        output,
+        r#"__lang_print_{name}_array:
+    push rbx
+    push r12
+    push r13
+    mov rbx, rdi
+    mov r12, qword ptr [rdi]
+    xor r13, r13
+    lea rsi, [rip + __lang_open_text]
+    mov edx, 1
+    call __lang_write
+.L__lang_print_{name}_array_loop:
+    cmp r13, r12
+    jge .L__lang_print_{name}_array_done
+    test r13, r13
+    jz .L__lang_print_{name}_array_element
+    lea rsi, [rip + __lang_separator_text]
+    mov edx, 2
+    call __lang_write
+.L__lang_print_{name}_array_element:
+    {load}, qword ptr [rbx + r13 * 8 + 8]
+    call {routine}
+    inc r13
+    jmp .L__lang_print_{name}_array_loop
+.L__lang_print_{name}_array_done:
+    lea rsi, [rip + __lang_close_text]
+    mov edx, 1
+    call __lang_write
+    pop r13
+    pop r12
+    pop rbx
+    ret
+"#
+    );
+}
+
+pub fn text() -> String {
+    let mut output = TEXT
+        .replace("HEAP_SIZE", &HEAP_SIZE.to_string())
+        .replace("CAPTURE_SIZE", &CAPTURE_SIZE.to_string())
+        .replace("BIG_WORDS", &BIG_WORDS.to_string())
+        .replace("MAX_RANGE_LENGTH", &MAX_RANGE_LENGTH.to_string());
+    for (routine, message) in PANICS {
+        let _ = write!(
+            output,
+            "{routine}:\n    lea rsi, [rip + {routine}_text]\n    mov edx, {}\n    jmp __lang_panic\n",
+            message.len() + 8
+        );
+    }
+    array_printer(&mut output, "int", "mov rdi", "__lang_print_int");
+    array_printer(&mut output, "float", "movq xmm0", "__lang_print_float");
+    array_printer(&mut output, "bool", "mov rdi", "__lang_print_bool");
+    case_mapper(&mut output, "upper", b'a');
+    case_mapper(&mut output, "lower", b'A');
+    output
+}
+
+pub fn data() -> String {
+    let mut output = DATA.to_string();
+    for (routine, message) in PANICS {
+        let _ = writeln!(output, "{}_text: .ascii \"panic: {}\\n\"", routine, message);
+    }
+    output
+}
+
+pub fn bss() -> String {
+    let mut output = format!(
+        "    .align 16\n__lang_heap_pointer: .zero 8\n__lang_capturing: .zero 8\n\
+         __lang_capture_length: .zero 8\n__lang_heap: .zero {}\n__lang_capture_buffer: .zero {}\n",
+        HEAP_SIZE, CAPTURE_SIZE
+    );
+    for bignum in FLOAT_BIGNUMS {
+        let _ = writeln!(output, "__lang_float_{}: .zero {}", bignum, BIG_WORDS * 8);
+    }
+    output
+}
@@ -0,0 +1,527 @@
+use crate::{
+    common::{
+        diagnostic::{Diagnostic, DiagnosticSink},
+        error::{Error, ErrorKind, Result},
+        span::Span,
+        suggest::closest,
+    },
+    lexer::KEYWORDS,
+    parser::ast::{
+        calculate_span::CalculateSpan,
+        expression::{Expression, MatchArm, Operator},
+        function::Function,
+        node::Node,
+        pattern::Pattern,
+        spanned::Spanned,
+        statement::Statement,
+    },
+    stdlib::{self, BUILTINS},
+};
+
+use super::Checker;
+
+#[derive(Clone, Debug)]
+struct Binding<'a> {
+    name: &'a str,
+    mutable: bool,
+    reassigned: bool,
+    span: Span,
+}
+#[derive(Clone, Debug, Default)]
+struct State {
+    definite: Vec<bool>,
+    possible: Vec<bool>,
+}
+#[derive(Debug, Default)]
+pub(super) struct Bindings<'a> {
+    bindings: Vec<Binding<'a>>,
+    scopes: Vec<Vec<usize>>,
+    state: State,
+}
+
+impl State {
+    fn merge(&mut self, other: &State) {
+        for (definite, other) in self.definite.iter_mut().zip(&other.definite) {
+            *definite &= *other;
+        }
+        for (possible, other) in self.possible.iter_mut().zip(&other.possible) {
+            *possible |= *other;
+        }
+    }
+    fn diverge(&mut self) {
+        self.definite
+            .iter_mut()
+            .for_each(|definite| *definite = true);
+    }
+}
+impl<'a> Bindings<'a> {
+    fn declare(&mut self, name: &'a str, mutable: bool, initialized: bool, span: Span) {
+        self.bindings.push(Binding {
+            name,
+            mutable,
+            reassigned: false,
+            span,
+        });
+        self.state.definite.push(initialized);
+        self.state.possible.push(initialized);
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(self.bindings.len() - 1);
+        }
+    }
+    fn lookup(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .copied()
+            .find(|index| self.bindings[*index].name == name)
+    }
+    fn restore(&mut self, state: &State) {
+        let length = self.state.definite.len();
+        self.state.definite[..state.definite.len()].copy_from_slice(&state.definite);
+        self.state.possible[..state.possible.len()].copy_from_slice(&state.possible);
+        self.state.definite.truncate(length);
+        self.state.possible.truncate(length);
+    }
+}
+
+impl<'a, 'p> Checker<'a, 'p> {
+    pub(super) fn check_bindings(
+        &self,
+        function: &Function<'a>,
+        diagnostics: &mut DiagnosticSink<'a>,
+    ) -> Result<'a, ()> {
+        let mut bindings = Bindings::default();
+        bindings.scopes.push(vec![]);
+        for argument in &function.arguments {
+            bindings.declare(argument.name, false, true, argument.span);
+        }
+        self.visit(&function.body, &mut bindings, diagnostics)
+    }
+    fn error(&self, kind: ErrorKind, span: Span) -> Box<Error<'a>> {
+        Box::new(Error::new(kind, span, self.source))
+    }
+    fn resolve(
+        &self,
+        name: &Spanned<&'a str>,
+        kind: ErrorKind,
+        bindings: &Bindings<'a>,
+        diagnostics: &mut DiagnosticSink<'a>,
+    ) {
+        let functions = self.program.functions.iter().map(|function| function.name);
+        if name.value.contains("::")
+            || stdlib::lookup(name.value).is_some()
+            || functions.clone().any(|function| function == name.value)
+        {
+            return;
+        }
+        let names = bindings
+            .scopes
+            .iter()
+            .flatten()
+            .map(|index| bindings.bindings[*index].name)
+            .chain(functions)
+            .chain(BUILTINS.iter().map(|builtin| builtin.name));
+        let mut diagnostic = Diagnostic::error(kind, name.span, self.source);
+        let keywords = KEYWORDS
+            .iter()
+            .map(|(keyword, _)| *keyword)
+            .filter(|keyword| keyword.len() > 1);
+        if let Some(similar) = closest(name.value, names).or_else(|| closest(name.value, keywords))
+        {
+            diagnostic = diagnostic.with_suggestion(
+                format!("did you mean `{}`", similar),
+                name.span,
+                similar,
+            );
+        }
+        diagnostics.emit(diagnostic);
+    }
+    fn immutable_assignment(&self, binding: &Binding<'a>, span: Span) -> Diagnostic<'a> {
+        let diagnostic = Diagnostic::error(ErrorKind::ImmutableAssignment, span, self.source)
+            .with_label(
+                binding.span,
+                format!("`{}` is declared immutable here", binding.name),
+            );
+        match self.let_keyword(binding.span) {
+            Some(end) => {
+                diagnostic.with_suggestion("make the binding mutable", Span::new(end, end), " mut")
+            }
+            None => diagnostic.with_note("arguments and pattern bindings cannot be reassigned"),
+        }
+    }
+    fn let_keyword(&self, span: Span) -> Option<usize> {
+        (Span::new(span.start, span.start + 3).slice(self.source.code) == "let")
+            .then_some(span.start + 3)
+    }
+    fn mut_keyword(&self, span: Span) -> Option<Span> {
+        let end = self.let_keyword(span)?;
+        let offset = span
+            .slice(self.source.code)
+            .chars()
+            .skip(3)
+            .position(|char| !char.is_whitespace())?;
+        let start = end + offset;
+        (Span::new(start, start + 3).slice(self.source.code) == "mut")
+            .then_some(Span::new(end, start + 3))
+    }
+    fn scoped(
+        &self,
+        bindings: &mut Bindings<'a>,
+        diagnostics: &mut DiagnosticSink<'a>,
+        visit: impl FnOnce(&Self, &mut Bindings<'a>, &mut DiagnosticSink<'a>) -> Result<'a, ()>,
+    ) -> Result<'a, ()> {
+        bindings.scopes.push(vec![]);
+        let result = visit(self, bindings, diagnostics);
+        for index in bindings.scopes.pop().unwrap_or_default() {
+            let binding = &bindings.bindings[index];
+            if result.is_ok() && binding.mutable && !binding.reassigned {
+                let mut diagnostic =
+                    Diagnostic::warning(ErrorKind::UnusedMut, binding.span, self.source);
+                if let Some(span) = self.mut_keyword(binding.span) {
+                    diagnostic = diagnostic.with_suggestion("remove `mut`", span, "");
+                }
+                diagnostics.emit(diagnostic);
+            }
+        }
+        result
+    }
+    fn branch(
+        &self,
+        node: &Node<'a>,
+        before: &State,
+        bindings: &mut Bindings<'a>,
+        diagnostics: &mut DiagnosticSink<'a>,
+    ) -> Result<'a, State> {
+        bindings.restore(before);
+        self.visit(node, bindings, diagnostics)?;
+        Ok(bindings.state.clone())
+    }
+    fn visit(
+        &self,
+        node: &Node<'a>,
+        bindings: &mut Bindings<'a>,
+        diagnostics: &mut DiagnosticSink<'a>,
+    ) -> Result<'a, ()> {
+        match node {
+            Node::Integer(_) | Node::Float(_) | Node::String(_) => Ok(()),
+            Node::Identifier(identifier) => match bindings.lookup(identifier.value) {
+                Some(index) if !bindings.state.definite[index] => {
+                    Err(self.error(ErrorKind::UninitializedVariable, identifier.span))
+                }
+                Some(_) => Ok(()),
+                None => {
+                    self.resolve(
+                        identifier,
+                        ErrorKind::UndefinedVariable,
+                        bindings,
+                        diagnostics,
+                    );
+                    Ok(())
+                }
+            },
+            Node::Block(block) => {
+                self.scoped(bindings, diagnostics, |checker, bindings, diagnostics| {
+                    for statement in &block.value {
+                        checker.visit(statement, bindings, diagnostics)?;
+                    }
+                    Ok(())
+                })
+            }
+            Node::Array(array) => {
+                for element in &array.value {
+                    self.visit(element, bindings, diagnostics)?;
+                }
+                Ok(())
+            }
+            Node::Expression(expression) => {
+                self.visit_expression(expression, bindings, diagnostics)
+            }
+            Node::Statement(statement) => match statement {
+                Statement::While(while_statement) => {
+                    self.visit(&while_statement.value.test, bindings, diagnostics)?;
+                    let before = bindings.state.clone();
+                    let mut assigned = vec![];
+                    assignments(&while_statement.value.body, &mut assigned);
+                    for name in assigned {
+                        if let Some(index) = bindings.lookup(name) {
+                            bindings.state.possible[index] = true;
+                        }
+                    }
+                    let body = self.branch(
+                        &while_statement.value.body,
+                        &bindings.state.clone(),
+                        bindings,
+                        diagnostics,
+                    )?;
+                    bindings.restore(&before);
+                    bindings.state.merge(&body);
+                    Ok(())
+                }
+                Statement::For(for_statement) => {
+                    let for_statement = &for_statement.value;
+                    self.visit(&for_statement.iterable, bindings, diagnostics)?;
+                    let before = bindings.state.clone();
+                    let mut assigned = vec![];
+                    assignments(&for_statement.body, &mut assigned);
+                    for name in assigned {
+                        if let Some(index) = bindings.lookup(name) {
+                            bindings.state.possible[index] = true;
+                        }
+                    }
+                    self.scoped(bindings, diagnostics, |checker, bindings, diagnostics| {
+                        let binding = &for_statement.binding;
+                        bindings.declare(binding.value, false, true, binding.span);
+                        checker.visit(&for_statement.body, bindings, diagnostics)
+                    })?;
+                    let body = bindings.state.clone();
+                    bindings.restore(&before);
+                    bindings.state.merge(&body);
+                    Ok(())
+                }
+                Statement::If(if_statement) => {
+                    self.visit(&if_statement.value.test, bindings, diagnostics)?;
+                    let before = bindings.state.clone();
+                    let mut state = self.branch(
+                        &if_statement.value.consequent,
+                        &before,
+                        bindings,
+                        diagnostics,
+                    )?;
+                    let alternative = match &if_statement.value.alternative {
+                        Some(alternative) => {
+                            self.branch(alternative, &before, bindings, diagnostics)?
+                        }
+                        None => before,
+                    };
+                    state.merge(&alternative);
+                    bindings.restore(&state);
+                    Ok(())
+                }
+                Statement::Let(let_statement) => {
+                    if let Some(init) = &let_statement.value.init {
+                        self.visit(init, bindings, diagnostics)?;
+                    }
+                    bindings.declare(
+                        let_statement.value.name,
+                        let_statement.value.mutable,
+                        let_statement.value.init.is_some(),
+                        let_statement.span,
+                    );
+                    Ok(())
+                }
+                Statement::Return(return_statement) => {
+                    if let Some(value) = &return_statement.value.value {
+                        self.visit(value, bindings, diagnostics)?;
+                    }
+                    bindings.state.diverge();
+                    Ok(())
+                }
+            },
+        }
+    }
+    fn visit_expression(
+        &self,
+        expression: &Expression<'a>,
+        bindings: &mut Bindings<'a>,
+        diagnostics: &mut DiagnosticSink<'a>,
+    ) -> Result<'a, ()> {
+        match expression {
+            Expression::Infix {
+                operator: Operator::Assignment,
+                lhs,
+                rhs,
+            } => {
+                self.visit(rhs, bindings, diagnostics)?;
+                self.assign(lhs, expression.calculate_span(), bindings, diagnostics)
+            }
+            Expression::Infix { lhs, rhs, .. } => {
+                self.visit(lhs, bindings, diagnostics)?;
+                self.visit(rhs, bindings, diagnostics)
+            }
+            Expression::Prefix { value, .. } => self.visit(value, bindings, diagnostics),
+            Expression::Call {
+                callee, arguments, ..
+            } => {
+                match callee.as_ref() {
+                    Node::Identifier(name) if bindings.lookup(name.value).is_none() => {
+                        self.resolve(name, ErrorKind::UndefinedFunction, bindings, diagnostics)
+                    }
+                    _ => self.visit(callee, bindings, diagnostics)?,
+                }
+                for argument in &arguments.value {
+                    self.visit(argument, bindings, diagnostics)?;
+                }
+                Ok(())
+            }
+            Expression::Member { target, .. } => self.visit(target, bindings, diagnostics),
+            Expression::Lambda { parameters, body } => {
+                let before = bindings.state.clone();
+                let result =
+                    self.scoped(bindings, diagnostics, |checker, bindings, diagnostics| {
+                        for parameter in &parameters.value {
+                            bindings.declare(
+                                parameter.name.value,
+                                false,
+                                true,
+                                parameter.name.span,
+                            );
+                        }
+                        checker.visit(body, bindings, diagnostics)
+                    });
+                bindings.restore(&before);
+                result
+            }
+            Expression::Index { target, index } => {
+                self.visit(target, bindings, diagnostics)?;
+                self.visit(&index.value, bindings, diagnostics)
+            }
+            Expression::Match { value, arms, .. } => {
+                self.visit(value, bindings, diagnostics)?;
+                let before = bindings.state.clone();
+                let mut state: Option<State> = None;
+                for MatchArm { pattern, body } in &arms.value {
+                    bindings.restore(&before);
+                    self.scoped(bindings, diagnostics, |checker, bindings, diagnostics| {
+                        declare_pattern(pattern, bindings);
+                        checker.visit(body, bindings, diagnostics)
+                    })?;
+                    match &mut state {
+                        Some(state) => state.merge(&bindings.state),
+                        None => state = Some(bindings.state.clone()),
+                    }
+                }
+                bindings.restore(&state.unwrap_or(before));
+                Ok(())
+            }
+            Expression::Ternary {
+                test,
+                consequent,
+                alternative,
+            } => {
+                self.visit(test, bindings, diagnostics)?;
+                let before = bindings.state.clone();
+                let mut state = self.branch(consequent, &before, bindings, diagnostics)?;
+                state.merge(&self.branch(alternative, &before, bindings, diagnostics)?);
+                bindings.restore(&state);
+                Ok(())
+            }
+            Expression::Range { start, end, .. } => {
+                self.visit(start, bindings, diagnostics)?;
+                self.visit(end, bindings, diagnostics)
+            }
+        }
+    }
+    fn assign(
+        &self,
+        target: &Node<'a>,
+        span: Span,
+        bindings: &mut Bindings<'a>,
+        diagnostics: &mut DiagnosticSink<'a>,
+    ) -> Result<'a, ()> {
+        match target {
+            Node::Identifier(identifier) => {
+                let Some(index) = bindings.lookup(identifier.value) else {
+                    self.resolve(
+                        identifier,
+                        ErrorKind::UndefinedVariable,
+                        bindings,
+                        diagnostics,
+                    );
+                    return Ok(());
+                };
+                let possible = bindings.state.possible[index];
+                if !bindings.bindings[index].mutable && possible {
+                    diagnostics.emit(self.immutable_assignment(&bindings.bindings[index], span));
+                }
+                if possible {
+                    bindings.bindings[index].reassigned = true;
+                }
+                bindings.state.definite[index] = true;
+                bindings.state.possible[index] = true;
+                Ok(())
+            }
+            Node::Expression(Expression::Index { target, index }) => {
+                self.visit(&index.value, bindings, diagnostics)?;
+                self.visit(target, bindings, diagnostics)?;
+                let Some(root) = root(target) else {
+                    return Ok(());
+                };
+                let Some(index) = bindings.lookup(root) else {
+                    return Ok(());
+                };
+                if !bindings.bindings[index].mutable {
+                    diagnostics.emit(self.immutable_assignment(&bindings.bindings[index], span));
+                }
+                bindings.bindings[index].reassigned = true;
+                Ok(())
+            }
+            _ => self.visit(target, bindings, diagnostics),
+        }
+    }
+}
+
+fn root<'a>(node: &Node<'a>) -> Option<&'a str> {
+    match node {
+        Node::Identifier(identifier) => Some(identifier.value),
+        Node::Expression(Expression::Index { target, .. }) => root(target),
+        _ => None,
+    }
+}
+fn declare_pattern<'a>(pattern: &Pattern<'a>, bindings: &mut Bindings<'a>) {
+    match pattern {
+        Pattern::Binding(binding) => bindings.declare(binding.value, false, true, binding.span),
+        Pattern::Variant {
+            fields: Some(fields),
+            ..
+        } => {
+            for field in &fields.value {
+                declare_pattern(field, bindings);
+            }
+        }
+        _ => {}
+    }
+}
+fn assignments<'a>(node: &Node<'a>, names: &mut Vec<&'a str>) {
+    match node {
+        Node::Block(block) => block
+            .value
+            .iter()
+            .for_each(|statement| assignments(statement, names)),
+        Node::Expression(Expression::Infix {
+            operator: Operator::Assignment,
+            lhs,
+            rhs,
+        }) => {
+            if let Some(name) = root(lhs) {
+                names.push(name);
+            }
+            assignments(rhs, names);
+        }
+        Node::Statement(Statement::If(if_statement)) => {
+            assignments(&if_statement.value.consequent, names);
+            if let Some(alternative) = &if_statement.value.alternative {
+                assignments(alternative, names);
+            }
+        }
+        Node::Statement(Statement::While(while_statement)) => {
+            assignments(&while_statement.value.body, names)
+        }
+        Node::Statement(Statement::For(for_statement)) => {
+            assignments(&for_statement.value.body, names)
+        }
+        Node::Expression(Expression::Match { arms, .. }) => arms
+            .value
+            .iter()
+            .for_each(|arm| assignments(&arm.body, names)),
+        Node::Expression(Expression::Ternary {
+            consequent,
+            alternative,
+            ..
+        }) => {
+            assignments(consequent, names);
+            assignments(alternative, names);
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{
+    common::error::{Error, ErrorKind, Result},
+    parser::ast::{
+        calculate_span::CalculateSpan,
+        expression::{Expression, MatchArm},
+        pattern::Pattern,
+        variant_path,
+    },
+};
+
+use super::Checker;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Constructor<'a> {
+    Variant { enumeration: &'a str, index: usize },
+    Literal(&'a str),
+}
+#[derive(Clone, Debug)]
+enum Pat<'a> {
+    Wild,
+    Constructor(Constructor<'a>, Vec<Pat<'a>>),
+}
+
+impl<'a, 'p> Checker<'a, 'p> {
+    pub(super) fn check_match(
+        &self,
+        expression: &Expression<'a>,
+        arms: &[MatchArm<'a>],
+    ) -> Result<'a, ()> {
+        let mut matrix = vec![];
+        for arm in arms {
+            let row = vec![self.lower_pattern(&arm.pattern)?];
+            if !self.is_useful(&matrix, &row) {
+                return Err(Box::new(Error::new(
+                    ErrorKind::UnreachablePattern,
+                    arm.pattern.calculate_span(),
+                    self.source,
+                )));
+            }
+            matrix.push(row);
+        }
+        if self.is_useful(&matrix, &[Pat::Wild]) {
+            return Err(Box::new(Error::new(
+                ErrorKind::NonExhaustiveMatch {
+                    missing: self.missing_patterns(&matrix),
+                },
+                expression.calculate_span(),
+                self.source,
+            )));
+        }
+        Ok(())
+    }
+    fn lower_pattern(&self, pattern: &Pattern<'a>) -> Result<'a, Pat<'a>> {
+        match pattern {
+            Pattern::Wildcard(_) | Pattern::Binding(_) => Ok(Pat::Wild),
+            Pattern::Integer(literal) | Pattern::Float(literal) => Ok(Pat::Constructor(
+                Constructor::Literal(literal.value),
+                vec![],
+            )),
+            Pattern::Variant { path, fields } => {
+                let (enum_name, variant_name) = variant_path(path.value);
+                let enumeration = self.enums.get(enum_name).ok_or_else(|| {
+                    Box::new(Error::new(ErrorKind::UnknownEnum, path.span, self.source))
+                })?;
+                let index = enumeration
+                    .variants
+                    .iter()
+                    .position(|variant| variant.name == variant_name)
+                    .ok_or_else(|| {
+                        Box::new(Error::new(
+                            ErrorKind::UnknownVariant,
+                            path.span,
+                            self.source,
+                        ))
+                    })?;
+                let fields = match fields {
+                    Some(fields) => fields.value.as_slice(),
+                    None => &[],
+                };
+                let expected = enumeration.variants[index].arity();
+                if fields.len() != expected {
+                    return Err(Box::new(Error::new(
+                        ErrorKind::WrongNumberOfFields {
+                            expected,
+                            received: fields.len(),
+                        },
+                        pattern.calculate_span(),
+                        self.source,
+                    )));
+                }
+                let fields = fields
+                    .iter()
+                    .map(|field| self.lower_pattern(field))
+                    .collect::<Result<'a, Vec<_>>>()?;
+                Ok(Pat::Constructor(
+                    Constructor::Variant {
+                        enumeration: enumeration.name,
+                        index,
+                    },
+                    fields,
+                ))
+            }
+        }
+    }
+    fn is_useful(&self, matrix: &[Vec<Pat<'a>>], row: &[Pat<'a>]) -> bool {
+        let (head, rest) = match row.split_first() {
+            Some(split) => split,
+            None => return matrix.is_empty(),
+        };
+        match head {
+            Pat::Constructor(constructor, fields) => {
+                let specialized = Self::specialize(matrix, *constructor, fields.len());
+                self.is_useful(&specialized, &[fields.as_slice(), rest].concat())
+            }
+            Pat::Wild => match self.complete_signature(matrix) {
+                Some(signature) => signature.into_iter().any(|(constructor, arity)| {
+                    let specialized = Self::specialize(matrix, constructor, arity);
+                    self.is_useful(
+                        &specialized,
+                        &[vec![Pat::Wild; arity].as_slice(), rest].concat(),
+                    )
+                }),
+                None => self.is_useful(&Self::default_matrix(matrix), rest),
+            },
+        }
+    }
+    fn complete_signature(&self, matrix: &[Vec<Pat<'a>>]) -> Option<Vec<(Constructor<'a>, usize)>> {
+        let enumeration = matrix.iter().find_map(|row| match row.first() {
+            Some(Pat::Constructor(Constructor::Variant { enumeration, .. }, _)) => {
+                self.enums.get(enumeration)
+            }
+            _ => None,
+        })?;
+        let signature = enumeration
+            .variants
+            .iter()
+            .enumerate()
+            .map(|(index, variant)| {
+                (
+                    Constructor::Variant {
+                        enumeration: enumeration.name,
+                        index,
+                    },
+                    variant.arity(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let is_complete = signature.iter().all(|(constructor, _)| {
+            matrix.iter().any(
+                |row| matches!(row.first(), Some(Pat::Constructor(head, _)) if head == constructor),
+            )
+        });
+        if is_complete {
+            Some(signature)
+        } else {
+            None
+        }
+    }
+    fn specialize(
+        matrix: &[Vec<Pat<'a>>],
+        constructor: Constructor<'a>,
+        arity: usize,
+    ) -> Vec<Vec<Pat<'a>>> {
+        matrix
+            .iter()
+            .filter_map(|row| {
+                let (head, rest) = row.split_first()?;
+                match head {
+                    Pat::Constructor(head, fields) if *head == constructor => {
+                        Some([fields.as_slice(), rest].concat())
+                    }
+                    Pat::Constructor(..) => None,
+                    Pat::Wild => Some([vec![Pat::Wild; arity].as_slice(), rest].concat()),
+                }
+            })
+            .collect()
+    }
+    fn default_matrix(matrix: &[Vec<Pat<'a>>]) -> Vec<Vec<Pat<'a>>> {
+        matrix
+            .iter()
+            .filter_map(|row| match row.split_first()? {
+                (Pat::Wild, rest) => Some(rest.to_vec()),
+                _ => None,
+            })
+            .collect()
+    }
+    fn missing_patterns(&self, matrix: &[Vec<Pat<'a>>]) -> Vec<String> {
+        let enumeration = matrix.iter().find_map(|row| match row.first() {
+            Some(Pat::Constructor(Constructor::Variant { enumeration, .. }, _)) => {
+                self.enums.get(enumeration)
+            }
+            _ => None,
+        });
+        let enumeration = match enumeration {
+            Some(enumeration) => enumeration,
+            None => return vec!["_".to_string()],
+        };
+        enumeration
+            .variants
+            .iter()
+            .enumerate()
+            .filter(|(index, variant)| {
+                let row = Pat::Constructor(
+                    Constructor::Variant {
+                        enumeration: enumeration.name,
+                        index: *index,
+                    },
+                    vec![Pat::Wild; variant.arity()],
+                );
+                self.is_useful(matrix, &[row])
+            })
+            .map(|(_, variant)| match variant.arity() {
+                0 => format!("{}::{}", enumeration.name, variant.name),
+                arity => format!(
+                    "{}::{}({})",
+                    enumeration.name,
+                    variant.name,
+                    vec!["_"; arity].join(", ")
+                ),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{
+    common::{
+        diagnostic::{Diagnostic, DiagnosticSink},
+        error::{Error, ErrorKind, Result},
+        span::Span,
+    },
+    parser::ast::{
+        calculate_span::CalculateSpan,
+        expression::{Expression, MatchArm},
+        function::Function,
+        node::Node,
+        statement::Statement,
+        types::TypeExpr,
+    },
+};
+
+use super::Checker;
+
+impl<'a, 'p> Checker<'a, 'p> {
+    pub(super) fn check_flow(
+        &self,
+        function: &Function<'a>,
+        diagnostics: &mut DiagnosticSink<'a>,
+    ) -> Result<'a, ()> {
+        let completes = self.completes(&function.body, diagnostics);
+        let returns_value = !matches!(function.return_type, None | Some(TypeExpr::Unit(_)));
+        if completes && returns_value {
+            let end = function.body.calculate_span().end;
+            return Err(Box::new(Error::new(
+                ErrorKind::MissingReturn,
+                Span::new(end.saturating_sub(1), end),
+                self.source,
+            )));
+        }
+        Ok(())
+    }
+    fn completes(&self, node: &Node<'a>, diagnostics: &mut DiagnosticSink<'a>) -> bool {
+        match node {
+            Node::Block(block) => {
+                let mut completes = true;
+                let mut previous = None;
+                for statement in &block.value {
+                    if let (false, Some(previous)) = (completes, previous) {
+                        diagnostics.emit(
+                            Diagnostic::warning(
+                                ErrorKind::UnreachableCode,
+                                statement.calculate_span(),
+                                self.source,
+                            )
+                            .with_label(
+                                previous,
+                                "any code following this statement is unreachable",
+                            )
+                            .with_help("remove the unreachable statements"),
+                        );
+                        break;
+                    }
+                    completes = self.completes(statement, diagnostics);
+                    previous = Some(statement.calculate_span());
+                }
+                completes
+            }
+            Node::Statement(Statement::Return(_)) => false,
+            Node::Statement(Statement::If(if_statement)) => {
+                let consequent = self.completes(&if_statement.value.consequent, diagnostics);
+                let alternative = match &if_statement.value.alternative {
+                    Some(alternative) => self.completes(alternative, diagnostics),
+                    None => true,
+                };
+                consequent || alternative
+            }
+            Node::Statement(Statement::While(while_statement)) => {
+                self.completes(&while_statement.value.body, diagnostics);
+                true
+            }
+            Node::Statement(Statement::For(for_statement)) => {
+                self.completes(&for_statement.value.body, diagnostics);
+                true
+            }
+            Node::Expression(Expression::Lambda { body, .. }) => {
+                self.completes(body, diagnostics);
+                true
+            }
+            Node::Expression(Expression::Match { arms, .. }) => {
+                let mut completes = arms.value.is_empty();
+                for MatchArm { body, .. } in &arms.value {
+                    completes |= self.completes(body, diagnostics);
+                }
+                completes
+            }
+            Node::Expression(Expression::Ternary {
+                consequent,
+                alternative,
+                ..
+            }) => {
+                self.completes(consequent, diagnostics) | self.completes(alternative, diagnostics)
+            }
+            _ => true,
+        }
+    }
+}
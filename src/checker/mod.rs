@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::{diagnostic::DiagnosticSink, error::Result, source::Source},
+    parser::ast::{
+        enumeration::Enum,
+        expression::{Expression, MatchArm},
+        node::Node,
+        statement::Statement,
+        Program,
+    },
+};
+
+pub mod bindings;
+pub mod exhaustiveness;
+pub mod flow;
+
+#[derive(Clone, Debug)]
+pub struct Checker<'a, 'p> {
+    source: Source<'a>,
+    program: &'p Program<'a>,
+    enums: HashMap<&'a str, &'p Enum<'a>>,
+}
+
+impl<'a, 'p> Checker<'a, 'p> {
+    pub fn new(source: Source<'a>, program: &'p Program<'a>) -> Self {
+        let enums = program
+            .enums
+            .iter()
+            .map(|enumeration| (enumeration.name, enumeration))
+            .collect();
+        Self {
+            source,
+            program,
+            enums,
+        }
+    }
+    pub fn with_enums(mut self, enums: impl IntoIterator<Item = (&'a str, &'p Enum<'a>)>) -> Self {
+        self.enums.extend(enums);
+        self
+    }
+    pub fn check(&self, diagnostics: &mut DiagnosticSink<'a>) -> Result<'a, ()> {
+        for function in &self.program.functions {
+            self.check_node(&function.body)?;
+            self.check_flow(function, diagnostics)?;
+            self.check_bindings(function, diagnostics)?;
+        }
+        Ok(())
+    }
+    fn check_node(&self, node: &Node<'a>) -> Result<'a, ()> {
+        match node {
+            Node::Integer(_) | Node::Float(_) | Node::String(_) | Node::Identifier(_) => Ok(()),
+            Node::Block(block) => {
+                for statement in &block.value {
+                    self.check_node(statement)?;
+                }
+                Ok(())
+            }
+            Node::Array(array) => {
+                for element in &array.value {
+                    self.check_node(element)?;
+                }
+                Ok(())
+            }
+            Node::Expression(expression) => match expression {
+                Expression::Infix { lhs, rhs, .. } => {
+                    self.check_node(lhs)?;
+                    self.check_node(rhs)
+                }
+                Expression::Prefix { value, .. } => self.check_node(value),
+                Expression::Call {
+                    callee, arguments, ..
+                } => {
+                    self.check_node(callee)?;
+                    for argument in &arguments.value {
+                        self.check_node(argument)?;
+                    }
+                    Ok(())
+                }
+                Expression::Member { target, .. } => self.check_node(target),
+                Expression::Lambda { body, .. } => self.check_node(body),
+                Expression::Index { target, index } => {
+                    self.check_node(target)?;
+                    self.check_node(&index.value)
+                }
+                Expression::Match { value, arms, .. } => {
+                    self.check_node(value)?;
+                    for MatchArm { body, .. } in &arms.value {
+                        self.check_node(body)?;
+                    }
+                    self.check_match(expression, &arms.value)
+                }
+                Expression::Ternary {
+                    test,
+                    consequent,
+                    alternative,
+                } => {
+                    self.check_node(test)?;
+                    self.check_node(consequent)?;
+                    self.check_node(alternative)
+                }
+                Expression::Range { start, end, .. } => {
+                    self.check_node(start)?;
+                    self.check_node(end)
+                }
+            },
+            Node::Statement(statement) => match statement {
+                Statement::While(while_statement) => {
+                    self.check_node(&while_statement.value.test)?;
+                    self.check_node(&while_statement.value.body)
+                }
+                Statement::For(for_statement) => {
+                    self.check_node(&for_statement.value.iterable)?;
+                    self.check_node(&for_statement.value.body)
+                }
+                Statement::If(if_statement) => {
+                    self.check_node(&if_statement.value.test)?;
+                    self.check_node(&if_statement.value.consequent)?;
+                    match &if_statement.value.alternative {
+                        Some(alternative) => self.check_node(alternative),
+                        None => Ok(()),
+                    }
+                }
+                Statement::Let(let_statement) => match &let_statement.value.init {
+                    Some(init) => self.check_node(init),
+                    None => Ok(()),
+                },
+                Statement::Return(return_statement) => match &return_statement.value.value {
+                    Some(value) => self.check_node(value),
+                    None => Ok(()),
+                },
+            },
+        }
+    }
+}
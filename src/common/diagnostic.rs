@@ -0,0 +1,267 @@
+use std::fmt::{self, Display};
+
+use crate::lexer::cursor::slice::utf8_slice;
+
+use super::{
+    error::{Error, ErrorKind},
+    json::{Json, ToJson},
+    source::Source,
+    span::Span,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Allow,
+    Help,
+    Note,
+    Warning,
+    Error,
+}
+#[derive(Clone, Debug)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+#[derive(Clone, Debug)]
+pub struct Suggestion {
+    pub message: String,
+    pub span: Span,
+    pub replacement: String,
+}
+#[derive(Clone, Debug)]
+pub struct Diagnostic<'a> {
+    pub severity: Severity,
+    pub kind: ErrorKind,
+    pub span: Span,
+    pub source: Source<'a>,
+    pub labels: Vec<Label>,
+    pub notes: Vec<(Severity, String)>,
+    pub suggestions: Vec<Suggestion>,
+}
+#[derive(Clone, Debug, Default)]
+pub struct DiagnosticSink<'a> {
+    diagnostics: Vec<Diagnostic<'a>>,
+    overrides: Vec<(String, Severity)>,
+}
+
+impl Severity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Allow => "allow",
+            Severity::Help => "help",
+            Severity::Note => "note",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl<'a> Diagnostic<'a> {
+    pub fn new(severity: Severity, kind: ErrorKind, span: Span, source: Source<'a>) -> Self {
+        Self {
+            severity,
+            kind,
+            span,
+            source,
+            labels: vec![],
+            notes: vec![],
+            suggestions: vec![],
+        }
+    }
+    pub fn error(kind: ErrorKind, span: Span, source: Source<'a>) -> Self {
+        Self::new(Severity::Error, kind, span, source)
+    }
+    pub fn warning(kind: ErrorKind, span: Span, source: Source<'a>) -> Self {
+        Self::new(Severity::Warning, kind, span, source)
+    }
+    pub fn with_label(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.labels.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+    pub fn with_note(mut self, message: impl Into<String>) -> Self {
+        self.notes.push((Severity::Note, message.into()));
+        self
+    }
+    pub fn with_help(mut self, message: impl Into<String>) -> Self {
+        self.notes.push((Severity::Help, message.into()));
+        self
+    }
+    pub fn with_suggestion(
+        mut self,
+        message: impl Into<String>,
+        span: Span,
+        replacement: impl Into<String>,
+    ) -> Self {
+        self.suggestions.push(Suggestion {
+            message: message.into(),
+            span,
+            replacement: replacement.into(),
+        });
+        self
+    }
+}
+impl<'a> From<Error<'a>> for Diagnostic<'a> {
+    fn from(error: Error<'a>) -> Self {
+        Self {
+            suggestions: error.suggestions,
+            ..Self::error(error.kind, error.span, error.source)
+        }
+    }
+}
+
+impl<'a> DiagnosticSink<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn set(&mut self, name: &str, severity: Severity) {
+        self.overrides.push((name.to_string(), severity));
+    }
+    pub fn emit(&mut self, mut diagnostic: Diagnostic<'a>) {
+        if diagnostic.kind.is_lint() {
+            for (name, severity) in &self.overrides {
+                let matches = match name.as_str() {
+                    "warnings" => diagnostic.severity == Severity::Warning,
+                    name => {
+                        diagnostic.kind.code().eq_ignore_ascii_case(name)
+                            || diagnostic.kind.name() == name
+                    }
+                };
+                if matches {
+                    diagnostic.severity = *severity;
+                }
+            }
+        }
+        if diagnostic.severity != Severity::Allow {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+    pub fn diagnostics(&self) -> &[Diagnostic<'a>] {
+        &self.diagnostics
+    }
+    pub fn take(&mut self) -> Vec<Diagnostic<'a>> {
+        std::mem::take(&mut self.diagnostics)
+    }
+}
+
+impl Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (
+            self.span.start == self.span.end,
+            self.replacement.is_empty(),
+        ) {
+            (true, _) => write!(
+                f,
+                "{}: insert `{}` at {}",
+                self.message, self.replacement, self.span.start
+            ),
+            (false, true) => write!(
+                f,
+                "{}: remove {}.{}",
+                self.message, self.span.start, self.span.end
+            ),
+            (false, false) => write!(
+                f,
+                "{}: replace {}.{} with `{}`",
+                self.message, self.span.start, self.span.end, self.replacement
+            ),
+        }
+    }
+}
+impl<'a> Display for Diagnostic<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let length = self.source.code.chars().count();
+        write!(
+            f,
+            "{}[{}]: {:?} at {}:{}.{}\n{}",
+            self.severity,
+            self.kind.code(),
+            self.kind,
+            self.source.path,
+            self.span.start,
+            self.span.end,
+            utf8_slice(
+                self.source.code,
+                self.span.start.saturating_sub(5).min(length),
+                (self.span.end + 5).min(length)
+            )
+        )?;
+        for label in &self.labels {
+            write!(
+                f,
+                "\n    {}.{}: {}",
+                label.span.start, label.span.end, label.message
+            )?;
+        }
+        for (severity, message) in &self.notes {
+            write!(f, "\n    {}: {}", severity, message)?;
+        }
+        for suggestion in &self.suggestions {
+            write!(f, "\n    help: {}", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+impl ToJson for Severity {
+    fn to_json(&self) -> Json {
+        self.name().to_json()
+    }
+}
+impl ToJson for Label {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("span", self.span.to_json()),
+            ("message", self.message.to_json()),
+        ])
+    }
+}
+impl ToJson for Suggestion {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("message", self.message.to_json()),
+            ("span", self.span.to_json()),
+            ("replacement", self.replacement.to_json()),
+        ])
+    }
+}
+impl<'a> ToJson for Diagnostic<'a> {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("severity", self.severity.to_json()),
+            ("code", self.kind.code().to_json()),
+            ("kind", self.kind.name().to_json()),
+            ("details", self.kind.details()),
+            ("path", self.source.path.to_json()),
+            ("span", self.span.to_json()),
+            ("labels", self.labels.to_json()),
+            (
+                "notes",
+                Json::Array(
+                    self.notes
+                        .iter()
+                        .map(|(severity, message)| {
+                            Json::object([
+                                ("severity", severity.to_json()),
+                                ("message", message.to_json()),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ),
+            ("suggestions", self.suggestions.to_json()),
+        ])
+    }
+}
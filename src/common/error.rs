@@ -2,7 +2,25 @@ use std::result;
 
 use crate::lexer::token::TokenKind;
 
-use super::{source::Source, span::Span};
+use super::{
+    diagnostic::{Diagnostic, Suggestion},
+    explain::EXPLANATIONS,
+    json::{Json, ToJson},
+    source::Source,
+    span::Span,
+};
+
+pub const LINTS: [&str; 9] = [
+    "UnreachableCode",
+    "UnusedMut",
+    "UnusedVariable",
+    "UnusedFunction",
+    "UnusedArgument",
+    "NonSnakeCaseName",
+    "RedundantParentheses",
+    "SelfComparison",
+    "EmptyBlock",
+];
 
 pub type Result<'a, T> = result::Result<T, Box<Error<'a>>>;
 #[derive(Clone, Debug)]
@@ -10,19 +28,200 @@ pub struct Error<'a> {
     pub kind: ErrorKind,
     pub span: Span,
     pub source: Source<'a>,
+    pub suggestions: Vec<Suggestion>,
 }
 
 impl<'a> Error<'a> {
     pub fn new(kind: ErrorKind, span: Span, source: Source<'a>) -> Self {
-        Self { kind, span, source }
+        Self {
+            kind,
+            span,
+            source,
+            suggestions: vec![],
+        }
+    }
+    pub fn with_suggestion(
+        mut self,
+        message: impl Into<String>,
+        span: Span,
+        replacement: impl Into<String>,
+    ) -> Self {
+        self.suggestions.push(Suggestion {
+            message: message.into(),
+            span,
+            replacement: replacement.into(),
+        });
+        self
     }
 }
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ErrorKind {
-    UnexpectedCharacter,
+    UnknownCharacter {
+        char: char,
+    },
+    MalformedNumber,
+    InvalidIdentifierStart {
+        char: char,
+    },
+    UnterminatedString,
+    AssignmentInCondition,
     UnexpectedToken {
         expected: Vec<TokenKind>,
         received: TokenKind,
     },
     UnexpectedEndOfInput,
+    UnknownEnum,
+    UnknownVariant,
+    WrongNumberOfFields {
+        expected: usize,
+        received: usize,
+    },
+    NonExhaustiveMatch {
+        missing: Vec<String>,
+    },
+    UnreachablePattern,
+    MissingReturn,
+    UnreachableCode,
+    ImmutableAssignment,
+    UninitializedVariable,
+    UnusedMut,
+    UndefinedVariable,
+    UndefinedFunction,
+    TypeMismatch,
+    WrongNumberOfArguments {
+        expected: usize,
+        received: usize,
+    },
+    IndexOutOfBounds {
+        index: i64,
+        length: usize,
+    },
+    InvalidAssignmentTarget,
+    DivisionByZero,
+    IntegerOverflow,
+    WrongNumberOfTypeArguments {
+        expected: usize,
+        received: usize,
+    },
+    CannotInferTypeArguments,
+    ModuleNotFound,
+    ModuleNotImported,
+    ImportCycle {
+        chain: Vec<String>,
+    },
+    DuplicateModule {
+        name: String,
+        first: String,
+    },
+    PrivateFunction,
+    InvalidConversion,
+    AssertionFailed,
+    Io {
+        message: String,
+    },
+    UnusedVariable {
+        name: String,
+    },
+    UnusedFunction {
+        name: String,
+    },
+    UnusedArgument {
+        name: String,
+    },
+    NonSnakeCaseName {
+        name: String,
+        suggestion: String,
+    },
+    RedundantParentheses,
+    SelfComparison {
+        name: String,
+    },
+    EmptyBlock {
+        block: String,
+    },
+    MalformedSyntaxTree,
+    InvalidIr {
+        message: String,
+    },
+    CodegenFailed {
+        message: String,
+    },
+    RangeTooLarge {
+        start: i64,
+        end: i64,
+    },
+    InstantiationTooDeep {
+        name: String,
+    },
+    NestingTooDeep,
+}
+
+impl ErrorKind {
+    pub fn name(&self) -> String {
+        format!("{:?}", self)
+            .chars()
+            .take_while(char::is_ascii_alphanumeric)
+            .collect()
+    }
+    pub fn code(&self) -> &'static str {
+        let name = self.name();
+        EXPLANATIONS
+            .iter()
+            .find(|(_, known, _)| *known == name)
+            .map_or("E0000", |(code, _, _)| code)
+    }
+    pub fn is_lint(&self) -> bool {
+        LINTS.contains(&self.name().as_str())
+    }
+    pub fn details(&self) -> Json {
+        match self {
+            ErrorKind::UnknownCharacter { char } | ErrorKind::InvalidIdentifierStart { char } => {
+                Json::object([("char", char.to_string().to_json())])
+            }
+            ErrorKind::UnexpectedToken { expected, received } => Json::object([
+                ("expected", expected.to_json()),
+                ("received", received.to_json()),
+            ]),
+            ErrorKind::WrongNumberOfFields { expected, received }
+            | ErrorKind::WrongNumberOfArguments { expected, received }
+            | ErrorKind::WrongNumberOfTypeArguments { expected, received } => Json::object([
+                ("expected", expected.to_json()),
+                ("received", received.to_json()),
+            ]),
+            ErrorKind::NonExhaustiveMatch { missing } => {
+                Json::object([("missing", missing.to_json())])
+            }
+            ErrorKind::IndexOutOfBounds { index, length } => {
+                Json::object([("index", index.to_json()), ("length", length.to_json())])
+            }
+            ErrorKind::ImportCycle { chain } => Json::object([("chain", chain.to_json())]),
+            ErrorKind::DuplicateModule { name, first } => {
+                Json::object([("name", name.to_json()), ("first", first.to_json())])
+            }
+            ErrorKind::Io { message }
+            | ErrorKind::InvalidIr { message }
+            | ErrorKind::CodegenFailed { message } => {
+                Json::object([("message", message.to_json())])
+            }
+            ErrorKind::UnusedVariable { name }
+            | ErrorKind::UnusedFunction { name }
+            | ErrorKind::UnusedArgument { name }
+            | ErrorKind::SelfComparison { name }
+            | ErrorKind::InstantiationTooDeep { name } => Json::object([("name", name.to_json())]),
+            ErrorKind::NonSnakeCaseName { name, suggestion } => Json::object([
+                ("name", name.to_json()),
+                ("suggestion", suggestion.to_json()),
+            ]),
+            ErrorKind::EmptyBlock { block } => Json::object([("block", block.to_json())]),
+            ErrorKind::RangeTooLarge { start, end } => {
+                Json::object([("start", start.to_json()), ("end", end.to_json())])
+            }
+            _ => Json::Object(vec![]),
+        }
+    }
+}
+impl<'a> ToJson for Error<'a> {
+    fn to_json(&self) -> Json {
+        Diagnostic::from(self.clone()).to_json()
+    }
 }
@@ -0,0 +1,294 @@
+pub const EXPLANATIONS: [(&str, &str, &str); 48] = [
+    (
+        "E0001",
+        "UnknownCharacter",
+        "The lexer met a character that does not start any token, such as `@` or `$`.\n\
+         Remove it, or put it inside a string literal if it is meant as text.",
+    ),
+    (
+        "E0002",
+        "MalformedNumber",
+        "A number literal has more than one decimal point, as in `1.2.3`.\n\
+         A float literal is digits, a single `.`, and more digits.",
+    ),
+    (
+        "E0003",
+        "InvalidIdentifierStart",
+        "An identifier starts with a character that may only continue one, such as a\n\
+         digit in `2nd` or a combining mark. Identifiers start with a letter or `_`.",
+    ),
+    (
+        "E0004",
+        "UnterminatedString",
+        "A string literal is missing its closing `\"` before the end of the file.\n\
+         A `\\\"` inside the literal is an escaped quote and does not close it.",
+    ),
+    (
+        "E0005",
+        "UnexpectedToken",
+        "The parser found a token that cannot appear at this position. The\n\
+         diagnostic lists the tokens that would have been accepted instead.",
+    ),
+    (
+        "E0006",
+        "UnexpectedEndOfInput",
+        "The file ended in the middle of a construct, for example an unclosed block\n\
+         or a function signature without a body.",
+    ),
+    (
+        "E0007",
+        "UnknownEnum",
+        "A path like `Shape::Circle` names an enum that is not defined in this\n\
+         module or in any imported one.",
+    ),
+    (
+        "E0008",
+        "UnknownVariant",
+        "The enum exists but has no variant with this name.",
+    ),
+    (
+        "E0009",
+        "WrongNumberOfFields",
+        "A variant is constructed or matched with a different number of fields than\n\
+         its definition declares.",
+    ),
+    (
+        "E0010",
+        "NonExhaustiveMatch",
+        "A match does not cover every variant of the matched enum. Add arms for the\n\
+         missing variants or a `_` arm.",
+    ),
+    (
+        "E0011",
+        "UnreachablePattern",
+        "An arm can never be taken because earlier arms already match every value it\n\
+         matches.",
+    ),
+    (
+        "E0012",
+        "MissingReturn",
+        "A function with a return type has a path through its body that ends\n\
+         without a `return`.",
+    ),
+    (
+        "E0013",
+        "UnreachableCode",
+        "A statement follows a `return` in the same block and can never run.",
+    ),
+    (
+        "E0014",
+        "ImmutableAssignment",
+        "A binding declared with `let` is assigned after its initialization. Declare\n\
+         it with `let mut` to allow reassignment.",
+    ),
+    (
+        "E0015",
+        "UninitializedVariable",
+        "A variable is read on a path where it has not been assigned a value yet.",
+    ),
+    (
+        "E0016",
+        "UnusedMut",
+        "A binding is declared `mut` but never reassigned; the `mut` can be removed.",
+    ),
+    (
+        "E0017",
+        "UndefinedVariable",
+        "A name is used that is not a variable, argument or function in scope.",
+    ),
+    (
+        "E0018",
+        "UndefinedFunction",
+        "A call names a function that is neither defined, imported nor built in.",
+    ),
+    (
+        "E0019",
+        "TypeMismatch",
+        "A value's type differs from the type its position requires, such as an\n\
+         argument, an annotated binding or an operand.",
+    ),
+    (
+        "E0020",
+        "WrongNumberOfArguments",
+        "A function is called with a different number of arguments than it declares.",
+    ),
+    (
+        "E0021",
+        "IndexOutOfBounds",
+        "An array or string was indexed at runtime with a position outside\n\
+         `0..length`.",
+    ),
+    (
+        "E0022",
+        "InvalidAssignmentTarget",
+        "The left side of `=` is not a variable, an index or a member.",
+    ),
+    (
+        "E0023",
+        "DivisionByZero",
+        "An integer was divided by zero, either at runtime or in a constant\n\
+         expression folded at compile time.",
+    ),
+    (
+        "E0024",
+        "IntegerOverflow",
+        "An integer operation produced a value outside the 64-bit signed range.",
+    ),
+    (
+        "E0025",
+        "WrongNumberOfTypeArguments",
+        "A generic function is called with a different number of explicit type\n\
+         arguments than it has type parameters.",
+    ),
+    (
+        "E0026",
+        "CannotInferTypeArguments",
+        "The type parameters of a generic call cannot be inferred from its\n\
+         arguments. Spell them out, as in `max::<float>(a, b)`. A generic function\n\
+         used as a value needs a function type to instantiate it, as in\n\
+         `let pick: fn(int, int) -> int = max;`.",
+    ),
+    (
+        "E0027",
+        "ModuleNotFound",
+        "An `import` names a module that has no matching file.",
+    ),
+    (
+        "E0028",
+        "ModuleNotImported",
+        "A path refers to a module that the current file does not `import`.",
+    ),
+    (
+        "E0029",
+        "ImportCycle",
+        "Modules import each other in a cycle. The diagnostic lists the chain.",
+    ),
+    (
+        "E0030",
+        "PrivateFunction",
+        "A function of another module is called without being declared `pub`.",
+    ),
+    (
+        "E0031",
+        "InvalidConversion",
+        "A value could not be converted, for example a string that does not hold a\n\
+         number.",
+    ),
+    (
+        "E0032",
+        "AssertionFailed",
+        "A call to `assert` received a false condition at runtime.",
+    ),
+    (
+        "E0033",
+        "Io",
+        "Reading or writing a file failed. The diagnostic carries the system message.",
+    ),
+    (
+        "E0034",
+        "AssignmentInCondition",
+        "The condition of an `if` or `while` is an assignment, as in `if(a = 1)`.\n\
+         Compare with `==` instead.",
+    ),
+    (
+        "E0035",
+        "DuplicateModule",
+        "Two loaded files have the same module name. The name comes from the\n\
+         `module` declaration, or from the file name without its extension, so\n\
+         `a/math.lang` and `b/math.lang` collide. Rename one file or declare a\n\
+         different `module` name in it.",
+    ),
+    (
+        "E0036",
+        "UnusedVariable",
+        "A `let` or pattern binding is never read. Remove it, or prefix its name\n\
+         with `_` to keep it. Reported by `lang lint` as `unused_variables`.",
+    ),
+    (
+        "E0037",
+        "UnusedFunction",
+        "A private function is never called or referenced. `pub` functions and\n\
+         `main` are never reported. Reported by `lang lint` as `unused_functions`.",
+    ),
+    (
+        "E0038",
+        "UnusedArgument",
+        "A function or lambda parameter is never read. Prefix its name with `_` if\n\
+         the signature must keep it. Reported by `lang lint` as `unused_arguments`.",
+    ),
+    (
+        "E0039",
+        "NonSnakeCaseName",
+        "A function name is not snake case, as in `addOne`. The fix renames a private\n\
+         function and its uses; `pub` functions are only reported, since other\n\
+         modules may call them. Reported by `lang lint` as `snake_case`.",
+    ),
+    (
+        "E0040",
+        "RedundantParentheses",
+        "An expression is wrapped in parentheses that change nothing, as in\n\
+         `let a = (1 + 2);` or `if ((a))`. Reported by `lang lint` as\n\
+         `redundant_parentheses`.",
+    ),
+    (
+        "E0041",
+        "SelfComparison",
+        "A variable is compared with itself, as in `a == a`, which always has the\n\
+         same result. Reported by `lang lint` as `self_comparison`.",
+    ),
+    (
+        "E0042",
+        "EmptyBlock",
+        "A function, `if`, `else`, loop or lambda body is empty. An empty `else` can\n\
+         be removed. Reported by `lang lint` as `empty_block`.",
+    ),
+    (
+        "E0043",
+        "MalformedSyntaxTree",
+        "With `--lossless`, the file parsed into a syntax tree that has no AST\n\
+         equivalent. This is a compiler bug; the same file without `--lossless`\n\
+         uses the direct parser.",
+    ),
+    (
+        "E0044",
+        "InvalidIr",
+        "The IR built for `--emit-ir`, `--emit-asm` or `-o` failed verification before\n\
+         or after an optimization pass. This is a compiler bug; `--print-passes`\n\
+         shows the IR after each pass.",
+    ),
+    (
+        "E0045",
+        "CodegenFailed",
+        "The x86_64 backend could not compile the program, either because a function\n\
+         uses something it does not support yet (closures, dynamic values and some\n\
+         builtins) or because `as` or `ld` failed. The interpreter still runs it.",
+    ),
+    (
+        "E0046",
+        "RangeTooLarge",
+        "A range such as `0..9000000000000000000` or a call to `range` would create an\n\
+         array of more than 4194304 elements. Ranges are arrays, so iterate with a\n\
+         `while` loop over a counter instead.",
+    ),
+    (
+        "E0047",
+        "InstantiationTooDeep",
+        "A generic function calls itself with type arguments that keep growing, such as\n\
+         `f<T>` calling `f<[T]>`, so monomorphization would never finish. Instances are\n\
+         limited to a depth of 64; pass the value through a non-generic helper instead.",
+    ),
+    (
+        "E0048",
+        "NestingTooDeep",
+        "Expressions, statements, types or patterns are nested more than 128 levels\n\
+         deep, as in `((((...))))`. The parser stops there instead of overflowing its\n\
+         stack; move part of the expression into a `let` binding or a function.",
+    ),
+];
+
+pub fn explain(code: &str) -> Option<(&'static str, &'static str, &'static str)> {
+    EXPLANATIONS
+        .iter()
+        .find(|(known, _, _)| known.eq_ignore_ascii_case(code))
+        .copied()
+}
@@ -0,0 +1,44 @@
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    fmt::{self, Debug},
+};
+
+use typed_arena::Arena;
+
+#[derive(Default)]
+pub struct Interner {
+    strings: Arena<String>,
+    known: RefCell<HashSet<&'static str>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn intern(&self, string: String) -> &str {
+        let mut known = self.known.borrow_mut();
+        if let Some(interned) = known.get(string.as_str()) {
+            return interned;
+        }
+        let interned: &str = self.strings.alloc(string);
+        // SAFETY: the arena never moves or frees a string before the interner is dropped, and
+        // the set never hands out a reference that outlives `&self`.
+        let interned = unsafe { &*(interned as *const str) };
+        known.insert(interned);
+        interned
+    }
+    pub fn len(&self) -> usize {
+        self.known.borrow().len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.known.borrow().is_empty()
+    }
+}
+impl Debug for Interner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interner")
+            .field("strings", &self.len())
+            .finish()
+    }
+}
@@ -1,3 +1,8 @@
+pub mod diagnostic;
 pub mod error;
+pub mod explain;
+pub mod intern;
+pub mod json;
 pub mod source;
 pub mod span;
+pub mod suggest;
@@ -1,5 +1,15 @@
-#[derive(Clone, Copy, Debug)]
+use std::fmt::{self, Debug};
+
+#[derive(Clone, Copy)]
 pub struct Source<'a> {
     pub code: &'a str,
     pub path: &'a str,
 }
+
+impl<'a> Debug for Source<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Source")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
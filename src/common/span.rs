@@ -1,5 +1,7 @@
 use crate::lexer::cursor::slice::utf8_slice;
 
+use super::json::{Json, ToJson};
+
 #[derive(Clone, Copy, Debug)]
 pub struct Span {
     pub start: usize,
@@ -14,3 +16,8 @@ impl Span {
         utf8_slice(string, self.start, self.end)
     }
 }
+impl ToJson for Span {
+    fn to_json(&self) -> Json {
+        Json::object([("start", self.start.to_json()), ("end", self.end.to_json())])
+    }
+}
@@ -0,0 +1,238 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    backend::{self, BackendError},
+    checker::Checker,
+    common::{
+        diagnostic::{Diagnostic, DiagnosticSink, Severity},
+        error::{ErrorKind, Result},
+        source::Source,
+        span::Span,
+    },
+    interpreter::{value::Value, Interpreter},
+    ir::{self, build::Builder, verify::verify},
+    lexer::{token::Token, Lexer},
+    linter::{config::Config, Linter},
+    loader::{link::SymbolTable, Loader, Module},
+    monomorphize::Monomorphizer,
+    optimizer::{self, fold::Folder, OptimizationLevel},
+    parser::ast::Program,
+    syntax::{
+        self,
+        incremental::{Document, Reparse, TextEdit},
+        red::SyntaxNode,
+    },
+};
+
+#[derive(Debug, Default)]
+pub struct Compiler {
+    loader: Loader,
+    overrides: Vec<(String, Severity)>,
+}
+#[derive(Clone, Debug)]
+pub struct Session<'a> {
+    loader: &'a Loader,
+    entry: Source<'a>,
+    diagnostics: DiagnosticSink<'a>,
+    reported: usize,
+}
+#[derive(Clone, Debug)]
+pub struct SyntaxFile<'a> {
+    pub source: Source<'a>,
+    pub reparses: Vec<Reparse>,
+    pub tree: SyntaxNode,
+}
+
+impl Compiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn lossless(mut self) -> Self {
+        self.loader = self.loader.lossless();
+        self
+    }
+    pub fn set(&mut self, name: &str, severity: Severity) {
+        self.overrides.push((name.to_string(), severity));
+    }
+    pub fn load(&mut self, path: &Path) -> io::Result<()> {
+        self.loader.load(path)
+    }
+    pub fn add(&mut self, path: PathBuf, code: String) -> io::Result<()> {
+        self.loader.add(path, code)
+    }
+    pub fn session(&self) -> Session<'_> {
+        let mut diagnostics = DiagnosticSink::new();
+        for (name, severity) in &self.overrides {
+            diagnostics.set(name, *severity);
+        }
+        Session {
+            loader: &self.loader,
+            entry: self
+                .loader
+                .sources()
+                .next()
+                .unwrap_or(Source { code: "", path: "" }),
+            diagnostics,
+            reported: 0,
+        }
+    }
+}
+
+impl<'a> Session<'a> {
+    pub fn sources(&self) -> impl Iterator<Item = Source<'a>> {
+        self.loader.sources()
+    }
+    pub fn tokenize(&mut self) -> Option<Vec<(Source<'a>, Vec<Token<'a>>)>> {
+        let mut files = vec![];
+        for source in self.loader.sources() {
+            let tokens = Lexer::new(source).collect::<Result<'a, Vec<_>>>();
+            files.push((source, self.accept(tokens)?));
+        }
+        Some(files)
+    }
+    pub fn syntax(&mut self, edits: &[TextEdit]) -> Vec<SyntaxFile<'a>> {
+        let names = self.loader.names();
+        let mut files = vec![];
+        for (index, source) in self.loader.sources().enumerate() {
+            let mut document = Document::new(source.path, source.code);
+            let edits = if index == 0 { edits } else { &[] };
+            let reparses = edits.iter().map(|edit| document.edit(edit)).collect();
+            let source = match edits {
+                [] => source,
+                _ => Source {
+                    code: names.intern(document.text().to_string()),
+                    path: source.path,
+                },
+            };
+            let tree = match document.tree() {
+                Some(tree) => tree.clone(),
+                None => {
+                    let (tree, error) = syntax::recover(source, names);
+                    if let Some(err) = error {
+                        self.diagnostics.emit(Diagnostic::from(*err));
+                    }
+                    tree
+                }
+            };
+            files.push(SyntaxFile {
+                source,
+                reparses,
+                tree,
+            });
+        }
+        files
+    }
+    pub fn parse(&mut self) -> Option<Vec<Module<'a>>> {
+        let modules = self.loader.parse();
+        self.accept(modules)
+    }
+    pub fn check(&mut self, modules: &[Module<'a>]) -> Option<Program<'a>> {
+        let before = self.diagnostics.diagnostics().len();
+        let table = SymbolTable::new(modules, self.loader.names());
+        for (index, module) in modules.iter().enumerate() {
+            let checker =
+                Checker::new(module.source, &module.program).with_enums(table.visible_enums(index));
+            if let Err(err) = checker.check(&mut self.diagnostics) {
+                self.diagnostics.emit(Diagnostic::from(*err));
+                break;
+            }
+        }
+        let failed = self.diagnostics.diagnostics()[before..]
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error);
+        if failed {
+            return None;
+        }
+        self.accept(table.link())
+    }
+    pub fn lower(&mut self, program: &Program<'a>) -> Option<Program<'a>> {
+        let names = self.loader.names();
+        let program = self.accept(Monomorphizer::new(self.entry, program, names).run())?;
+        self.accept(Folder::new(self.entry, names).run(program))
+    }
+    pub fn build(&mut self, program: &Program<'a>) -> Option<ir::Module<'a>> {
+        self.accept(Builder::new(self.entry, program, self.loader.names()).build())
+    }
+    pub fn optimize(
+        &mut self,
+        mut module: ir::Module<'a>,
+        level: OptimizationLevel,
+        mut observe: impl FnMut(&str, &ir::Module),
+    ) -> Option<ir::Module<'a>> {
+        let mut failure = verify(&module).err();
+        optimizer::optimize(&mut module, level, |pass, module| {
+            if failure.is_none() {
+                failure = verify(module).err();
+            }
+            observe(pass, module);
+        });
+        match failure {
+            Some(err) => {
+                self.fail(ErrorKind::InvalidIr {
+                    message: err.to_string(),
+                });
+                None
+            }
+            None => Some(module),
+        }
+    }
+    pub fn emit_asm(&mut self, module: &ir::Module<'a>) -> Option<String> {
+        let assembly = backend::x86_64::generate(module);
+        self.accept_backend(assembly)
+    }
+    pub fn link(&mut self, module: &ir::Module<'a>, output: &Path) -> Option<PathBuf> {
+        let linked = backend::compile(module, output).map(|()| output.to_path_buf());
+        self.accept_backend(linked)
+    }
+    pub fn lint(&mut self, modules: &[Module<'a>], config: &Config) {
+        config.apply(&mut self.diagnostics);
+        for module in modules {
+            Linter::new(module.source, &module.program).run(&mut self.diagnostics);
+        }
+    }
+    pub fn run(&mut self, program: &Program<'a>) -> Option<Value<'a>> {
+        self.accept(Interpreter::new(self.entry, program).run())
+    }
+    pub fn diagnostics(&self) -> &[Diagnostic<'a>] {
+        self.diagnostics.diagnostics()
+    }
+    pub fn unreported(&mut self) -> &[Diagnostic<'a>] {
+        let start = self.reported;
+        self.reported = self.diagnostics.diagnostics().len();
+        &self.diagnostics.diagnostics()[start..]
+    }
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.has_errors()
+    }
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic<'a>> {
+        self.reported = 0;
+        self.diagnostics.take()
+    }
+    fn fail(&mut self, kind: ErrorKind) {
+        self.diagnostics
+            .emit(Diagnostic::error(kind, Span::new(0, 0), self.entry));
+    }
+    fn accept_backend<T>(&mut self, result: std::result::Result<T, BackendError>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.fail(ErrorKind::CodegenFailed {
+                    message: err.to_string(),
+                });
+                None
+            }
+        }
+    }
+    fn accept<T>(&mut self, result: Result<'a, T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.diagnostics.emit(Diagnostic::from(*err));
+                None
+            }
+        }
+    }
+}
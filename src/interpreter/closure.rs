@@ -0,0 +1,182 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ptr,
+};
+
+use crate::{
+    common::source::Source,
+    parser::ast::{
+        expression::{Expression, Parameter},
+        node::Node,
+        pattern::Pattern,
+        spanned::Spanned,
+        statement::Statement,
+    },
+};
+
+use super::value::Value;
+
+#[derive(Debug)]
+pub struct Closure<'a> {
+    pub parameters: Vec<&'a str>,
+    pub body: Node<'a>,
+    pub captures: HashMap<&'a str, Value<'a>>,
+    pub source: Source<'a>,
+}
+
+impl<'a> PartialEq for Closure<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self, other)
+    }
+}
+
+pub fn free_variables<'a>(parameters: &[Parameter<'a>], body: &Node<'a>) -> Vec<Spanned<&'a str>> {
+    let mut collector = FreeVariables {
+        scopes: vec![parameters
+            .iter()
+            .map(|parameter| parameter.name.value)
+            .collect()],
+        free: vec![],
+    };
+    collector.visit(body);
+    collector.free
+}
+
+struct FreeVariables<'a> {
+    scopes: Vec<HashSet<&'a str>>,
+    free: Vec<Spanned<&'a str>>,
+}
+
+impl<'a> FreeVariables<'a> {
+    fn is_bound(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains(name))
+    }
+    fn bind(&mut self, name: &'a str) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name);
+        }
+    }
+    fn bind_pattern(&mut self, pattern: &Pattern<'a>) {
+        match pattern {
+            Pattern::Binding(name) => self.bind(name.value),
+            Pattern::Variant {
+                fields: Some(fields),
+                ..
+            } => {
+                for field in &fields.value {
+                    self.bind_pattern(field);
+                }
+            }
+            _ => {}
+        }
+    }
+    fn visit(&mut self, node: &Node<'a>) {
+        match node {
+            Node::Integer(_) | Node::Float(_) | Node::String(_) => {}
+            Node::Identifier(name) => {
+                if !name.value.contains("::")
+                    && !self.is_bound(name.value)
+                    && !self.free.iter().any(|free| free.value == name.value)
+                {
+                    self.free.push(name.clone());
+                }
+            }
+            Node::Block(block) => {
+                self.scopes.push(HashSet::new());
+                for statement in &block.value {
+                    self.visit(statement);
+                }
+                self.scopes.pop();
+            }
+            Node::Array(array) => {
+                for element in &array.value {
+                    self.visit(element);
+                }
+            }
+            Node::Expression(expression) => match expression {
+                Expression::Infix { lhs, rhs, .. } => {
+                    self.visit(lhs);
+                    self.visit(rhs);
+                }
+                Expression::Prefix { value, .. } => self.visit(value),
+                Expression::Call {
+                    callee, arguments, ..
+                } => {
+                    self.visit(callee);
+                    for argument in &arguments.value {
+                        self.visit(argument);
+                    }
+                }
+                Expression::Member { target, .. } => self.visit(target),
+                Expression::Lambda { parameters, body } => {
+                    self.scopes.push(
+                        parameters
+                            .value
+                            .iter()
+                            .map(|parameter| parameter.name.value)
+                            .collect(),
+                    );
+                    self.visit(body);
+                    self.scopes.pop();
+                }
+                Expression::Index { target, index } => {
+                    self.visit(target);
+                    self.visit(&index.value);
+                }
+                Expression::Match { value, arms, .. } => {
+                    self.visit(value);
+                    for arm in &arms.value {
+                        self.scopes.push(HashSet::new());
+                        self.bind_pattern(&arm.pattern);
+                        self.visit(&arm.body);
+                        self.scopes.pop();
+                    }
+                }
+                Expression::Ternary {
+                    test,
+                    consequent,
+                    alternative,
+                } => {
+                    self.visit(test);
+                    self.visit(consequent);
+                    self.visit(alternative);
+                }
+                Expression::Range { start, end, .. } => {
+                    self.visit(start);
+                    self.visit(end);
+                }
+            },
+            Node::Statement(statement) => match statement {
+                Statement::While(while_statement) => {
+                    self.visit(&while_statement.value.test);
+                    self.visit(&while_statement.value.body);
+                }
+                Statement::For(for_statement) => {
+                    self.visit(&for_statement.value.iterable);
+                    self.scopes
+                        .push(HashSet::from([for_statement.value.binding.value]));
+                    self.visit(&for_statement.value.body);
+                    self.scopes.pop();
+                }
+                Statement::If(if_statement) => {
+                    self.visit(&if_statement.value.test);
+                    self.visit(&if_statement.value.consequent);
+                    if let Some(alternative) = &if_statement.value.alternative {
+                        self.visit(alternative);
+                    }
+                }
+                Statement::Let(let_statement) => {
+                    if let Some(init) = &let_statement.value.init {
+                        self.visit(init);
+                    }
+                    self.bind(let_statement.value.name);
+                }
+                Statement::Return(return_statement) => {
+                    if let Some(value) = &return_statement.value.value {
+                        self.visit(value);
+                    }
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,614 @@
+use std::{collections::HashMap, rc::Rc, result};
+
+use crate::{
+    common::{
+        error::{Error, ErrorKind, Result},
+        source::Source,
+        span::Span,
+    },
+    lexer::escape::unescape,
+    parser::ast::{
+        calculate_span::CalculateSpan,
+        enumeration::Enum,
+        expression::{Expression, Operator},
+        function::Function,
+        node::Node,
+        pattern::Pattern,
+        spanned::Spanned,
+        statement::{ForStatement, Statement},
+        variant_path, Program,
+    },
+    stdlib,
+};
+
+use self::{
+    closure::{free_variables, Closure},
+    value::Value,
+};
+
+pub mod closure;
+pub mod value;
+
+enum Interrupt<'a> {
+    Return(Value<'a>),
+    Error(Box<Error<'a>>),
+}
+
+impl<'a> From<Box<Error<'a>>> for Interrupt<'a> {
+    fn from(error: Box<Error<'a>>) -> Self {
+        Interrupt::Error(error)
+    }
+}
+type Flow<'a, T> = result::Result<T, Interrupt<'a>>;
+
+#[derive(Clone, Debug)]
+pub struct Interpreter<'a, 'p> {
+    source: Source<'a>,
+    functions: HashMap<&'a str, &'p Function<'a>>,
+    enums: HashMap<&'a str, &'p Enum<'a>>,
+    scopes: Vec<HashMap<&'a str, Value<'a>>>,
+}
+
+impl<'a, 'p> Interpreter<'a, 'p> {
+    pub fn new(source: Source<'a>, program: &'p Program<'a>) -> Self {
+        Self {
+            source,
+            functions: program
+                .functions
+                .iter()
+                .map(|function| (function.name, function))
+                .collect(),
+            enums: program
+                .enums
+                .iter()
+                .map(|enumeration| (enumeration.name, enumeration))
+                .collect(),
+            scopes: vec![],
+        }
+    }
+    pub fn run(&mut self) -> Result<'a, Value<'a>> {
+        self.call("main", vec![], Span::new(0, 0))
+    }
+    pub fn call(
+        &mut self,
+        name: &str,
+        arguments: Vec<Value<'a>>,
+        span: Span,
+    ) -> Result<'a, Value<'a>> {
+        let function = match self.functions.get(name) {
+            Some(function) => *function,
+            None => {
+                return match stdlib::lookup(name) {
+                    Some(builtin) => builtin.call(arguments, span, self.source),
+                    None => Err(self.error(ErrorKind::UndefinedFunction, span)),
+                }
+            }
+        };
+        if function.arguments.len() != arguments.len() {
+            return Err(self.error(
+                ErrorKind::WrongNumberOfArguments {
+                    expected: function.arguments.len(),
+                    received: arguments.len(),
+                },
+                span,
+            ));
+        }
+        let frame = function
+            .arguments
+            .iter()
+            .map(|argument| argument.name)
+            .zip(arguments)
+            .collect();
+        let scopes = std::mem::replace(&mut self.scopes, vec![frame]);
+        let source = std::mem::replace(&mut self.source, function.source);
+        let result = self.evaluate(&function.body);
+        self.scopes = scopes;
+        self.source = source;
+        match result {
+            Ok(_) => Ok(Value::Unit),
+            Err(Interrupt::Return(value)) => Ok(value),
+            Err(Interrupt::Error(error)) => Err(error),
+        }
+    }
+    fn call_value(
+        &mut self,
+        callee: Value<'a>,
+        arguments: Vec<Value<'a>>,
+        span: Span,
+    ) -> Result<'a, Value<'a>> {
+        let closure = match callee {
+            Value::Function(name) => return self.call(name, arguments, span),
+            Value::Closure(closure) => closure,
+            _ => return Err(self.error(ErrorKind::TypeMismatch, span)),
+        };
+        if closure.parameters.len() != arguments.len() {
+            return Err(self.error(
+                ErrorKind::WrongNumberOfArguments {
+                    expected: closure.parameters.len(),
+                    received: arguments.len(),
+                },
+                span,
+            ));
+        }
+        let mut frame = closure.captures.clone();
+        frame.extend(closure.parameters.iter().copied().zip(arguments));
+        let scopes = std::mem::replace(&mut self.scopes, vec![frame]);
+        let source = std::mem::replace(&mut self.source, closure.source);
+        let result = self.evaluate(&closure.body);
+        self.scopes = scopes;
+        self.source = source;
+        match result {
+            Ok(value) | Err(Interrupt::Return(value)) => Ok(value),
+            Err(Interrupt::Error(error)) => Err(error),
+        }
+    }
+    fn evaluate(&mut self, node: &Node<'a>) -> Flow<'a, Value<'a>> {
+        match node {
+            Node::Integer(integer) => match integer.value.parse() {
+                Ok(integer) => Ok(Value::Integer(integer)),
+                Err(_) => Err(self.error(ErrorKind::IntegerOverflow, integer.span).into()),
+            },
+            Node::Float(float) => match float.value.parse() {
+                Ok(float) => Ok(Value::Float(float)),
+                Err(_) => Err(self.error(ErrorKind::TypeMismatch, float.span).into()),
+            },
+            Node::String(string) => Ok(Value::String(unescape(string.value))),
+            Node::Identifier(name) => self.lookup(name),
+            Node::Block(block) => {
+                self.scopes.push(HashMap::new());
+                let result = block
+                    .value
+                    .iter()
+                    .try_for_each(|statement| self.evaluate(statement).map(drop));
+                self.scopes.pop();
+                result.map(|_| Value::Unit)
+            }
+            Node::Array(array) => Ok(Value::Array(
+                array
+                    .value
+                    .iter()
+                    .map(|element| self.evaluate(element))
+                    .collect::<Flow<'a, _>>()?,
+            )),
+            Node::Expression(expression) => self.evaluate_expression(expression),
+            Node::Statement(statement) => self.evaluate_statement(statement),
+        }
+    }
+    fn evaluate_all(&mut self, nodes: &[Node<'a>]) -> Flow<'a, Vec<Value<'a>>> {
+        nodes.iter().map(|node| self.evaluate(node)).collect()
+    }
+    fn evaluate_expression(&mut self, expression: &Expression<'a>) -> Flow<'a, Value<'a>> {
+        let span = expression.calculate_span();
+        match expression {
+            Expression::Infix {
+                operator: Operator::Assignment,
+                lhs,
+                rhs,
+            } => {
+                let value = self.evaluate(rhs)?;
+                *self.place(lhs)? = value;
+                Ok(Value::Unit)
+            }
+            Expression::Infix { operator, lhs, rhs } => {
+                let lhs = self.evaluate(lhs)?;
+                let rhs = self.evaluate(rhs)?;
+                Ok(self.binary(*operator, lhs, rhs, span)?)
+            }
+            Expression::Prefix { operator, value } => {
+                match (operator.value, self.evaluate(value)?) {
+                    (Operator::Plus, value @ (Value::Integer(_) | Value::Float(_))) => Ok(value),
+                    (Operator::Minus, Value::Integer(integer)) => match integer.checked_neg() {
+                        Some(integer) => Ok(Value::Integer(integer)),
+                        None => Err(self.error(ErrorKind::IntegerOverflow, span).into()),
+                    },
+                    (Operator::Minus, Value::Float(float)) => Ok(Value::Float(-float)),
+                    _ => Err(self.error(ErrorKind::TypeMismatch, span).into()),
+                }
+            }
+            Expression::Call {
+                callee, arguments, ..
+            } => match callee.as_ref() {
+                Node::Identifier(name)
+                    if name.value.contains("::") && !self.functions.contains_key(name.value) =>
+                {
+                    let arguments = self.evaluate_all(&arguments.value)?;
+                    Ok(self.construct(name, arguments)?)
+                }
+                Node::Expression(Expression::Member { target, name }) => {
+                    let target = self.evaluate(target)?;
+                    let mut arguments = self.evaluate_all(&arguments.value)?;
+                    arguments.insert(0, target);
+                    Ok(self.call(name.value, arguments, span)?)
+                }
+                _ => {
+                    let callee = self.evaluate(callee)?;
+                    let arguments = self.evaluate_all(&arguments.value)?;
+                    Ok(self.call_value(callee, arguments, span)?)
+                }
+            },
+            Expression::Member { name, .. } => {
+                Err(self.error(ErrorKind::UndefinedVariable, name.span).into())
+            }
+            Expression::Lambda { parameters, body } => {
+                let captures = free_variables(&parameters.value, body)
+                    .into_iter()
+                    .filter_map(|name| {
+                        self.scopes
+                            .iter()
+                            .rev()
+                            .find_map(|scope| scope.get(name.value))
+                            .map(|value| (name.value, value.clone()))
+                    })
+                    .collect();
+                Ok(Value::Closure(Rc::new(Closure {
+                    parameters: parameters
+                        .value
+                        .iter()
+                        .map(|parameter| parameter.name.value)
+                        .collect(),
+                    body: body.as_ref().clone(),
+                    captures,
+                    source: self.source,
+                })))
+            }
+            Expression::Index { target, index } => {
+                let target = self.evaluate(target)?;
+                if let Node::Expression(Expression::Range {
+                    start,
+                    end,
+                    inclusive,
+                }) = index.value.as_ref()
+                {
+                    let (start, end) = self.bounds(start, end, *inclusive)?;
+                    let arguments = vec![target, Value::Integer(start), Value::Integer(end)];
+                    return Ok(stdlib::runtime::slice(arguments, index.span, self.source)?);
+                }
+                let position = self.evaluate(&index.value)?;
+                match (target, position) {
+                    (Value::Array(elements), Value::Integer(position)) => {
+                        let length = elements.len();
+                        match usize::try_from(position)
+                            .ok()
+                            .and_then(|position| elements.into_iter().nth(position))
+                        {
+                            Some(element) => Ok(element),
+                            None => Err(self
+                                .error(
+                                    ErrorKind::IndexOutOfBounds {
+                                        index: position,
+                                        length,
+                                    },
+                                    index.span,
+                                )
+                                .into()),
+                        }
+                    }
+                    _ => Err(self.error(ErrorKind::TypeMismatch, span).into()),
+                }
+            }
+            Expression::Match { value, arms, .. } => {
+                let value = self.evaluate(value)?;
+                for arm in &arms.value {
+                    let mut bindings = HashMap::new();
+                    if self.matches(&arm.pattern, &value, &mut bindings)? {
+                        self.scopes.push(bindings);
+                        let result = self.evaluate(&arm.body);
+                        self.scopes.pop();
+                        return result;
+                    }
+                }
+                Err(self
+                    .error(
+                        ErrorKind::NonExhaustiveMatch {
+                            missing: vec![value.to_string()],
+                        },
+                        span,
+                    )
+                    .into())
+            }
+            Expression::Ternary {
+                test,
+                consequent,
+                alternative,
+            } => match self.test(test)? {
+                true => self.evaluate(consequent),
+                false => self.evaluate(alternative),
+            },
+            Expression::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let (start, end) = self.bounds(start, end, *inclusive)?;
+                let arguments = vec![Value::Integer(start), Value::Integer(end)];
+                Ok(stdlib::runtime::range(arguments, span, self.source)?)
+            }
+        }
+    }
+    fn evaluate_statement(&mut self, statement: &Statement<'a>) -> Flow<'a, Value<'a>> {
+        match statement {
+            Statement::While(while_statement) => {
+                while self.test(&while_statement.value.test)? {
+                    self.evaluate(&while_statement.value.body)?;
+                }
+                Ok(Value::Unit)
+            }
+            Statement::For(for_statement) => {
+                let for_statement = &for_statement.value;
+                match for_statement.iterable.as_ref() {
+                    Node::Expression(Expression::Range {
+                        start,
+                        end,
+                        inclusive,
+                    }) => {
+                        let (start, end) = self.bounds(start, end, *inclusive)?;
+                        self.iterate(for_statement, (start..end).map(Value::Integer))?;
+                    }
+                    iterable => match self.evaluate(iterable)? {
+                        Value::Array(elements) => self.iterate(for_statement, elements)?,
+                        _ => {
+                            return Err(self
+                                .error(ErrorKind::TypeMismatch, iterable.calculate_span())
+                                .into())
+                        }
+                    },
+                }
+                Ok(Value::Unit)
+            }
+            Statement::If(if_statement) => {
+                if self.test(&if_statement.value.test)? {
+                    self.evaluate(&if_statement.value.consequent)?;
+                } else if let Some(alternative) = &if_statement.value.alternative {
+                    self.evaluate(alternative)?;
+                }
+                Ok(Value::Unit)
+            }
+            Statement::Let(let_statement) => {
+                let value = match &let_statement.value.init {
+                    Some(init) => self.evaluate(init)?,
+                    None => Value::Unit,
+                };
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.insert(let_statement.value.name, value);
+                }
+                Ok(Value::Unit)
+            }
+            Statement::Return(return_statement) => {
+                let value = match &return_statement.value.value {
+                    Some(value) => self.evaluate(value)?,
+                    None => Value::Unit,
+                };
+                Err(Interrupt::Return(value))
+            }
+        }
+    }
+    fn iterate(
+        &mut self,
+        for_statement: &ForStatement<'a>,
+        values: impl IntoIterator<Item = Value<'a>>,
+    ) -> Flow<'a, ()> {
+        for value in values {
+            self.scopes
+                .push(HashMap::from([(for_statement.binding.value, value)]));
+            let result = self.evaluate(&for_statement.body);
+            self.scopes.pop();
+            result?;
+        }
+        Ok(())
+    }
+    fn test(&mut self, test: &Node<'a>) -> Flow<'a, bool> {
+        match self.evaluate(test)? {
+            Value::Boolean(boolean) => Ok(boolean),
+            _ => Err(self
+                .error(ErrorKind::TypeMismatch, test.calculate_span())
+                .into()),
+        }
+    }
+    fn bounds(
+        &mut self,
+        start: &Node<'a>,
+        end: &Node<'a>,
+        inclusive: bool,
+    ) -> Flow<'a, (i64, i64)> {
+        let span = Span::new(start.calculate_span().start, end.calculate_span().end);
+        match (self.evaluate(start)?, self.evaluate(end)?) {
+            (Value::Integer(start), Value::Integer(end)) if inclusive => match end.checked_add(1) {
+                Some(end) => Ok((start, end)),
+                None => Err(self.error(ErrorKind::IntegerOverflow, span).into()),
+            },
+            (Value::Integer(start), Value::Integer(end)) => Ok((start, end)),
+            _ => Err(self.error(ErrorKind::TypeMismatch, span).into()),
+        }
+    }
+    fn lookup(&self, name: &Spanned<&'a str>) -> Flow<'a, Value<'a>> {
+        if name.value.contains("::") && !self.functions.contains_key(name.value) {
+            return Ok(self.construct(name, vec![])?);
+        }
+        match self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name.value))
+        {
+            Some(value) => Ok(value.clone()),
+            None if self.functions.contains_key(name.value)
+                || stdlib::lookup(name.value).is_some() =>
+            {
+                Ok(Value::Function(name.value))
+            }
+            None => Err(self.error(ErrorKind::UndefinedVariable, name.span).into()),
+        }
+    }
+    fn place(&mut self, node: &Node<'a>) -> Flow<'a, &mut Value<'a>> {
+        let source = self.source;
+        match node {
+            Node::Identifier(name) => self
+                .scopes
+                .iter_mut()
+                .rev()
+                .find_map(|scope| scope.get_mut(name.value))
+                .ok_or_else(|| {
+                    Box::new(Error::new(ErrorKind::UndefinedVariable, name.span, source)).into()
+                }),
+            Node::Expression(Expression::Index { target, index }) => {
+                let position = match self.evaluate(&index.value)? {
+                    Value::Integer(position) => position,
+                    _ => return Err(self.error(ErrorKind::TypeMismatch, index.span).into()),
+                };
+                match self.place(target)? {
+                    Value::Array(elements) => {
+                        let length = elements.len();
+                        usize::try_from(position)
+                            .ok()
+                            .and_then(|position| elements.get_mut(position))
+                            .ok_or_else(|| {
+                                Box::new(Error::new(
+                                    ErrorKind::IndexOutOfBounds {
+                                        index: position,
+                                        length,
+                                    },
+                                    index.span,
+                                    source,
+                                ))
+                                .into()
+                            })
+                    }
+                    _ => Err(Box::new(Error::new(
+                        ErrorKind::TypeMismatch,
+                        target.calculate_span(),
+                        source,
+                    ))
+                    .into()),
+                }
+            }
+            _ => Err(self
+                .error(ErrorKind::InvalidAssignmentTarget, node.calculate_span())
+                .into()),
+        }
+    }
+    fn construct(&self, path: &Spanned<&'a str>, fields: Vec<Value<'a>>) -> Result<'a, Value<'a>> {
+        let (enum_name, variant_name) = variant_path(path.value);
+        let enumeration = self
+            .enums
+            .get(enum_name)
+            .ok_or_else(|| self.error(ErrorKind::UnknownEnum, path.span))?;
+        let variant = enumeration
+            .variant(variant_name)
+            .ok_or_else(|| self.error(ErrorKind::UnknownVariant, path.span))?;
+        if variant.arity() != fields.len() {
+            return Err(self.error(
+                ErrorKind::WrongNumberOfFields {
+                    expected: variant.arity(),
+                    received: fields.len(),
+                },
+                path.span,
+            ));
+        }
+        Ok(Value::Variant {
+            enumeration: enumeration.name,
+            variant: variant.name,
+            fields,
+        })
+    }
+    fn matches(
+        &self,
+        pattern: &Pattern<'a>,
+        value: &Value<'a>,
+        bindings: &mut HashMap<&'a str, Value<'a>>,
+    ) -> Result<'a, bool> {
+        match (pattern, value) {
+            (Pattern::Wildcard(_), _) => Ok(true),
+            (Pattern::Binding(name), _) => {
+                bindings.insert(name.value, value.clone());
+                Ok(true)
+            }
+            (Pattern::Integer(literal), Value::Integer(integer)) => match literal.value.parse() {
+                Ok(literal) => Ok(*integer == literal),
+                Err(_) => Err(self.error(ErrorKind::IntegerOverflow, literal.span)),
+            },
+            (Pattern::Float(literal), Value::Float(float)) => match literal.value.parse() {
+                Ok(literal) => Ok(*float == literal),
+                Err(_) => Err(self.error(ErrorKind::TypeMismatch, literal.span)),
+            },
+            (
+                Pattern::Variant { path, fields },
+                Value::Variant {
+                    enumeration,
+                    variant,
+                    fields: values,
+                },
+            ) => {
+                if variant_path(path.value) != (*enumeration, *variant) {
+                    return Ok(false);
+                }
+                let fields = match fields {
+                    Some(fields) => fields.value.as_slice(),
+                    None => &[],
+                };
+                for (field, value) in fields.iter().zip(values) {
+                    if !self.matches(field, value, bindings)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+    fn binary(
+        &self,
+        operator: Operator,
+        lhs: Value<'a>,
+        rhs: Value<'a>,
+        span: Span,
+    ) -> Result<'a, Value<'a>> {
+        match (operator, lhs, rhs) {
+            (Operator::Equal, lhs, rhs) => Ok(Value::Boolean(lhs == rhs)),
+            (Operator::Divide, Value::Integer(_), Value::Integer(0)) => {
+                Err(self.error(ErrorKind::DivisionByZero, span))
+            }
+            (Operator::LessThen, Value::Integer(lhs), Value::Integer(rhs)) => {
+                Ok(Value::Boolean(lhs < rhs))
+            }
+            (Operator::GreaterThen, Value::Integer(lhs), Value::Integer(rhs)) => {
+                Ok(Value::Boolean(lhs > rhs))
+            }
+            (Operator::LessThenEqual, Value::Integer(lhs), Value::Integer(rhs)) => {
+                Ok(Value::Boolean(lhs <= rhs))
+            }
+            (Operator::GreaterThenEqual, Value::Integer(lhs), Value::Integer(rhs)) => {
+                Ok(Value::Boolean(lhs >= rhs))
+            }
+            (Operator::Plus, Value::String(lhs), Value::String(rhs)) => {
+                Ok(Value::String(lhs + &rhs))
+            }
+            (operator, Value::Integer(lhs), Value::Integer(rhs)) => {
+                let result = match operator {
+                    Operator::Plus => lhs.checked_add(rhs),
+                    Operator::Minus => lhs.checked_sub(rhs),
+                    Operator::Multiply => lhs.checked_mul(rhs),
+                    Operator::Divide => lhs.checked_div(rhs),
+                    _ => return Err(self.error(ErrorKind::TypeMismatch, span)),
+                };
+                result
+                    .map(Value::Integer)
+                    .ok_or_else(|| self.error(ErrorKind::IntegerOverflow, span))
+            }
+            (operator, Value::Float(lhs), Value::Float(rhs)) => match operator {
+                Operator::Plus => Ok(Value::Float(lhs + rhs)),
+                Operator::Minus => Ok(Value::Float(lhs - rhs)),
+                Operator::Multiply => Ok(Value::Float(lhs * rhs)),
+                Operator::Divide => Ok(Value::Float(lhs / rhs)),
+                Operator::LessThen => Ok(Value::Boolean(lhs < rhs)),
+                Operator::GreaterThen => Ok(Value::Boolean(lhs > rhs)),
+                Operator::LessThenEqual => Ok(Value::Boolean(lhs <= rhs)),
+                Operator::GreaterThenEqual => Ok(Value::Boolean(lhs >= rhs)),
+                _ => Err(self.error(ErrorKind::TypeMismatch, span)),
+            },
+            _ => Err(self.error(ErrorKind::TypeMismatch, span)),
+        }
+    }
+    fn error(&self, kind: ErrorKind, span: Span) -> Box<Error<'a>> {
+        Box::new(Error::new(kind, span, self.source))
+    }
+}
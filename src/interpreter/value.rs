@@ -0,0 +1,65 @@
+use std::{
+    fmt::{self, Display},
+    rc::Rc,
+};
+
+use super::closure::Closure;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value<'a> {
+    Unit,
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+    Array(Vec<Value<'a>>),
+    Variant {
+        enumeration: &'a str,
+        variant: &'a str,
+        fields: Vec<Value<'a>>,
+    },
+    Function(&'a str),
+    Closure(Rc<Closure<'a>>),
+}
+
+impl<'a> Display for Value<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Unit => write!(f, "()"),
+            Value::Integer(integer) => write!(f, "{}", integer),
+            Value::Float(float) => write!(f, "{:?}", float),
+            Value::Boolean(boolean) => write!(f, "{}", boolean),
+            Value::String(string) => write!(f, "{}", string),
+            Value::Array(elements) => {
+                write!(f, "[")?;
+                for (index, element) in elements.iter().enumerate() {
+                    if index != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, "]")
+            }
+            Value::Variant {
+                enumeration,
+                variant,
+                fields,
+            } => {
+                write!(f, "{}::{}", enumeration, variant)?;
+                if !fields.is_empty() {
+                    write!(f, "(")?;
+                    for (index, field) in fields.iter().enumerate() {
+                        if index != 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}", field)?;
+                    }
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
+            Value::Function(name) => write!(f, "<function {}>", name),
+            Value::Closure(_) => write!(f, "<closure>"),
+        }
+    }
+}
@@ -0,0 +1,1058 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::{
+        error::{Error, ErrorKind, Result},
+        intern::Interner,
+        source::Source,
+        span::Span,
+    },
+    interpreter::closure::free_variables,
+    lexer::escape::unescape,
+    parser::ast::{
+        calculate_span::CalculateSpan,
+        enumeration::Enum,
+        expression::{Expression, Operator, Parameter},
+        function,
+        node::Node,
+        pattern::Pattern,
+        spanned::Spanned,
+        statement::Statement,
+        types::TypeExpr,
+        variant_path, Program,
+    },
+    stdlib::{self, signature},
+};
+
+use super::{
+    transform::{remove_trivial_phis, remove_unreachable_blocks, renumber},
+    BinaryOperator, Block, BlockId, Constant, Function, Instruction, InstructionKind, Module, Phi,
+    Terminator, Type, ValueId,
+};
+
+type Variable = usize;
+
+struct FunctionBuilder<'a> {
+    name: &'a str,
+    parameters: Vec<(ValueId, Type<'a>)>,
+    return_type: Type<'a>,
+    blocks: Vec<Block<'a>>,
+    terminated: HashSet<BlockId>,
+    current: BlockId,
+    values: usize,
+    types: HashMap<ValueId, Type<'a>>,
+    unit: ValueId,
+    variables: Vec<Type<'a>>,
+    scopes: Vec<HashMap<&'a str, Variable>>,
+    definitions: HashMap<(BlockId, Variable), ValueId>,
+    predecessors: HashMap<BlockId, Vec<BlockId>>,
+    sealed: HashSet<BlockId>,
+    incomplete: HashMap<BlockId, Vec<(Variable, ValueId)>>,
+}
+
+impl<'a> FunctionBuilder<'a> {
+    fn new(name: &'a str, return_type: Type<'a>) -> Self {
+        let mut builder = Self {
+            name,
+            parameters: vec![],
+            return_type,
+            blocks: vec![],
+            terminated: HashSet::new(),
+            current: BlockId(0),
+            values: 0,
+            types: HashMap::new(),
+            unit: ValueId(0),
+            variables: vec![],
+            scopes: vec![HashMap::new()],
+            definitions: HashMap::new(),
+            predecessors: HashMap::new(),
+            sealed: HashSet::new(),
+            incomplete: HashMap::new(),
+        };
+        let entry = builder.create_block();
+        builder.seal(entry);
+        builder.current = entry;
+        builder.unit = builder.emit(Type::Unit, InstructionKind::Constant(Constant::Unit));
+        builder
+    }
+    fn value(&mut self, value_type: Type<'a>) -> ValueId {
+        let value = ValueId(self.values);
+        self.values += 1;
+        self.types.insert(value, value_type);
+        value
+    }
+    fn type_of(&self, value: ValueId) -> Type<'a> {
+        self.types.get(&value).cloned().unwrap_or(Type::Dynamic)
+    }
+    fn create_block(&mut self) -> BlockId {
+        let id = BlockId(self.blocks.len());
+        self.blocks.push(Block {
+            id,
+            phis: vec![],
+            instructions: vec![],
+            terminator: Terminator::Unreachable,
+        });
+        self.predecessors.insert(id, vec![]);
+        id
+    }
+    fn switch_to(&mut self, block: BlockId) {
+        self.current = block;
+    }
+    fn emit(&mut self, value_type: Type<'a>, kind: InstructionKind<'a>) -> ValueId {
+        let value = self.value(value_type.clone());
+        self.blocks[self.current.0].instructions.push(Instruction {
+            value,
+            value_type,
+            kind,
+        });
+        value
+    }
+    fn parameter(&mut self, value_type: Type<'a>) -> ValueId {
+        let value = self.value(value_type.clone());
+        self.parameters.push((value, value_type));
+        value
+    }
+    fn terminate(&mut self, terminator: Terminator) {
+        let mut successors = terminator.successors();
+        successors.dedup();
+        for successor in successors {
+            self.predecessors
+                .entry(successor)
+                .or_default()
+                .push(self.current);
+        }
+        self.blocks[self.current.0].terminator = terminator;
+        self.terminated.insert(self.current);
+    }
+    fn terminate_and_continue(&mut self, terminator: Terminator) {
+        self.terminate(terminator);
+        let dead = self.create_block();
+        self.seal(dead);
+        self.switch_to(dead);
+    }
+    fn jump(&mut self, target: BlockId) {
+        self.terminate(Terminator::Jump(target));
+    }
+    fn declare(&mut self, name: &'a str, value_type: Type<'a>) -> Variable {
+        let variable = self.variables.len();
+        self.variables.push(value_type);
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, variable);
+        }
+        variable
+    }
+    fn variable(&self, name: &str) -> Option<Variable> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).copied())
+    }
+    fn write_variable(&mut self, variable: Variable, block: BlockId, value: ValueId) {
+        self.definitions.insert((block, variable), value);
+    }
+    fn read_variable(&mut self, variable: Variable, block: BlockId) -> ValueId {
+        match self.definitions.get(&(block, variable)) {
+            Some(value) => *value,
+            None => self.read_variable_recursive(variable, block),
+        }
+    }
+    fn read_variable_recursive(&mut self, variable: Variable, block: BlockId) -> ValueId {
+        let value = if !self.sealed.contains(&block) {
+            let phi = self.phi(variable, block);
+            self.incomplete
+                .entry(block)
+                .or_default()
+                .push((variable, phi));
+            phi
+        } else if let [predecessor] = self.predecessors[&block].as_slice() {
+            let predecessor = *predecessor;
+            self.read_variable(variable, predecessor)
+        } else {
+            let phi = self.phi(variable, block);
+            self.write_variable(variable, block, phi);
+            self.add_phi_operands(variable, block, phi);
+            phi
+        };
+        self.write_variable(variable, block, value);
+        value
+    }
+    fn phi(&mut self, variable: Variable, block: BlockId) -> ValueId {
+        let value_type = self.variables[variable].clone();
+        let value = self.value(value_type.clone());
+        self.blocks[block.0].phis.push(Phi {
+            value,
+            value_type,
+            incoming: vec![],
+        });
+        value
+    }
+    fn add_phi_operands(&mut self, variable: Variable, block: BlockId, phi: ValueId) {
+        for predecessor in self.predecessors[&block].clone() {
+            let value = self.read_variable(variable, predecessor);
+            if let Some(phi) = self.blocks[block.0]
+                .phis
+                .iter_mut()
+                .find(|candidate| candidate.value == phi)
+            {
+                phi.incoming.push((predecessor, value));
+            }
+        }
+    }
+    fn seal(&mut self, block: BlockId) {
+        for (variable, phi) in self.incomplete.remove(&block).unwrap_or_default() {
+            self.add_phi_operands(variable, block, phi);
+        }
+        self.sealed.insert(block);
+    }
+    fn infer_return_type(&mut self, result: ValueId) -> Type<'a> {
+        if !self.terminated.contains(&self.current) {
+            self.terminate(Terminator::Return(result));
+        }
+        let mut reachable = HashSet::from([BlockId(0)]);
+        let mut pending = vec![BlockId(0)];
+        let mut returned: Option<Type<'a>> = None;
+        while let Some(block) = pending.pop() {
+            let terminator = &self.blocks[block.0].terminator;
+            if let Terminator::Return(value) = terminator {
+                let value_type = self.type_of(*value);
+                returned = match returned {
+                    None | Some(Type::Dynamic) => Some(value_type),
+                    Some(known) if value_type.compatible(&known) => Some(known),
+                    Some(_) => Some(Type::Dynamic),
+                };
+            }
+            for successor in terminator.successors() {
+                if reachable.insert(successor) {
+                    pending.push(successor);
+                }
+            }
+        }
+        returned.unwrap_or(Type::Unit)
+    }
+    fn finish(mut self, result: ValueId) -> Function<'a> {
+        if !self.terminated.contains(&self.current) {
+            self.terminate(Terminator::Return(result));
+        }
+        let mut function = Function {
+            name: self.name,
+            parameters: self.parameters,
+            return_type: self.return_type,
+            blocks: self.blocks,
+        };
+        remove_unreachable_blocks(&mut function);
+        remove_trivial_phis(&mut function);
+        renumber(&mut function);
+        function
+    }
+}
+
+pub struct Builder<'a, 'p> {
+    source: Source<'a>,
+    names: &'a Interner,
+    program: &'p Program<'a>,
+    functions: HashMap<&'a str, &'p function::Function<'a>>,
+    enums: HashMap<&'a str, &'p Enum<'a>>,
+    enum_names: Vec<&'a str>,
+    output: Vec<Function<'a>>,
+    lifted: Vec<Function<'a>>,
+    lambdas: usize,
+}
+
+impl<'a, 'p> Builder<'a, 'p> {
+    pub fn new(source: Source<'a>, program: &'p Program<'a>, names: &'a Interner) -> Self {
+        Self {
+            source,
+            names,
+            program,
+            functions: program
+                .functions
+                .iter()
+                .map(|function| (function.name, function))
+                .collect(),
+            enums: program
+                .enums
+                .iter()
+                .map(|enumeration| (enumeration.name, enumeration))
+                .collect(),
+            enum_names: program
+                .enums
+                .iter()
+                .map(|enumeration| enumeration.name)
+                .collect(),
+            output: vec![],
+            lifted: vec![],
+            lambdas: 0,
+        }
+    }
+    pub fn build(mut self) -> Result<'a, Module<'a>> {
+        for function in &self.program.functions {
+            self.source = function.source;
+            let built = self.build_function(function)?;
+            self.output.push(built);
+            self.output.append(&mut self.lifted);
+        }
+        Ok(Module {
+            functions: self.output,
+        })
+    }
+    fn build_function(&mut self, function: &'p function::Function<'a>) -> Result<'a, Function<'a>> {
+        let return_type = function
+            .return_type
+            .as_ref()
+            .map_or(Type::Unit, |return_type| self.type_of(return_type));
+        let mut builder = FunctionBuilder::new(function.name, return_type);
+        for argument in &function.arguments {
+            let value_type = self.type_of(&argument.argument_type);
+            let value = builder.parameter(value_type.clone());
+            let variable = builder.declare(argument.name, value_type);
+            builder.write_variable(variable, builder.current, value);
+        }
+        self.lower(&mut builder, &function.body)?;
+        if !matches!(builder.return_type, Type::Unit | Type::Dynamic) {
+            builder.terminate(Terminator::Unreachable);
+        }
+        let unit = builder.unit;
+        Ok(builder.finish(unit))
+    }
+    fn type_of(&self, type_expr: &TypeExpr<'a>) -> Type<'a> {
+        Type::from_type_expr(type_expr, &self.enum_names)
+    }
+    fn lower(&mut self, builder: &mut FunctionBuilder<'a>, node: &Node<'a>) -> Result<'a, ValueId> {
+        match node {
+            Node::Integer(integer) => match integer.value.parse() {
+                Ok(integer) => {
+                    Ok(builder.emit(Type::Int, InstructionKind::Constant(Constant::Int(integer))))
+                }
+                Err(_) => Err(self.error(ErrorKind::IntegerOverflow, integer.span)),
+            },
+            Node::Float(float) => match float.value.parse() {
+                Ok(float) => Ok(builder.emit(
+                    Type::Float,
+                    InstructionKind::Constant(Constant::Float(float)),
+                )),
+                Err(_) => Err(self.error(ErrorKind::TypeMismatch, float.span)),
+            },
+            Node::String(string) => Ok(builder.emit(
+                Type::String,
+                InstructionKind::Constant(Constant::String(unescape(string.value))),
+            )),
+            Node::Identifier(name) => self.lower_identifier(builder, name),
+            Node::Block(block) => {
+                builder.scopes.push(HashMap::new());
+                let result = block
+                    .value
+                    .iter()
+                    .try_for_each(|statement| self.lower(builder, statement).map(drop));
+                builder.scopes.pop();
+                result.map(|_| builder.unit)
+            }
+            Node::Array(array) => {
+                let elements = array
+                    .value
+                    .iter()
+                    .map(|element| self.lower(builder, element))
+                    .collect::<Result<'a, Vec<_>>>()?;
+                let element_type = elements
+                    .first()
+                    .map_or(Type::Dynamic, |element| builder.type_of(*element));
+                Ok(builder.emit(
+                    Type::Array(Box::new(element_type)),
+                    InstructionKind::Array(elements),
+                ))
+            }
+            Node::Expression(expression) => self.lower_expression(builder, expression),
+            Node::Statement(statement) => self.lower_statement(builder, statement),
+        }
+    }
+    fn lower_identifier(
+        &mut self,
+        builder: &mut FunctionBuilder<'a>,
+        name: &Spanned<&'a str>,
+    ) -> Result<'a, ValueId> {
+        if let Some(variable) = builder.variable(name.value) {
+            return Ok(builder.read_variable(variable, builder.current));
+        }
+        if let Some(function) = self.functions.get(name.value) {
+            let value_type = Type::Function {
+                parameters: function
+                    .arguments
+                    .iter()
+                    .map(|argument| self.type_of(&argument.argument_type))
+                    .collect(),
+                return_type: Box::new(
+                    function
+                        .return_type
+                        .as_ref()
+                        .map_or(Type::Unit, |return_type| self.type_of(return_type)),
+                ),
+            };
+            return Ok(builder.emit(
+                value_type,
+                InstructionKind::Constant(Constant::Function(name.value)),
+            ));
+        }
+        if stdlib::lookup(name.value).is_some() {
+            return Ok(builder.emit(
+                Type::Dynamic,
+                InstructionKind::Constant(Constant::Function(name.value)),
+            ));
+        }
+        if name.value.contains("::") {
+            return self.construct(builder, name, vec![]);
+        }
+        Err(self.error(ErrorKind::UndefinedVariable, name.span))
+    }
+    fn lower_expression(
+        &mut self,
+        builder: &mut FunctionBuilder<'a>,
+        expression: &Expression<'a>,
+    ) -> Result<'a, ValueId> {
+        let span = expression.calculate_span();
+        match expression {
+            Expression::Infix {
+                operator: Operator::Assignment,
+                lhs,
+                rhs,
+            } => {
+                let value = self.lower(builder, rhs)?;
+                self.assign(builder, lhs, value)?;
+                Ok(builder.unit)
+            }
+            Expression::Infix { operator, lhs, rhs } => {
+                let lhs = self.lower(builder, lhs)?;
+                let rhs = self.lower(builder, rhs)?;
+                let operator = match BinaryOperator::from_operator(*operator) {
+                    Some(operator) => operator,
+                    None => return Err(self.error(ErrorKind::TypeMismatch, span)),
+                };
+                let value_type = match (builder.type_of(lhs), builder.type_of(rhs)) {
+                    (Type::Dynamic, _) | (_, Type::Dynamic) if !operator.is_comparison() => {
+                        Type::Dynamic
+                    }
+                    (lhs, rhs) if lhs != Type::Dynamic && rhs != Type::Dynamic && lhs != rhs => {
+                        return Err(self.error(ErrorKind::TypeMismatch, span))
+                    }
+                    _ if operator.is_comparison() => Type::Bool,
+                    (lhs, _) => lhs,
+                };
+                Ok(builder.emit(value_type, InstructionKind::Binary { operator, lhs, rhs }))
+            }
+            Expression::Prefix { operator, value } => {
+                let value = self.lower(builder, value)?;
+                match operator.value {
+                    Operator::Plus => Ok(value),
+                    Operator::Minus => {
+                        let value_type = builder.type_of(value);
+                        Ok(builder.emit(value_type, InstructionKind::Negate(value)))
+                    }
+                    _ => Err(self.error(ErrorKind::TypeMismatch, span)),
+                }
+            }
+            Expression::Call {
+                callee, arguments, ..
+            } => match callee.as_ref() {
+                Node::Identifier(name)
+                    if name.value.contains("::") && !self.functions.contains_key(name.value) =>
+                {
+                    let fields = self.lower_all(builder, &arguments.value)?;
+                    self.construct(builder, name, fields)
+                }
+                Node::Expression(Expression::Member { target, name }) => {
+                    let target = self.lower(builder, target)?;
+                    let mut values = self.lower_all(builder, &arguments.value)?;
+                    values.insert(0, target);
+                    self.call(builder, name, values)
+                }
+                Node::Identifier(name) if builder.variable(name.value).is_none() => {
+                    let values = self.lower_all(builder, &arguments.value)?;
+                    self.call(builder, name, values)
+                }
+                _ => {
+                    let callee = self.lower(builder, callee)?;
+                    let arguments = self.lower_all(builder, &arguments.value)?;
+                    let value_type = match builder.type_of(callee) {
+                        Type::Function { return_type, .. } => *return_type,
+                        _ => Type::Dynamic,
+                    };
+                    Ok(builder.emit(
+                        value_type,
+                        InstructionKind::CallIndirect { callee, arguments },
+                    ))
+                }
+            },
+            Expression::Member { name, .. } => {
+                Err(self.error(ErrorKind::UndefinedVariable, name.span))
+            }
+            Expression::Lambda { parameters, body } => {
+                self.lower_lambda(builder, &parameters.value, body)
+            }
+            Expression::Index { target, index } => {
+                let array = self.lower(builder, target)?;
+                if let Node::Expression(Expression::Range {
+                    start,
+                    end,
+                    inclusive,
+                }) = index.value.as_ref()
+                {
+                    let (start, end) = self.lower_bounds(builder, start, end, *inclusive)?;
+                    return Ok(builder.emit(
+                        builder.type_of(array),
+                        InstructionKind::Call {
+                            function: "slice",
+                            arguments: vec![array, start, end],
+                        },
+                    ));
+                }
+                let index = self.lower(builder, &index.value)?;
+                let value_type = match builder.type_of(array) {
+                    Type::Array(element) => *element,
+                    _ => Type::Dynamic,
+                };
+                Ok(builder.emit(value_type, InstructionKind::Extract { array, index }))
+            }
+            Expression::Match { value, arms, .. } => {
+                let value = self.lower(builder, value)?;
+                let merge = builder.create_block();
+                let result = builder.variables.len();
+                builder.variables.push(Type::Dynamic);
+                let mut result_type = None;
+                for arm in &arms.value {
+                    let fail = builder.create_block();
+                    let mut bindings = vec![];
+                    self.lower_pattern(builder, &arm.pattern, value, fail, &mut bindings)?;
+                    builder.scopes.push(HashMap::new());
+                    for (name, binding) in bindings {
+                        let variable = builder.declare(name, builder.type_of(binding));
+                        builder.write_variable(variable, builder.current, binding);
+                    }
+                    let body = self.lower(builder, &arm.body);
+                    builder.scopes.pop();
+                    let body = body?;
+                    let body_type = builder.type_of(body);
+                    result_type = match result_type {
+                        None => Some(body_type),
+                        Some(current) if current == body_type => Some(current),
+                        Some(_) => Some(Type::Dynamic),
+                    };
+                    builder.write_variable(result, builder.current, body);
+                    builder.jump(merge);
+                    builder.seal(fail);
+                    builder.switch_to(fail);
+                }
+                builder.terminate(Terminator::Unreachable);
+                builder.variables[result] = result_type.unwrap_or(Type::Unit);
+                builder.seal(merge);
+                builder.switch_to(merge);
+                Ok(builder.read_variable(result, merge))
+            }
+            Expression::Ternary {
+                test,
+                consequent,
+                alternative,
+            } => {
+                let condition = self.lower(builder, test)?;
+                self.expect_bool(builder, condition, test)?;
+                let blocks = [builder.create_block(), builder.create_block()];
+                let merge = builder.create_block();
+                let result = builder.variables.len();
+                builder.variables.push(Type::Dynamic);
+                builder.terminate(Terminator::Branch {
+                    condition,
+                    consequent: blocks[0],
+                    alternative: blocks[1],
+                });
+                let mut types = vec![];
+                for (block, branch) in blocks.into_iter().zip([consequent, alternative]) {
+                    builder.seal(block);
+                    builder.switch_to(block);
+                    let value = self.lower(builder, branch)?;
+                    types.push(builder.type_of(value));
+                    builder.write_variable(result, builder.current, value);
+                    builder.jump(merge);
+                }
+                builder.variables[result] = match (&types[0], &types[1]) {
+                    (consequent, alternative) if consequent == alternative => consequent.clone(),
+                    _ => Type::Dynamic,
+                };
+                builder.seal(merge);
+                builder.switch_to(merge);
+                Ok(builder.read_variable(result, merge))
+            }
+            Expression::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let (start, end) = self.lower_bounds(builder, start, end, *inclusive)?;
+                Ok(builder.emit(
+                    Type::Array(Box::new(Type::Int)),
+                    InstructionKind::Call {
+                        function: "range",
+                        arguments: vec![start, end],
+                    },
+                ))
+            }
+        }
+    }
+    fn lower_bounds(
+        &mut self,
+        builder: &mut FunctionBuilder<'a>,
+        start: &Node<'a>,
+        end: &Node<'a>,
+        inclusive: bool,
+    ) -> Result<'a, (ValueId, ValueId)> {
+        let span = Span::new(start.calculate_span().start, end.calculate_span().end);
+        let start = self.lower(builder, start)?;
+        let mut end = self.lower(builder, end)?;
+        for value in [start, end] {
+            if !matches!(builder.type_of(value), Type::Int | Type::Dynamic) {
+                return Err(self.error(ErrorKind::TypeMismatch, span));
+            }
+        }
+        if inclusive {
+            let one = builder.emit(Type::Int, InstructionKind::Constant(Constant::Int(1)));
+            end = builder.emit(
+                Type::Int,
+                InstructionKind::Binary {
+                    operator: BinaryOperator::Add,
+                    lhs: end,
+                    rhs: one,
+                },
+            );
+        }
+        Ok((start, end))
+    }
+    fn lower_all(
+        &mut self,
+        builder: &mut FunctionBuilder<'a>,
+        nodes: &[Node<'a>],
+    ) -> Result<'a, Vec<ValueId>> {
+        nodes.iter().map(|node| self.lower(builder, node)).collect()
+    }
+    fn lower_statement(
+        &mut self,
+        builder: &mut FunctionBuilder<'a>,
+        statement: &Statement<'a>,
+    ) -> Result<'a, ValueId> {
+        match statement {
+            Statement::While(while_statement) => {
+                let header = builder.create_block();
+                let body = builder.create_block();
+                let exit = builder.create_block();
+                builder.jump(header);
+                builder.switch_to(header);
+                let condition = self.lower(builder, &while_statement.value.test)?;
+                self.expect_bool(builder, condition, &while_statement.value.test)?;
+                builder.terminate(Terminator::Branch {
+                    condition,
+                    consequent: body,
+                    alternative: exit,
+                });
+                builder.seal(body);
+                builder.switch_to(body);
+                self.lower(builder, &while_statement.value.body)?;
+                builder.jump(header);
+                builder.seal(header);
+                builder.seal(exit);
+                builder.switch_to(exit);
+                Ok(builder.unit)
+            }
+            Statement::For(for_statement) => {
+                let for_statement = &for_statement.value;
+                let (array, start, end) = match for_statement.iterable.as_ref() {
+                    Node::Expression(Expression::Range {
+                        start,
+                        end,
+                        inclusive,
+                    }) => {
+                        let (start, end) = self.lower_bounds(builder, start, end, *inclusive)?;
+                        (None, start, end)
+                    }
+                    iterable => {
+                        let array = self.lower(builder, iterable)?;
+                        if !matches!(builder.type_of(array), Type::Array(_) | Type::Dynamic) {
+                            return Err(
+                                self.error(ErrorKind::TypeMismatch, iterable.calculate_span())
+                            );
+                        }
+                        let start =
+                            builder.emit(Type::Int, InstructionKind::Constant(Constant::Int(0)));
+                        let end = builder.emit(
+                            Type::Int,
+                            InstructionKind::Call {
+                                function: "len",
+                                arguments: vec![array],
+                            },
+                        );
+                        (Some(array), start, end)
+                    }
+                };
+                let counter = builder.variables.len();
+                builder.variables.push(Type::Int);
+                builder.write_variable(counter, builder.current, start);
+                let header = builder.create_block();
+                let body = builder.create_block();
+                let exit = builder.create_block();
+                builder.jump(header);
+                builder.switch_to(header);
+                let index = builder.read_variable(counter, header);
+                let condition = builder.emit(
+                    Type::Bool,
+                    InstructionKind::Binary {
+                        operator: BinaryOperator::LessThen,
+                        lhs: index,
+                        rhs: end,
+                    },
+                );
+                builder.terminate(Terminator::Branch {
+                    condition,
+                    consequent: body,
+                    alternative: exit,
+                });
+                builder.seal(body);
+                builder.switch_to(body);
+                let element = match array {
+                    Some(array) => {
+                        let element_type = match builder.type_of(array) {
+                            Type::Array(element) => *element,
+                            _ => Type::Dynamic,
+                        };
+                        builder.emit(element_type, InstructionKind::Extract { array, index })
+                    }
+                    None => index,
+                };
+                builder.scopes.push(HashMap::new());
+                let variable =
+                    builder.declare(for_statement.binding.value, builder.type_of(element));
+                builder.write_variable(variable, builder.current, element);
+                let result = self.lower(builder, &for_statement.body);
+                builder.scopes.pop();
+                result?;
+                let index = builder.read_variable(counter, builder.current);
+                let one = builder.emit(Type::Int, InstructionKind::Constant(Constant::Int(1)));
+                let next = builder.emit(
+                    Type::Int,
+                    InstructionKind::Binary {
+                        operator: BinaryOperator::Add,
+                        lhs: index,
+                        rhs: one,
+                    },
+                );
+                builder.write_variable(counter, builder.current, next);
+                builder.jump(header);
+                builder.seal(header);
+                builder.seal(exit);
+                builder.switch_to(exit);
+                Ok(builder.unit)
+            }
+            Statement::If(if_statement) => {
+                let if_statement = &if_statement.value;
+                let condition = self.lower(builder, &if_statement.test)?;
+                self.expect_bool(builder, condition, &if_statement.test)?;
+                let consequent = builder.create_block();
+                let alternative = if_statement
+                    .alternative
+                    .as_ref()
+                    .map(|alternative| (builder.create_block(), alternative));
+                let merge = builder.create_block();
+                builder.terminate(Terminator::Branch {
+                    condition,
+                    consequent,
+                    alternative: alternative.as_ref().map_or(merge, |(block, _)| *block),
+                });
+                builder.seal(consequent);
+                builder.switch_to(consequent);
+                self.lower(builder, &if_statement.consequent)?;
+                builder.jump(merge);
+                if let Some((block, alternative)) = alternative {
+                    builder.seal(block);
+                    builder.switch_to(block);
+                    self.lower(builder, alternative)?;
+                    builder.jump(merge);
+                }
+                builder.seal(merge);
+                builder.switch_to(merge);
+                Ok(builder.unit)
+            }
+            Statement::Let(let_statement) => {
+                let value = match &let_statement.value.init {
+                    Some(init) => self.lower(builder, init)?,
+                    None => builder.unit,
+                };
+                let value_type = match &let_statement.value.value_type {
+                    Some(value_type) => self.type_of(value_type),
+                    None => builder.type_of(value),
+                };
+                let variable = builder.declare(let_statement.value.name, value_type);
+                builder.write_variable(variable, builder.current, value);
+                Ok(builder.unit)
+            }
+            Statement::Return(return_statement) => {
+                let value = match &return_statement.value.value {
+                    Some(value) => self.lower(builder, value)?,
+                    None => builder.unit,
+                };
+                if !builder.type_of(value).compatible(&builder.return_type) {
+                    return Err(self.error(ErrorKind::TypeMismatch, return_statement.span));
+                }
+                builder.terminate_and_continue(Terminator::Return(value));
+                Ok(builder.unit)
+            }
+        }
+    }
+    fn lower_lambda(
+        &mut self,
+        builder: &mut FunctionBuilder<'a>,
+        parameters: &[Parameter<'a>],
+        body: &Node<'a>,
+    ) -> Result<'a, ValueId> {
+        let mut captures = vec![];
+        for name in free_variables(parameters, body) {
+            if let Some(variable) = builder.variable(name.value) {
+                if !captures.iter().any(|(capture, _)| *capture == name.value) {
+                    captures.push((name.value, variable));
+                }
+            }
+        }
+        let name = self
+            .names
+            .intern(format!("{}$lambda{}", builder.name, self.lambdas));
+        self.lambdas += 1;
+        let mut lambda = FunctionBuilder::new(name, Type::Dynamic);
+        let mut values = vec![];
+        let mut parameter_types = vec![];
+        for (capture, variable) in &captures {
+            let value_type = builder.variables[*variable].clone();
+            let value = lambda.parameter(value_type.clone());
+            let inner = lambda.declare(capture, value_type);
+            lambda.write_variable(inner, lambda.current, value);
+            values.push(builder.read_variable(*variable, builder.current));
+        }
+        for parameter in parameters {
+            let value_type = parameter
+                .parameter_type
+                .as_ref()
+                .map_or(Type::Dynamic, |parameter_type| self.type_of(parameter_type));
+            let value = lambda.parameter(value_type.clone());
+            let variable = lambda.declare(parameter.name.value, value_type.clone());
+            lambda.write_variable(variable, lambda.current, value);
+            parameter_types.push(value_type);
+        }
+        let result = self.lower(&mut lambda, body)?;
+        lambda.return_type = lambda.infer_return_type(result);
+        let value_type = Type::Function {
+            parameters: parameter_types,
+            return_type: Box::new(lambda.return_type.clone()),
+        };
+        let lifted = lambda.finish(result);
+        self.lifted.push(lifted);
+        Ok(builder.emit(
+            value_type,
+            InstructionKind::Closure {
+                function: name,
+                captures: values,
+            },
+        ))
+    }
+    fn lower_pattern(
+        &mut self,
+        builder: &mut FunctionBuilder<'a>,
+        pattern: &Pattern<'a>,
+        value: ValueId,
+        fail: BlockId,
+        bindings: &mut Vec<(&'a str, ValueId)>,
+    ) -> Result<'a, ()> {
+        let (constant, value_type) = match pattern {
+            Pattern::Wildcard(_) => return Ok(()),
+            Pattern::Binding(name) => {
+                bindings.push((name.value, value));
+                return Ok(());
+            }
+            Pattern::Integer(literal) => match literal.value.parse() {
+                Ok(integer) => (Constant::Int(integer), Type::Int),
+                Err(_) => return Err(self.error(ErrorKind::IntegerOverflow, literal.span)),
+            },
+            Pattern::Float(literal) => match literal.value.parse() {
+                Ok(float) => (Constant::Float(float), Type::Float),
+                Err(_) => return Err(self.error(ErrorKind::TypeMismatch, literal.span)),
+            },
+            Pattern::Variant { path, fields } => {
+                let (enumeration, index) = self.variant(path)?;
+                let tag = builder.emit(Type::Int, InstructionKind::Tag(value));
+                let expected = builder.emit(
+                    Type::Int,
+                    InstructionKind::Constant(Constant::Int(index as i64)),
+                );
+                self.test(builder, tag, expected, fail);
+                let variant = &enumeration.variants[index];
+                for (field, pattern) in fields
+                    .iter()
+                    .flat_map(|fields| fields.value.iter())
+                    .enumerate()
+                {
+                    let field_type = variant
+                        .fields
+                        .as_ref()
+                        .and_then(|fields| fields.get(field))
+                        .map_or(Type::Dynamic, |field_type| self.type_of(field_type));
+                    let field = builder.emit(
+                        field_type,
+                        InstructionKind::Field {
+                            value,
+                            index: field,
+                        },
+                    );
+                    self.lower_pattern(builder, pattern, field, fail, bindings)?;
+                }
+                return Ok(());
+            }
+        };
+        let expected = builder.emit(value_type, InstructionKind::Constant(constant));
+        self.test(builder, value, expected, fail);
+        Ok(())
+    }
+    fn test(
+        &mut self,
+        builder: &mut FunctionBuilder<'a>,
+        lhs: ValueId,
+        rhs: ValueId,
+        fail: BlockId,
+    ) {
+        let condition = builder.emit(
+            Type::Bool,
+            InstructionKind::Binary {
+                operator: BinaryOperator::Equal,
+                lhs,
+                rhs,
+            },
+        );
+        let success = builder.create_block();
+        builder.terminate(Terminator::Branch {
+            condition,
+            consequent: success,
+            alternative: fail,
+        });
+        builder.seal(success);
+        builder.switch_to(success);
+    }
+    fn assign(
+        &mut self,
+        builder: &mut FunctionBuilder<'a>,
+        target: &Node<'a>,
+        value: ValueId,
+    ) -> Result<'a, ()> {
+        match target {
+            Node::Identifier(name) => match builder.variable(name.value) {
+                Some(variable) => {
+                    builder.write_variable(variable, builder.current, value);
+                    Ok(())
+                }
+                None => Err(self.error(ErrorKind::UndefinedVariable, name.span)),
+            },
+            Node::Expression(Expression::Index { target, index }) => {
+                let array = self.lower(builder, target)?;
+                let index = self.lower(builder, &index.value)?;
+                let array_type = builder.type_of(array);
+                let updated = builder.emit(
+                    array_type,
+                    InstructionKind::Insert {
+                        array,
+                        index,
+                        value,
+                    },
+                );
+                self.assign(builder, target, updated)
+            }
+            _ => Err(self.error(ErrorKind::InvalidAssignmentTarget, target.calculate_span())),
+        }
+    }
+    fn call(
+        &mut self,
+        builder: &mut FunctionBuilder<'a>,
+        name: &Spanned<&'a str>,
+        arguments: Vec<ValueId>,
+    ) -> Result<'a, ValueId> {
+        let value_type = if let Some(function) = self.functions.get(name.value) {
+            function
+                .return_type
+                .as_ref()
+                .map_or(Type::Unit, |return_type| self.type_of(return_type))
+        } else if let Some(builtin) = stdlib::lookup(name.value) {
+            match builtin.signature.return_type {
+                signature::Type::Int => Type::Int,
+                signature::Type::Float => Type::Float,
+                signature::Type::Bool => Type::Bool,
+                signature::Type::String => Type::String,
+                signature::Type::Unit => Type::Unit,
+                signature::Type::Number => arguments
+                    .first()
+                    .map_or(Type::Dynamic, |argument| builder.type_of(*argument)),
+                signature::Type::Array => Type::Array(Box::new(Type::Dynamic)),
+                signature::Type::Any => Type::Dynamic,
+            }
+        } else {
+            return Err(self.error(ErrorKind::UndefinedFunction, name.span));
+        };
+        Ok(builder.emit(
+            value_type,
+            InstructionKind::Call {
+                function: name.value,
+                arguments,
+            },
+        ))
+    }
+    fn construct(
+        &mut self,
+        builder: &mut FunctionBuilder<'a>,
+        path: &Spanned<&'a str>,
+        fields: Vec<ValueId>,
+    ) -> Result<'a, ValueId> {
+        let (enumeration, index) = self.variant(path)?;
+        let variant = &enumeration.variants[index];
+        if variant.arity() != fields.len() {
+            return Err(self.error(
+                ErrorKind::WrongNumberOfFields {
+                    expected: variant.arity(),
+                    received: fields.len(),
+                },
+                path.span,
+            ));
+        }
+        Ok(builder.emit(
+            Type::Enum(enumeration.name),
+            InstructionKind::Variant {
+                enumeration: enumeration.name,
+                variant: variant.name,
+                tag: index,
+                fields,
+            },
+        ))
+    }
+    fn variant(&self, path: &Spanned<&'a str>) -> Result<'a, (&'p Enum<'a>, usize)> {
+        let (enum_name, variant_name) = variant_path(path.value);
+        let enumeration = self
+            .enums
+            .get(enum_name)
+            .ok_or_else(|| self.error(ErrorKind::UnknownEnum, path.span))?;
+        let index = enumeration
+            .variants
+            .iter()
+            .position(|variant| variant.name == variant_name)
+            .ok_or_else(|| self.error(ErrorKind::UnknownVariant, path.span))?;
+        Ok((enumeration, index))
+    }
+    fn expect_bool(
+        &self,
+        builder: &FunctionBuilder<'a>,
+        condition: ValueId,
+        node: &Node<'a>,
+    ) -> Result<'a, ()> {
+        match builder.type_of(condition) {
+            Type::Bool | Type::Dynamic => Ok(()),
+            _ => Err(self.error(ErrorKind::TypeMismatch, node.calculate_span())),
+        }
+    }
+    fn error(&self, kind: ErrorKind, span: Span) -> Box<Error<'a>> {
+        Box::new(Error::new(kind, span, self.source))
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{BlockId, Function};
+
+pub struct Dominators {
+    order: Vec<BlockId>,
+    immediate: HashMap<BlockId, BlockId>,
+}
+
+impl Dominators {
+    pub fn new(function: &Function) -> Self {
+        let entry = function.entry();
+        let order = reverse_postorder(function);
+        let position: HashMap<_, _> = order
+            .iter()
+            .enumerate()
+            .map(|(index, block)| (*block, index))
+            .collect();
+        let predecessors = function.predecessors();
+        let mut immediate = HashMap::from([(entry, entry)]);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block in order.iter().skip(1) {
+                let mut candidate = None;
+                for predecessor in &predecessors[block] {
+                    if !immediate.contains_key(predecessor) {
+                        continue;
+                    }
+                    candidate = Some(match candidate {
+                        None => *predecessor,
+                        Some(current) => intersect(&immediate, &position, current, *predecessor),
+                    });
+                }
+                if let Some(candidate) = candidate {
+                    if immediate.insert(*block, candidate) != Some(candidate) {
+                        changed = true;
+                    }
+                }
+            }
+        }
+        Self { order, immediate }
+    }
+    pub fn reverse_postorder(&self) -> &[BlockId] {
+        &self.order
+    }
+    pub fn is_reachable(&self, block: BlockId) -> bool {
+        self.immediate.contains_key(&block)
+    }
+    pub fn immediate_dominator(&self, block: BlockId) -> Option<BlockId> {
+        self.immediate
+            .get(&block)
+            .copied()
+            .filter(|dominator| *dominator != block)
+    }
+    pub fn dominates(&self, dominator: BlockId, mut block: BlockId) -> bool {
+        if !self.is_reachable(block) {
+            return false;
+        }
+        loop {
+            if block == dominator {
+                return true;
+            }
+            match self.immediate_dominator(block) {
+                Some(parent) => block = parent,
+                None => return false,
+            }
+        }
+    }
+}
+
+fn intersect(
+    immediate: &HashMap<BlockId, BlockId>,
+    position: &HashMap<BlockId, usize>,
+    mut lhs: BlockId,
+    mut rhs: BlockId,
+) -> BlockId {
+    while lhs != rhs {
+        while position[&lhs] > position[&rhs] {
+            lhs = immediate[&lhs];
+        }
+        while position[&rhs] > position[&lhs] {
+            rhs = immediate[&rhs];
+        }
+    }
+    lhs
+}
+pub fn reverse_postorder(function: &Function) -> Vec<BlockId> {
+    let mut visited = HashSet::new();
+    let mut order = vec![];
+    let mut stack = vec![(function.entry(), false)];
+    while let Some((block, finished)) = stack.pop() {
+        if finished {
+            order.push(block);
+            continue;
+        }
+        if !visited.insert(block) {
+            continue;
+        }
+        stack.push((block, true));
+        if let Some(current) = function.block(block) {
+            for successor in current.terminator.successors().into_iter().rev() {
+                if !visited.contains(&successor) {
+                    stack.push((successor, false));
+                }
+            }
+        }
+    }
+    order.reverse();
+    order
+}
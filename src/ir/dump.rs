@@ -0,0 +1,171 @@
+use std::fmt::{self, Display};
+
+use super::{
+    BinaryOperator, Block, Constant, Function, Instruction, InstructionKind, Module, Phi,
+    Terminator, Type,
+};
+
+fn list<T: Display>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl<'a> Display for Type<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Unit => write!(f, "unit"),
+            Type::Bool => write!(f, "bool"),
+            Type::Int => write!(f, "int"),
+            Type::Float => write!(f, "float"),
+            Type::String => write!(f, "string"),
+            Type::Array(element) => write!(f, "[{}]", element),
+            Type::Enum(name) => write!(f, "{}", name),
+            Type::Function {
+                parameters,
+                return_type,
+            } => write!(f, "fn({}) -> {}", list(parameters), return_type),
+            Type::Dynamic => write!(f, "dyn"),
+        }
+    }
+}
+impl<'a> Display for Constant<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constant::Unit => write!(f, "()"),
+            Constant::Bool(boolean) => write!(f, "{}", boolean),
+            Constant::Int(integer) => write!(f, "{}", integer),
+            Constant::Float(float) => write!(f, "{:?}", float),
+            Constant::String(string) => write!(f, "{:?}", string),
+            Constant::Function(name) => write!(f, "@{}", name),
+        }
+    }
+}
+impl Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BinaryOperator::Add => "add",
+            BinaryOperator::Subtract => "sub",
+            BinaryOperator::Multiply => "mul",
+            BinaryOperator::Divide => "div",
+            BinaryOperator::Equal => "eq",
+            BinaryOperator::LessThen => "lt",
+            BinaryOperator::GreaterThen => "gt",
+            BinaryOperator::LessThenEqual => "le",
+            BinaryOperator::GreaterThenEqual => "ge",
+        };
+        write!(f, "{}", name)
+    }
+}
+impl<'a> Display for InstructionKind<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstructionKind::Constant(constant) => write!(f, "const {}", constant),
+            InstructionKind::Binary { operator, lhs, rhs } => {
+                write!(f, "{} {}, {}", operator, lhs, rhs)
+            }
+            InstructionKind::Negate(value) => write!(f, "neg {}", value),
+            InstructionKind::Call {
+                function,
+                arguments,
+            } => write!(f, "call @{}({})", function, list(arguments)),
+            InstructionKind::CallIndirect { callee, arguments } => {
+                write!(f, "call {}({})", callee, list(arguments))
+            }
+            InstructionKind::Closure { function, captures } => {
+                write!(f, "closure @{}[{}]", function, list(captures))
+            }
+            InstructionKind::Array(elements) => write!(f, "array [{}]", list(elements)),
+            InstructionKind::Extract { array, index } => {
+                write!(f, "extract {}, {}", array, index)
+            }
+            InstructionKind::Insert {
+                array,
+                index,
+                value,
+            } => write!(f, "insert {}, {}, {}", array, index, value),
+            InstructionKind::Variant {
+                enumeration,
+                variant,
+                fields,
+                ..
+            } => write!(f, "variant {}::{}({})", enumeration, variant, list(fields)),
+            InstructionKind::Tag(value) => write!(f, "tag {}", value),
+            InstructionKind::Field { value, index } => write!(f, "field {}, {}", value, index),
+        }
+    }
+}
+impl<'a> Display for Instruction<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} = {}", self.value, self.value_type, self.kind)
+    }
+}
+impl<'a> Display for Phi<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let incoming = self
+            .incoming
+            .iter()
+            .map(|(block, value)| format!("[{}: {}]", block, value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{}: {} = phi {}", self.value, self.value_type, incoming)
+    }
+}
+impl Display for Terminator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Terminator::Jump(target) => write!(f, "jump {}", target),
+            Terminator::Branch {
+                condition,
+                consequent,
+                alternative,
+            } => write!(f, "branch {}, {}, {}", condition, consequent, alternative),
+            Terminator::Return(value) => write!(f, "return {}", value),
+            Terminator::Unreachable => write!(f, "unreachable"),
+        }
+    }
+}
+impl<'a> Display for Block<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}:", self.id)?;
+        for phi in &self.phis {
+            writeln!(f, "    {}", phi)?;
+        }
+        for instruction in &self.instructions {
+            writeln!(f, "    {}", instruction)?;
+        }
+        writeln!(f, "    {}", self.terminator)
+    }
+}
+impl<'a> Display for Function<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|(value, value_type)| format!("{}: {}", value, value_type))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            f,
+            "function @{}({}) -> {} {{",
+            self.name, parameters, self.return_type
+        )?;
+        for block in &self.blocks {
+            write!(f, "{}", block)?;
+        }
+        writeln!(f, "}}")
+    }
+}
+impl<'a> Display for Module<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, function) in self.functions.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", function)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,345 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+use crate::parser::ast::{expression::Operator, types::TypeExpr};
+
+pub mod build;
+pub mod dominators;
+pub mod dump;
+pub mod transform;
+pub mod verify;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ValueId(pub usize);
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BlockId(pub usize);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Type<'a> {
+    Unit,
+    Bool,
+    Int,
+    Float,
+    String,
+    Array(Box<Type<'a>>),
+    Enum(&'a str),
+    Function {
+        parameters: Vec<Type<'a>>,
+        return_type: Box<Type<'a>>,
+    },
+    Dynamic,
+}
+#[derive(Clone, Debug, PartialEq)]
+pub enum Constant<'a> {
+    Unit,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Function(&'a str),
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Equal,
+    LessThen,
+    GreaterThen,
+    LessThenEqual,
+    GreaterThenEqual,
+}
+#[derive(Clone, Debug, PartialEq)]
+pub enum InstructionKind<'a> {
+    Constant(Constant<'a>),
+    Binary {
+        operator: BinaryOperator,
+        lhs: ValueId,
+        rhs: ValueId,
+    },
+    Negate(ValueId),
+    Call {
+        function: &'a str,
+        arguments: Vec<ValueId>,
+    },
+    CallIndirect {
+        callee: ValueId,
+        arguments: Vec<ValueId>,
+    },
+    Closure {
+        function: &'a str,
+        captures: Vec<ValueId>,
+    },
+    Array(Vec<ValueId>),
+    Extract {
+        array: ValueId,
+        index: ValueId,
+    },
+    Insert {
+        array: ValueId,
+        index: ValueId,
+        value: ValueId,
+    },
+    Variant {
+        enumeration: &'a str,
+        variant: &'a str,
+        tag: usize,
+        fields: Vec<ValueId>,
+    },
+    Tag(ValueId),
+    Field {
+        value: ValueId,
+        index: usize,
+    },
+}
+#[derive(Clone, Debug)]
+pub struct Instruction<'a> {
+    pub value: ValueId,
+    pub value_type: Type<'a>,
+    pub kind: InstructionKind<'a>,
+}
+#[derive(Clone, Debug)]
+pub struct Phi<'a> {
+    pub value: ValueId,
+    pub value_type: Type<'a>,
+    pub incoming: Vec<(BlockId, ValueId)>,
+}
+#[derive(Clone, Debug, PartialEq)]
+pub enum Terminator {
+    Jump(BlockId),
+    Branch {
+        condition: ValueId,
+        consequent: BlockId,
+        alternative: BlockId,
+    },
+    Return(ValueId),
+    Unreachable,
+}
+#[derive(Clone, Debug)]
+pub struct Block<'a> {
+    pub id: BlockId,
+    pub phis: Vec<Phi<'a>>,
+    pub instructions: Vec<Instruction<'a>>,
+    pub terminator: Terminator,
+}
+#[derive(Clone, Debug)]
+pub struct Function<'a> {
+    pub name: &'a str,
+    pub parameters: Vec<(ValueId, Type<'a>)>,
+    pub return_type: Type<'a>,
+    pub blocks: Vec<Block<'a>>,
+}
+#[derive(Clone, Debug)]
+pub struct Module<'a> {
+    pub functions: Vec<Function<'a>>,
+}
+
+impl<'a> Type<'a> {
+    pub fn from_type_expr(type_expr: &TypeExpr<'a>, enums: &[&'a str]) -> Self {
+        match type_expr {
+            TypeExpr::Path { path, .. } => match path.value {
+                "int" => Type::Int,
+                "float" => Type::Float,
+                "bool" => Type::Bool,
+                "string" => Type::String,
+                name => match enums.iter().find(|enumeration| **enumeration == name) {
+                    Some(enumeration) => Type::Enum(enumeration),
+                    None => Type::Dynamic,
+                },
+            },
+            TypeExpr::Unit(_) => Type::Unit,
+            TypeExpr::Array { element, .. } => {
+                Type::Array(Box::new(Type::from_type_expr(element, enums)))
+            }
+            TypeExpr::Function {
+                arguments,
+                return_type,
+                ..
+            } => Type::Function {
+                parameters: arguments
+                    .value
+                    .iter()
+                    .map(|argument| Type::from_type_expr(argument, enums))
+                    .collect(),
+                return_type: Box::new(return_type.as_ref().map_or(Type::Unit, |return_type| {
+                    Type::from_type_expr(return_type, enums)
+                })),
+            },
+            TypeExpr::Tuple(_) | TypeExpr::Reference { .. } => Type::Dynamic,
+        }
+    }
+    pub fn compatible(&self, other: &Type<'a>) -> bool {
+        match (self, other) {
+            (Type::Dynamic, _) | (_, Type::Dynamic) => true,
+            (Type::Array(lhs), Type::Array(rhs)) => lhs.compatible(rhs),
+            (
+                Type::Function {
+                    parameters: lhs_parameters,
+                    return_type: lhs_return,
+                },
+                Type::Function {
+                    parameters: rhs_parameters,
+                    return_type: rhs_return,
+                },
+            ) => {
+                lhs_parameters.len() == rhs_parameters.len()
+                    && lhs_parameters
+                        .iter()
+                        .zip(rhs_parameters)
+                        .all(|(lhs, rhs)| lhs.compatible(rhs))
+                    && lhs_return.compatible(rhs_return)
+            }
+            (lhs, rhs) => lhs == rhs,
+        }
+    }
+}
+impl BinaryOperator {
+    pub fn from_operator(operator: Operator) -> Option<Self> {
+        match operator {
+            Operator::Plus => Some(BinaryOperator::Add),
+            Operator::Minus => Some(BinaryOperator::Subtract),
+            Operator::Multiply => Some(BinaryOperator::Multiply),
+            Operator::Divide => Some(BinaryOperator::Divide),
+            Operator::Equal => Some(BinaryOperator::Equal),
+            Operator::LessThen => Some(BinaryOperator::LessThen),
+            Operator::GreaterThen => Some(BinaryOperator::GreaterThen),
+            Operator::LessThenEqual => Some(BinaryOperator::LessThenEqual),
+            Operator::GreaterThenEqual => Some(BinaryOperator::GreaterThenEqual),
+            Operator::Assignment => None,
+        }
+    }
+    pub fn is_comparison(self) -> bool {
+        !matches!(
+            self,
+            BinaryOperator::Add
+                | BinaryOperator::Subtract
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+        )
+    }
+}
+impl<'a> InstructionKind<'a> {
+    pub fn operands(&self) -> Vec<ValueId> {
+        match self {
+            InstructionKind::Constant(_) => vec![],
+            InstructionKind::Binary { lhs, rhs, .. } => vec![*lhs, *rhs],
+            InstructionKind::Negate(value) | InstructionKind::Tag(value) => vec![*value],
+            InstructionKind::Field { value, .. } => vec![*value],
+            InstructionKind::Call { arguments, .. } => arguments.clone(),
+            InstructionKind::CallIndirect { callee, arguments } => {
+                let mut operands = vec![*callee];
+                operands.extend(arguments);
+                operands
+            }
+            InstructionKind::Closure { captures, .. } => captures.clone(),
+            InstructionKind::Array(elements) => elements.clone(),
+            InstructionKind::Extract { array, index } => vec![*array, *index],
+            InstructionKind::Insert {
+                array,
+                index,
+                value,
+            } => vec![*array, *index, *value],
+            InstructionKind::Variant { fields, .. } => fields.clone(),
+        }
+    }
+    pub fn operands_mut(&mut self) -> Vec<&mut ValueId> {
+        match self {
+            InstructionKind::Constant(_) => vec![],
+            InstructionKind::Binary { lhs, rhs, .. } => vec![lhs, rhs],
+            InstructionKind::Negate(value) | InstructionKind::Tag(value) => vec![value],
+            InstructionKind::Field { value, .. } => vec![value],
+            InstructionKind::Call { arguments, .. } => arguments.iter_mut().collect(),
+            InstructionKind::CallIndirect { callee, arguments } => {
+                let mut operands = vec![callee];
+                operands.extend(arguments.iter_mut());
+                operands
+            }
+            InstructionKind::Closure { captures, .. } => captures.iter_mut().collect(),
+            InstructionKind::Array(elements) => elements.iter_mut().collect(),
+            InstructionKind::Extract { array, index } => vec![array, index],
+            InstructionKind::Insert {
+                array,
+                index,
+                value,
+            } => vec![array, index, value],
+            InstructionKind::Variant { fields, .. } => fields.iter_mut().collect(),
+        }
+    }
+}
+impl<'a> Instruction<'a> {
+    pub fn has_side_effects(&self) -> bool {
+        let traps = matches!(self.value_type, Type::Int | Type::Dynamic);
+        match &self.kind {
+            InstructionKind::Call { .. }
+            | InstructionKind::CallIndirect { .. }
+            | InstructionKind::Extract { .. }
+            | InstructionKind::Insert { .. } => true,
+            InstructionKind::Binary { operator, .. } => traps && !operator.is_comparison(),
+            InstructionKind::Negate(_) => traps,
+            _ => false,
+        }
+    }
+}
+impl Terminator {
+    pub fn successors(&self) -> Vec<BlockId> {
+        match self {
+            Terminator::Jump(target) => vec![*target],
+            Terminator::Branch {
+                consequent,
+                alternative,
+                ..
+            } => vec![*consequent, *alternative],
+            Terminator::Return(_) | Terminator::Unreachable => vec![],
+        }
+    }
+    pub fn operands_mut(&mut self) -> Vec<&mut ValueId> {
+        match self {
+            Terminator::Branch { condition, .. } => vec![condition],
+            Terminator::Return(value) => vec![value],
+            Terminator::Jump(_) | Terminator::Unreachable => vec![],
+        }
+    }
+    pub fn operands(&self) -> Vec<ValueId> {
+        match self {
+            Terminator::Branch { condition, .. } => vec![*condition],
+            Terminator::Return(value) => vec![*value],
+            Terminator::Jump(_) | Terminator::Unreachable => vec![],
+        }
+    }
+}
+impl<'a> Function<'a> {
+    pub fn block(&self, id: BlockId) -> Option<&Block<'a>> {
+        self.blocks.iter().find(|block| block.id == id)
+    }
+    pub fn block_mut(&mut self, id: BlockId) -> Option<&mut Block<'a>> {
+        self.blocks.iter_mut().find(|block| block.id == id)
+    }
+    pub fn entry(&self) -> BlockId {
+        self.blocks[0].id
+    }
+    pub fn predecessors(&self) -> HashMap<BlockId, Vec<BlockId>> {
+        let mut predecessors: HashMap<_, Vec<_>> =
+            self.blocks.iter().map(|block| (block.id, vec![])).collect();
+        for block in &self.blocks {
+            let mut successors = block.terminator.successors();
+            successors.dedup();
+            for successor in successors {
+                predecessors.entry(successor).or_default().push(block.id);
+            }
+        }
+        predecessors
+    }
+}
+impl Display for ValueId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "%{}", self.0)
+    }
+}
+impl Display for BlockId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bb{}", self.0)
+    }
+}
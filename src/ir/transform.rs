@@ -0,0 +1,180 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{dominators::reverse_postorder, BlockId, Function, Terminator, ValueId};
+
+pub fn remove_unreachable_blocks(function: &mut Function) {
+    let order = reverse_postorder(function);
+    let reachable = order.iter().copied().collect::<HashSet<_>>();
+    function
+        .blocks
+        .retain(|block| reachable.contains(&block.id));
+    function
+        .blocks
+        .sort_by_key(|block| order.iter().position(|id| *id == block.id));
+    let predecessors = function.predecessors();
+    for block in &mut function.blocks {
+        for phi in &mut block.phis {
+            phi.incoming
+                .retain(|(predecessor, _)| predecessors[&block.id].contains(predecessor));
+        }
+    }
+}
+pub fn remove_trivial_phis(function: &mut Function) {
+    let mut replacements = HashMap::new();
+    loop {
+        let mut changed = false;
+        for block in &mut function.blocks {
+            let mut index = 0;
+            while index < block.phis.len() {
+                let phi = &block.phis[index];
+                let mut operands = phi
+                    .incoming
+                    .iter()
+                    .map(|(_, value)| resolve(&replacements, *value))
+                    .filter(|value| *value != phi.value)
+                    .collect::<Vec<_>>();
+                operands.sort();
+                operands.dedup();
+                match operands.as_slice() {
+                    [value] => {
+                        replacements.insert(phi.value, *value);
+                        block.phis.remove(index);
+                        changed = true;
+                    }
+                    _ => index += 1,
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    replace_uses(function, &replacements);
+}
+pub fn resolve(replacements: &HashMap<ValueId, ValueId>, mut value: ValueId) -> ValueId {
+    while let Some(replacement) = replacements.get(&value) {
+        value = *replacement;
+    }
+    value
+}
+pub fn replace_uses(function: &mut Function, replacements: &HashMap<ValueId, ValueId>) {
+    if replacements.is_empty() {
+        return;
+    }
+    for block in &mut function.blocks {
+        for phi in &mut block.phis {
+            for (_, value) in &mut phi.incoming {
+                *value = resolve(replacements, *value);
+            }
+        }
+        for instruction in &mut block.instructions {
+            for operand in instruction.kind.operands_mut() {
+                *operand = resolve(replacements, *operand);
+            }
+        }
+        for operand in block.terminator.operands_mut() {
+            *operand = resolve(replacements, *operand);
+        }
+    }
+}
+pub fn renumber(function: &mut Function) {
+    let mut values = HashMap::new();
+    let mut number = |value: &mut ValueId| {
+        let next = ValueId(values.len());
+        *value = *values.entry(*value).or_insert(next);
+    };
+    for (value, _) in &mut function.parameters {
+        number(value);
+    }
+    for block in &mut function.blocks {
+        for phi in &mut block.phis {
+            number(&mut phi.value);
+        }
+        for instruction in &mut block.instructions {
+            number(&mut instruction.value);
+        }
+    }
+    let blocks: HashMap<_, _> = function
+        .blocks
+        .iter()
+        .enumerate()
+        .map(|(index, block)| (block.id, BlockId(index)))
+        .collect();
+    for block in &mut function.blocks {
+        block.id = blocks[&block.id];
+        for phi in &mut block.phis {
+            for (predecessor, value) in &mut phi.incoming {
+                *predecessor = blocks[predecessor];
+                number(value);
+            }
+        }
+        for instruction in &mut block.instructions {
+            for operand in instruction.kind.operands_mut() {
+                number(operand);
+            }
+        }
+        for operand in block.terminator.operands_mut() {
+            number(operand);
+        }
+        match &mut block.terminator {
+            Terminator::Jump(target) => *target = blocks[target],
+            Terminator::Branch {
+                consequent,
+                alternative,
+                ..
+            } => {
+                *consequent = blocks[consequent];
+                *alternative = blocks[alternative];
+            }
+            Terminator::Return(_) | Terminator::Unreachable => {}
+        }
+    }
+}
+pub fn merge_blocks(function: &mut Function) -> bool {
+    let mut changed = false;
+    loop {
+        let predecessors = function.predecessors();
+        let entry = function.entry();
+        let candidate = function
+            .blocks
+            .iter()
+            .find_map(|block| match block.terminator {
+                Terminator::Jump(target)
+                    if target != entry
+                        && target != block.id
+                        && predecessors[&target].len() == 1
+                        && function
+                            .block(target)
+                            .is_some_and(|target| target.phis.is_empty()) =>
+                {
+                    Some((block.id, target))
+                }
+                _ => None,
+            });
+        let Some((block, target)) = candidate else {
+            return changed;
+        };
+        let position = function
+            .blocks
+            .iter()
+            .position(|candidate| candidate.id == target)
+            .unwrap_or_default();
+        let merged = function.blocks.remove(position);
+        for successor in merged.terminator.successors() {
+            if let Some(successor) = function.block_mut(successor) {
+                for phi in &mut successor.phis {
+                    for (predecessor, _) in &mut phi.incoming {
+                        if *predecessor == target {
+                            *predecessor = block;
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(block) = function.block_mut(block) {
+            block.instructions.extend(merged.instructions);
+            block.terminator = merged.terminator;
+        }
+        changed = true;
+    }
+}
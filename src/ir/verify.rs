@@ -0,0 +1,207 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+};
+
+use crate::stdlib;
+
+use super::{
+    dominators::Dominators, BlockId, Constant, Function, InstructionKind, Module, Terminator, Type,
+    ValueId,
+};
+
+#[derive(Clone, Debug)]
+pub struct VerifyError {
+    pub function: String,
+    pub message: String,
+}
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid IR in @{}: {}", self.function, self.message)
+    }
+}
+
+pub fn verify(module: &Module) -> Result<(), VerifyError> {
+    let functions = module
+        .functions
+        .iter()
+        .map(|function| function.name)
+        .collect::<HashSet<_>>();
+    for function in &module.functions {
+        verify_function(function)?;
+        let references = function
+            .blocks
+            .iter()
+            .flat_map(|block| &block.instructions)
+            .filter_map(|instruction| match &instruction.kind {
+                InstructionKind::Constant(Constant::Function(name))
+                | InstructionKind::Call { function: name, .. }
+                | InstructionKind::Closure { function: name, .. } => Some(*name),
+                _ => None,
+            });
+        for name in references {
+            if !functions.contains(name) && stdlib::lookup(name).is_none() {
+                return Err(VerifyError {
+                    function: function.name.to_string(),
+                    message: format!("reference to unknown function @{}", name),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+enum Definition {
+    Parameter,
+    Phi(BlockId),
+    Instruction(BlockId, usize),
+}
+
+pub fn verify_function<'a>(function: &Function<'a>) -> Result<(), VerifyError> {
+    let fail = |message: String| VerifyError {
+        function: function.name.to_string(),
+        message,
+    };
+    if function.blocks.is_empty() {
+        return Err(fail("function has no blocks".to_string()));
+    }
+    let mut blocks = HashSet::new();
+    for block in &function.blocks {
+        if !blocks.insert(block.id) {
+            return Err(fail(format!("block {} is defined twice", block.id)));
+        }
+    }
+    let mut definitions = HashMap::new();
+    let mut types = HashMap::new();
+    let mut define = |value: ValueId, value_type: &Type<'a>, definition| {
+        types.insert(value, value_type.clone());
+        match definitions.insert(value, definition) {
+            Some(_) => Err(fail(format!("value {} is defined more than once", value))),
+            None => Ok(()),
+        }
+    };
+    for (value, value_type) in &function.parameters {
+        define(*value, value_type, Definition::Parameter)?;
+    }
+    for block in &function.blocks {
+        for phi in &block.phis {
+            define(phi.value, &phi.value_type, Definition::Phi(block.id))?;
+        }
+        for (index, instruction) in block.instructions.iter().enumerate() {
+            define(
+                instruction.value,
+                &instruction.value_type,
+                Definition::Instruction(block.id, index),
+            )?;
+        }
+    }
+    let predecessors = function.predecessors();
+    if !predecessors[&function.entry()].is_empty() {
+        return Err(fail(format!(
+            "entry block {} has predecessors",
+            function.entry()
+        )));
+    }
+    let dominators = Dominators::new(function);
+    let available = |value: ValueId, block: BlockId, position: usize| match definitions.get(&value)
+    {
+        None => Err(fail(format!(
+            "value {} is used in {} but never defined",
+            value, block
+        ))),
+        Some(Definition::Parameter) => Ok(()),
+        Some(Definition::Phi(definition)) if dominators.dominates(*definition, block) => Ok(()),
+        Some(Definition::Instruction(definition, index))
+            if (*definition == block && *index < position)
+                || (*definition != block && dominators.dominates(*definition, block)) =>
+        {
+            Ok(())
+        }
+        Some(_) => Err(fail(format!(
+            "definition of {} does not dominate its use in {}",
+            value, block
+        ))),
+    };
+    let type_of = |value: ValueId| types.get(&value).cloned().unwrap_or(Type::Dynamic);
+    for block in &function.blocks {
+        for successor in block.terminator.successors() {
+            if !blocks.contains(&successor) {
+                return Err(fail(format!(
+                    "{} jumps to undefined block {}",
+                    block.id, successor
+                )));
+            }
+        }
+        let mut expected = predecessors[&block.id].clone();
+        expected.sort();
+        for phi in &block.phis {
+            let mut incoming = phi
+                .incoming
+                .iter()
+                .map(|(predecessor, _)| *predecessor)
+                .collect::<Vec<_>>();
+            incoming.sort();
+            if incoming != expected {
+                return Err(fail(format!(
+                    "phi {} in {} does not match the block's predecessors",
+                    phi.value, block.id
+                )));
+            }
+            if !dominators.is_reachable(block.id) {
+                continue;
+            }
+            for (predecessor, value) in &phi.incoming {
+                if dominators.is_reachable(*predecessor) {
+                    available(*value, *predecessor, usize::MAX)?;
+                }
+            }
+        }
+        if !dominators.is_reachable(block.id) {
+            continue;
+        }
+        for (index, instruction) in block.instructions.iter().enumerate() {
+            for operand in instruction.kind.operands() {
+                available(operand, block.id, index)?;
+            }
+            if let InstructionKind::Binary { operator, lhs, rhs } = &instruction.kind {
+                if !type_of(*lhs).compatible(&type_of(*rhs)) {
+                    return Err(fail(format!(
+                        "operands of {} have different types",
+                        instruction.value
+                    )));
+                }
+                if operator.is_comparison() && instruction.value_type != Type::Bool {
+                    return Err(fail(format!(
+                        "comparison {} does not produce a bool",
+                        instruction.value
+                    )));
+                }
+            }
+        }
+        for operand in block.terminator.operands() {
+            available(operand, block.id, usize::MAX)?;
+        }
+        match &block.terminator {
+            Terminator::Branch { condition, .. }
+                if !type_of(*condition).compatible(&Type::Bool) =>
+            {
+                return Err(fail(format!(
+                    "branch condition {} in {} is not a bool",
+                    condition, block.id
+                )));
+            }
+            Terminator::Return(value) if !type_of(*value).compatible(&function.return_type) => {
+                return Err(fail(format!(
+                    "{} returns {} but the function returns {}",
+                    block.id,
+                    type_of(*value),
+                    function.return_type
+                )));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
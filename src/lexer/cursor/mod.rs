@@ -1,6 +1,6 @@
 use crate::common::{source::Source, span::Span};
 
-use self::{chunk::Chunk, slice::utf8_slice};
+use self::chunk::Chunk;
 
 pub mod chunk;
 pub mod slice;
@@ -9,6 +9,7 @@ pub mod slice;
 pub struct Cursor<'a> {
     start: usize,
     current: usize,
+    offsets: Vec<usize>,
     pub input: Source<'a>,
 }
 
@@ -17,6 +18,12 @@ impl<'a> Cursor<'a> {
         Self {
             start: 0,
             current: 0,
+            offsets: input
+                .code
+                .char_indices()
+                .map(|(offset, _)| offset)
+                .chain(Some(input.code.len()))
+                .collect(),
             input,
         }
     }
@@ -26,10 +33,7 @@ impl<'a> Cursor<'a> {
     }
     #[inline]
     pub fn peek(&self) -> char {
-        utf8_slice(self.input.code, self.current, self.current + 1)
-            .chars()
-            .next()
-            .expect("Empty slice in `peek`")
+        self.lookup(0)
     }
     pub fn span(&self) -> Span {
         Span {
@@ -39,19 +43,25 @@ impl<'a> Cursor<'a> {
     }
     pub fn chunk(&mut self) -> Chunk<'a> {
         let span = self.span();
-        let data = utf8_slice(self.input.code, span.start, span.end);
+        let data = self.slice(span.start, span.end);
         self.clear();
         Chunk::new(data, span)
     }
-    pub fn lookup(&mut self, lookup_amount: usize) -> char {
-        utf8_slice(
-            self.input.code,
+    pub fn lookup(&self, lookup_amount: usize) -> char {
+        self.slice(
             self.current + lookup_amount,
-            self.current + (lookup_amount + 1),
+            self.current + lookup_amount + 1,
         )
         .chars()
         .next()
-        .unwrap()
+        .unwrap_or('\0')
+    }
+    pub fn check(&self, lookup_amount: usize, expected: char) -> bool {
+        self.slice(
+            self.current + lookup_amount,
+            self.current + lookup_amount + 1,
+        )
+        .starts_with(expected)
     }
     #[inline]
     pub fn clear(&mut self) {
@@ -59,6 +69,10 @@ impl<'a> Cursor<'a> {
     }
     #[inline]
     pub fn eof(&self) -> bool {
-        self.current > (self.input.code.chars().count() - 1)
+        self.current >= self.offsets.len() - 1
+    }
+    fn slice(&self, start: usize, end: usize) -> &'a str {
+        let offset = |index: usize| self.offsets[index.min(self.offsets.len() - 1)];
+        &self.input.code[offset(start)..offset(end.max(start))]
     }
 }
@@ -1,13 +1,9 @@
 pub fn utf8_slice(s: &str, start: usize, end: usize) -> &str {
-    let mut iter = s
-        .char_indices()
-        .map(|(pos, _)| pos)
-        .chain(Some(s.len()))
-        .skip(start)
-        .peekable();
-    let start_pos = *iter.peek().unwrap();
-    for _ in start..end {
-        iter.next();
-    }
-    &s[start_pos..*iter.peek().unwrap()]
+    let mut positions = s.char_indices().map(|(pos, _)| pos).chain(Some(s.len()));
+    let start_pos = positions.nth(start).unwrap_or(s.len());
+    let end_pos = match end.checked_sub(start + 1) {
+        Some(skip) => positions.nth(skip).unwrap_or(s.len()),
+        None => start_pos,
+    };
+    &s[start_pos..end_pos]
 }
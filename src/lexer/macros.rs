@@ -4,15 +4,15 @@ macro_rules! choose {
         if $self.cursor.lookup(1) == $char {
             $self.cursor.next();
             $self.cursor.next();
-            Ok(crate::lexer::token::Token::new(
+            Ok($crate::lexer::token::Token::new(
                 $self.cursor.chunk(),
-                crate::lexer::token::TokenKind::$one,
+                $crate::lexer::token::TokenKind::$one,
             ))
         } else {
             $self.cursor.next();
-            Ok(crate::lexer::token::Token::new(
+            Ok($crate::lexer::token::Token::new(
                 $self.cursor.chunk(),
-                crate::lexer::token::TokenKind::$two,
+                $crate::lexer::token::TokenKind::$two,
             ))
         }
     }};
@@ -22,7 +22,7 @@ macro_rules! char {
         $self.cursor.next();
         Ok(Token::new(
             $self.cursor.chunk(),
-            crate::lexer::token::TokenKind::$ident,
+            $crate::lexer::token::TokenKind::$ident,
         ))
     }};
 }
@@ -11,30 +11,51 @@ use self::{
 };
 
 pub mod cursor;
+pub mod escape;
 pub mod iter;
 pub mod token;
 #[macro_use]
 pub mod macros;
+pub const KEYWORDS: [(&str, TokenKind); 16] = [
+    ("if", TokenKind::If),
+    ("else", TokenKind::Else),
+    ("while", TokenKind::While),
+    ("for", TokenKind::For),
+    ("in", TokenKind::In),
+    ("let", TokenKind::Let),
+    ("mut", TokenKind::Mut),
+    ("function", TokenKind::Function),
+    ("fn", TokenKind::Fn),
+    ("return", TokenKind::Return),
+    ("enum", TokenKind::Enum),
+    ("match", TokenKind::Match),
+    ("_", TokenKind::Underscore),
+    ("import", TokenKind::Import),
+    ("module", TokenKind::Module),
+    ("pub", TokenKind::Pub),
+];
 #[derive(Clone, Debug)]
 pub struct Lexer<'a> {
     cursor: Cursor<'a>,
+    comments: Vec<Chunk<'a>>,
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(input: Source<'a>) -> Self {
         Self {
             cursor: Cursor::new(input),
+            comments: vec![],
         }
     }
     #[inline]
     pub fn is_number_start(&mut self) -> bool {
         let char = self.cursor.peek();
-        ('0'..='9').contains(&char)
+        char.is_ascii_digit()
     }
     #[inline]
     pub fn is_number_continue(&mut self) -> bool {
         let char = self.cursor.peek();
-        self.is_number_start() || char == '.'
+        self.is_number_start() || (char == '.' && !self.cursor.check(1, '.'))
     }
     #[inline]
     pub fn is_identifier_start(&mut self) -> bool {
@@ -47,15 +68,25 @@ impl<'a> Lexer<'a> {
         UnicodeXID::is_xid_continue(char)
     }
     pub fn skip(&mut self) {
-        while !self.cursor.eof()
-            && (self.cursor.peek() == ' '
-                || self.cursor.peek() == '\t'
-                || self.cursor.peek() == '\n')
-        {
-            self.cursor.next();
+        while !self.cursor.eof() {
+            match self.cursor.peek() {
+                ' ' | '\t' | '\n' | '\r' => self.cursor.next(),
+                '/' if self.cursor.check(1, '/') => {
+                    self.cursor.clear();
+                    while !self.cursor.eof() && self.cursor.peek() != '\n' {
+                        self.cursor.next();
+                    }
+                    let comment = self.cursor.chunk();
+                    self.comments.push(comment);
+                }
+                _ => break,
+            }
         }
         self.cursor.clear();
     }
+    pub fn comments(&self) -> &[Chunk<'a>] {
+        &self.comments
+    }
     pub fn lex_identifier(&mut self) -> Result<'a, Chunk<'a>> {
         while !self.cursor.eof() && self.is_identifier_continue() {
             self.cursor.next()
@@ -64,18 +95,14 @@ impl<'a> Lexer<'a> {
     }
     pub fn lex_keyword_or_identifier(&mut self) -> Result<'a, Token<'a>> {
         let identifier = self.lex_identifier()?;
-        Ok(match identifier.data {
-            "if" => Token::new(identifier, TokenKind::If),
-            "else" => Token::new(identifier, TokenKind::Else),
-            "while" => Token::new(identifier, TokenKind::While),
-            "let" => Token::new(identifier, TokenKind::Let),
-            "mut" => Token::new(identifier, TokenKind::Mut),
-            "function" => Token::new(identifier, TokenKind::Function),
-            "return" => Token::new(identifier, TokenKind::Return),
-            _ => Token::new(identifier, TokenKind::Identifier),
-        })
+        let kind = KEYWORDS
+            .iter()
+            .find(|(keyword, _)| *keyword == identifier.data)
+            .map_or(TokenKind::Identifier, |(_, kind)| *kind);
+        Ok(Token::new(identifier, kind))
     }
     pub fn lex_integer(&mut self) -> Result<'a, Token<'a>> {
+        let first = self.cursor.peek();
         let mut is_float = false;
         let mut has_error = false;
         while !self.cursor.eof() && self.is_number_continue() {
@@ -88,11 +115,10 @@ impl<'a> Lexer<'a> {
             self.cursor.next();
         }
         if has_error {
-            return Err(Box::new(Error::new(
-                ErrorKind::UnexpectedCharacter,
-                self.cursor.span(),
-                self.cursor.input,
-            )));
+            return Err(self.error(ErrorKind::MalformedNumber));
+        }
+        if !self.cursor.eof() && self.is_identifier_continue() {
+            return self.lex_invalid_identifier(first);
         }
         let kind = if is_float {
             TokenKind::Float
@@ -101,37 +127,83 @@ impl<'a> Lexer<'a> {
         };
         Ok(Token::new(self.cursor.chunk(), kind))
     }
+    pub fn lex_string(&mut self) -> Result<'a, Token<'a>> {
+        self.cursor.next();
+        while !self.cursor.eof() && self.cursor.peek() != '"' {
+            if self.cursor.peek() == '\\' {
+                self.cursor.next();
+                if self.cursor.eof() {
+                    break;
+                }
+            }
+            self.cursor.next();
+        }
+        if self.cursor.eof() {
+            return Err(self.error(ErrorKind::UnterminatedString));
+        }
+        self.cursor.next();
+        Ok(Token::new(self.cursor.chunk(), TokenKind::String))
+    }
     pub fn lex_char(&mut self) -> Result<'a, Token<'a>> {
         let result = match self.cursor.peek() {
             '+' => char!(Plus; self),
             '-' => choose!('>' => Arrow || Minus; self),
             '*' => char!(Multiply; self),
             '/' => char!(Divide; self),
-            ':' => char!(Colon; self),
+            '&' => char!(Ampersand; self),
+            '|' => char!(Pipe; self),
+            '.' if self.cursor.lookup(1) == '.' => {
+                self.cursor.next();
+                choose!('=' => DotDotEqual || DotDot; self)
+            }
+            '.' => char!(Dot; self),
+            '?' => char!(Question; self),
+            ':' => choose!(':' => DoubleColon || Colon; self),
             ';' => char!(Semicolon; self),
             '(' => char!(LeftParenthesis; self),
             ')' => char!(RightParenthesis; self),
             '{' => char!(LeftCurlyBrace; self),
             '}' => char!(RightCurlyBrace; self),
+            '[' => char!(LeftSquareBracket; self),
+            ']' => char!(RightSquareBracket; self),
             ',' => char!(Comma; self),
+            '=' if self.cursor.lookup(1) == '>' => {
+                self.cursor.next();
+                char!(FatArrow; self)
+            }
             '=' => choose!('=' => Equal || Assignment; self),
             '>' => choose!('=' => GreaterThenEqual || GreaterThen; self),
             '<' => choose!('=' => LessThenEqual || LessThen; self),
-            _ => Err(Box::new(Error::new(
-                ErrorKind::UnexpectedEndOfInput,
-                self.cursor.span(),
-                self.cursor.input,
-            ))),
+            char => {
+                self.cursor.next();
+                Err(self.error(ErrorKind::UnknownCharacter { char }))
+            }
         };
         result
     }
+    fn lex_invalid_identifier(&mut self, char: char) -> Result<'a, Token<'a>> {
+        while !self.cursor.eof() && self.is_identifier_continue() {
+            self.cursor.next();
+        }
+        Err(self.error(ErrorKind::InvalidIdentifierStart { char }))
+    }
+    fn error(&self, kind: ErrorKind) -> Box<Error<'a>> {
+        Box::new(Error::new(kind, self.cursor.span(), self.cursor.input))
+    }
     pub fn next_token(&mut self) -> Result<'a, Token<'a>> {
         if self.is_number_start() {
             return self.lex_integer();
         }
-        if self.is_identifier_start() {
+        if self.is_identifier_start() || self.cursor.peek() == '_' {
             return self.lex_keyword_or_identifier();
         }
+        if self.cursor.peek() == '"' {
+            return self.lex_string();
+        }
+        if self.is_identifier_continue() {
+            let char = self.cursor.peek();
+            return self.lex_invalid_identifier(char);
+        }
         self.lex_char()
     }
 }
@@ -1,3 +1,5 @@
+use crate::common::json::{Json, ToJson};
+
 use super::cursor::chunk::Chunk;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -5,11 +7,14 @@ pub enum TokenKind {
     Identifier,
     Integer,
     Float,
+    String,
 
     Plus,
     Minus,
     Multiply,
     Divide,
+    Ampersand,
+    Pipe,
     Assignment,
     Equal,
     LessThen,
@@ -21,18 +26,35 @@ pub enum TokenKind {
     RightParenthesis,
     LeftCurlyBrace,
     RightCurlyBrace,
+    LeftSquareBracket,
+    RightSquareBracket,
     Colon,
+    DoubleColon,
     Semicolon,
     Arrow,
+    FatArrow,
     Comma,
+    Dot,
+    DotDot,
+    DotDotEqual,
+    Question,
+    Underscore,
 
     If,
     Else,
     While,
+    For,
+    In,
     Let,
     Mut,
     Function,
+    Fn,
     Return,
+    Enum,
+    Match,
+    Import,
+    Module,
+    Pub,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -46,3 +68,17 @@ impl<'a> Token<'a> {
         Self { chunk, kind }
     }
 }
+impl ToJson for TokenKind {
+    fn to_json(&self) -> Json {
+        Json::String(format!("{:?}", self))
+    }
+}
+impl<'a> ToJson for Token<'a> {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("kind", self.kind.to_json()),
+            ("text", self.chunk.data.to_json()),
+            ("span", self.chunk.span.to_json()),
+        ])
+    }
+}
@@ -0,0 +1,16 @@
+pub mod backend;
+pub mod checker;
+pub mod common;
+pub mod compiler;
+pub mod interpreter;
+pub mod ir;
+pub mod lexer;
+pub mod linter;
+pub mod loader;
+pub mod monomorphize;
+pub mod optimizer;
+pub mod parser;
+pub mod stdlib;
+pub mod syntax;
+
+pub use compiler::{Compiler, Session};
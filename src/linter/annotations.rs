@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use crate::{common::source::Source, lexer::Lexer};
+
+use super::Rule;
+
+#[derive(Clone, Debug, Default)]
+pub struct Annotations {
+    lines: HashMap<usize, Vec<Rule>>,
+}
+
+impl Annotations {
+    pub fn parse(source: Source) -> Self {
+        let mut lexer = Lexer::new(source);
+        while let Some(Ok(_)) = lexer.next() {}
+        let mut lines = HashMap::new();
+        let mut chars = source.code.chars();
+        let (mut position, mut line, mut trailing) = (0, 0, false);
+        for comment in lexer.comments() {
+            for char in chars.by_ref().take(comment.span.start - position) {
+                match char {
+                    '\n' => (line, trailing) = (line + 1, false),
+                    char if !char.is_whitespace() => trailing = true,
+                    _ => {}
+                }
+            }
+            position = comment.span.start;
+            let Some(rules) = comment.data[2..]
+                .trim()
+                .strip_prefix("allow(")
+                .and_then(|rest| rest.strip_suffix(')'))
+            else {
+                continue;
+            };
+            lines
+                .entry(if trailing { line } else { line + 1 })
+                .or_insert_with(Vec::new)
+                .extend(
+                    rules
+                        .split(',')
+                        .filter_map(|rule| Rule::from_name(rule.trim())),
+                );
+        }
+        Self { lines }
+    }
+    pub fn allows(&self, rule: Rule, line: usize) -> bool {
+        self.lines
+            .get(&line)
+            .is_some_and(|rules| rules.contains(&rule))
+    }
+}
@@ -0,0 +1,78 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::common::diagnostic::{DiagnosticSink, Severity};
+
+use super::Rule;
+
+pub const CONFIG_FILE: &str = "lint.toml";
+
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    severities: HashMap<Rule, Severity>,
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut severities = HashMap::new();
+        let mut section = None;
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = |message: String| format!("{}:{}: {}", CONFIG_FILE, index + 1, message);
+            if let Some(name) = line
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+            {
+                if name.trim() != "rules" {
+                    return Err(error(format!("unknown section `{}`", name.trim())));
+                }
+                section = Some(name.trim());
+                continue;
+            }
+            if section.is_none() {
+                return Err(error("expected a `[rules]` section".to_string()));
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(error(format!(
+                    "expected `rule = \"severity\"`, found `{}`",
+                    line
+                )));
+            };
+            let rule = Rule::from_name(key.trim())
+                .ok_or_else(|| error(format!("unknown rule `{}`", key.trim())))?;
+            let value = value.trim().trim_matches('"');
+            let severity = match value {
+                "allow" => Severity::Allow,
+                "warn" => Severity::Warning,
+                "deny" => Severity::Error,
+                _ => return Err(error(format!("unknown severity `{}`", value))),
+            };
+            severities.insert(rule, severity);
+        }
+        Ok(Self { severities })
+    }
+    pub fn find(path: &Path) -> Result<Self, String> {
+        let directory = path.parent().unwrap_or(path);
+        for ancestor in directory.ancestors() {
+            let candidate = ancestor.join(CONFIG_FILE);
+            if candidate.is_file() {
+                let text = fs::read_to_string(&candidate).map_err(|err| err.to_string())?;
+                return Self::parse(&text);
+            }
+        }
+        Ok(Self::default())
+    }
+    pub fn apply(&self, diagnostics: &mut DiagnosticSink) {
+        for rule in Rule::ALL {
+            diagnostics.set(rule.kind(), self.severity(rule));
+        }
+    }
+    pub fn severity(&self, rule: Rule) -> Severity {
+        self.severities
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_severity())
+    }
+}
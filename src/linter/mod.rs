@@ -0,0 +1,148 @@
+use crate::{
+    common::{
+        diagnostic::{Diagnostic, DiagnosticSink, Severity, Suggestion},
+        error::ErrorKind,
+        source::Source,
+        span::Span,
+    },
+    parser::ast::Program,
+};
+
+use self::annotations::Annotations;
+
+pub mod annotations;
+pub mod config;
+pub mod rules;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rule {
+    UnusedVariables,
+    UnusedFunctions,
+    UnusedArguments,
+    SnakeCase,
+    RedundantParentheses,
+    SelfComparison,
+    EmptyBlock,
+}
+pub struct Linter<'a, 'p> {
+    source: Source<'a>,
+    program: &'p Program<'a>,
+    chars: Vec<char>,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 7] = [
+        Rule::UnusedVariables,
+        Rule::UnusedFunctions,
+        Rule::UnusedArguments,
+        Rule::SnakeCase,
+        Rule::RedundantParentheses,
+        Rule::SelfComparison,
+        Rule::EmptyBlock,
+    ];
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::UnusedVariables => "unused_variables",
+            Rule::UnusedFunctions => "unused_functions",
+            Rule::UnusedArguments => "unused_arguments",
+            Rule::SnakeCase => "snake_case",
+            Rule::RedundantParentheses => "redundant_parentheses",
+            Rule::SelfComparison => "self_comparison",
+            Rule::EmptyBlock => "empty_block",
+        }
+    }
+    pub fn kind(self) -> &'static str {
+        match self {
+            Rule::UnusedVariables => "UnusedVariable",
+            Rule::UnusedFunctions => "UnusedFunction",
+            Rule::UnusedArguments => "UnusedArgument",
+            Rule::SnakeCase => "NonSnakeCaseName",
+            Rule::RedundantParentheses => "RedundantParentheses",
+            Rule::SelfComparison => "SelfComparison",
+            Rule::EmptyBlock => "EmptyBlock",
+        }
+    }
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rule| rule.name() == name)
+    }
+    pub fn of(kind: &ErrorKind) -> Option<Self> {
+        let name = kind.name();
+        Self::ALL.into_iter().find(|rule| rule.kind() == name)
+    }
+    pub fn default_severity(self) -> Severity {
+        match self {
+            Rule::SelfComparison => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+impl<'a, 'p> Linter<'a, 'p> {
+    pub fn new(source: Source<'a>, program: &'p Program<'a>) -> Self {
+        Self {
+            source,
+            program,
+            chars: source.code.chars().collect(),
+        }
+    }
+    pub fn run(&self, diagnostics: &mut DiagnosticSink<'a>) {
+        let annotations = Annotations::parse(self.source);
+        let mut lints = self
+            .check()
+            .into_iter()
+            .filter(|lint| {
+                Rule::of(&lint.kind)
+                    .is_none_or(|rule| !annotations.allows(rule, self.line(lint.span.start)))
+            })
+            .collect::<Vec<_>>();
+        lints.sort_by_key(|lint| (lint.span.start, lint.span.end));
+        for lint in lints {
+            diagnostics.emit(lint);
+        }
+    }
+    fn lint(&self, kind: ErrorKind, span: Span) -> Diagnostic<'a> {
+        Diagnostic::warning(kind, span, self.source)
+    }
+    fn line(&self, position: usize) -> usize {
+        self.chars
+            .iter()
+            .take(position)
+            .filter(|char| **char == '\n')
+            .count()
+    }
+}
+
+pub fn apply_fixes(code: &str, diagnostics: &[Diagnostic]) -> (String, Vec<usize>) {
+    let overlaps = |a: &Suggestion, b: &Suggestion| {
+        let (a, b) = (a.span, b.span);
+        (a.start < b.end && b.start < a.end)
+            || (a.start == a.end && b.start == b.end && a.start == b.start)
+    };
+    let mut accepted: Vec<Suggestion> = vec![];
+    let mut fixed = vec![];
+    for (index, diagnostic) in diagnostics.iter().enumerate() {
+        if diagnostic.suggestions.is_empty() {
+            continue;
+        }
+        if diagnostic
+            .suggestions
+            .iter()
+            .any(|edit| accepted.iter().any(|other| overlaps(edit, other)))
+        {
+            continue;
+        }
+        accepted.extend(diagnostic.suggestions.iter().cloned());
+        fixed.push(index);
+    }
+    accepted.sort_by_key(|edit| (edit.span.start, edit.span.end));
+    let chars = code.chars().collect::<Vec<_>>();
+    let mut output = String::new();
+    let mut position = 0;
+    for edit in accepted {
+        output.extend(&chars[position..edit.span.start]);
+        output.push_str(&edit.replacement);
+        position = edit.span.end;
+    }
+    output.extend(&chars[position..]);
+    (output, fixed)
+}
@@ -0,0 +1,488 @@
+use std::collections::HashSet;
+
+use crate::{
+    common::{diagnostic::Diagnostic, error::ErrorKind, span::Span},
+    parser::ast::{
+        calculate_span::CalculateSpan,
+        expression::{Expression, Operator},
+        function::Function,
+        node::Node,
+        pattern::Pattern,
+        statement::Statement,
+    },
+};
+
+use super::{Linter, Rule};
+
+struct Binding<'a> {
+    name: &'a str,
+    span: Span,
+    rule: Rule,
+    used: bool,
+}
+#[derive(Default)]
+struct Usage<'a> {
+    scopes: Vec<Vec<Binding<'a>>>,
+    unused: Vec<Binding<'a>>,
+}
+
+impl<'a> Usage<'a> {
+    fn enter(&mut self) {
+        self.scopes.push(vec![]);
+    }
+    fn exit(&mut self) {
+        let scope = self.scopes.pop().unwrap_or_default();
+        self.unused.extend(
+            scope
+                .into_iter()
+                .filter(|binding| !binding.used && !binding.name.starts_with('_')),
+        );
+    }
+    fn declare(&mut self, name: &'a str, span: Span, rule: Rule) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(Binding {
+                name,
+                span,
+                rule,
+                used: false,
+            });
+        }
+    }
+    fn read(&mut self, name: &str) {
+        if let Some(binding) = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .flat_map(|scope| scope.iter_mut().rev())
+            .find(|binding| binding.name == name)
+        {
+            binding.used = true;
+        }
+    }
+}
+
+pub fn children<'n, 'a>(node: &'n Node<'a>) -> Vec<&'n Node<'a>> {
+    match node {
+        Node::Integer(_) | Node::Float(_) | Node::String(_) | Node::Identifier(_) => vec![],
+        Node::Block(block) => block.value.iter().collect(),
+        Node::Array(array) => array.value.iter().collect(),
+        Node::Expression(expression) => match expression {
+            Expression::Infix { lhs, rhs, .. } => vec![lhs, rhs],
+            Expression::Prefix { value, .. } => vec![value],
+            Expression::Call {
+                callee, arguments, ..
+            } => std::iter::once(callee.as_ref())
+                .chain(arguments.value.iter())
+                .collect(),
+            Expression::Member { target, .. } => vec![target],
+            Expression::Lambda { body, .. } => vec![body],
+            Expression::Index { target, index } => vec![target, &index.value],
+            Expression::Match { value, arms, .. } => std::iter::once(value.as_ref())
+                .chain(arms.value.iter().map(|arm| arm.body.as_ref()))
+                .collect(),
+            Expression::Ternary {
+                test,
+                consequent,
+                alternative,
+            } => vec![test, consequent, alternative],
+            Expression::Range { start, end, .. } => vec![start, end],
+        },
+        Node::Statement(statement) => match statement {
+            Statement::While(while_statement) => {
+                vec![&while_statement.value.test, &while_statement.value.body]
+            }
+            Statement::For(for_statement) => {
+                vec![&for_statement.value.iterable, &for_statement.value.body]
+            }
+            Statement::If(if_statement) => {
+                let mut children = vec![
+                    if_statement.value.test.as_ref(),
+                    &if_statement.value.consequent,
+                ];
+                children.extend(if_statement.value.alternative.as_deref());
+                children
+            }
+            Statement::Let(let_statement) => {
+                let_statement.value.init.as_deref().into_iter().collect()
+            }
+            Statement::Return(return_statement) => return_statement
+                .value
+                .value
+                .as_deref()
+                .into_iter()
+                .collect(),
+        },
+    }
+}
+pub fn walk<'n, 'a>(node: &'n Node<'a>, visit: &mut impl FnMut(&'n Node<'a>)) {
+    visit(node);
+    for child in children(node) {
+        walk(child, visit);
+    }
+}
+fn is_snake_case(name: &str) -> bool {
+    name.chars()
+        .all(|char| char.is_lowercase() || char.is_ascii_digit() || char == '_')
+}
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (index, char) in name.chars().enumerate() {
+        if char.is_uppercase() {
+            if index > 0 && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(char.to_lowercase());
+        } else {
+            snake.push(char);
+        }
+    }
+    snake
+}
+
+impl<'a, 'p> Linter<'a, 'p> {
+    pub(super) fn check(&self) -> Vec<Diagnostic<'a>> {
+        let mut lints = vec![];
+        for function in &self.program.functions {
+            self.unused_bindings(function, &mut lints);
+            self.snake_case(function, &mut lints);
+            self.empty_block(&function.body, "function body", &mut lints);
+            walk(&function.body, &mut |node| {
+                self.redundant_parentheses(node, &mut lints);
+                self.self_comparison(node, &mut lints);
+                self.empty_blocks(node, &mut lints);
+            });
+        }
+        self.unused_functions(&mut lints);
+        lints
+    }
+    fn skip_whitespace(&self, mut position: usize) -> usize {
+        while self
+            .chars
+            .get(position)
+            .is_some_and(|char| char.is_whitespace())
+        {
+            position += 1;
+        }
+        position
+    }
+    fn let_name_span(&self, span: Span, name: &str) -> Span {
+        let mut position = self.skip_whitespace(span.start + "let".len());
+        let text = self.chars[position..].iter().take(4).collect::<String>();
+        if text.starts_with("mut") && text.chars().nth(3).is_some_and(char::is_whitespace) {
+            position = self.skip_whitespace(position + "mut".len());
+        }
+        Span::new(position, position + name.chars().count())
+    }
+    fn wrapped(&self, span: Span) -> Option<(usize, usize)> {
+        let mut open = span.start;
+        loop {
+            open = open.checked_sub(1)?;
+            match self.chars[open] {
+                '(' => break,
+                char if char.is_whitespace() => {}
+                _ => return None,
+            }
+        }
+        let close = self.skip_whitespace(span.end);
+        if self.chars.get(close) != Some(&')') {
+            return None;
+        }
+        let mut depth = 0usize;
+        for char in &self.chars[open + 1..close] {
+            match char {
+                '(' => depth += 1,
+                ')' => depth = depth.checked_sub(1)?,
+                _ => {}
+            }
+        }
+        Some((open, close))
+    }
+    fn unused_bindings(&self, function: &Function<'a>, lints: &mut Vec<Diagnostic<'a>>) {
+        let mut usage = Usage::default();
+        usage.enter();
+        for argument in &function.arguments {
+            let span = Span::new(
+                argument.span.start,
+                argument.span.start + argument.name.chars().count(),
+            );
+            usage.declare(argument.name, span, Rule::UnusedArguments);
+        }
+        self.usage(&function.body, &mut usage);
+        usage.exit();
+        for binding in usage.unused {
+            let name = binding.name.to_string();
+            let kind = match binding.rule {
+                Rule::UnusedArguments => ErrorKind::UnusedArgument { name },
+                _ => ErrorKind::UnusedVariable { name },
+            };
+            lints.push(self.lint(kind, binding.span).with_suggestion(
+                "prefix the name with `_`",
+                Span::new(binding.span.start, binding.span.start),
+                "_",
+            ));
+        }
+    }
+    fn usage(&self, node: &Node<'a>, usage: &mut Usage<'a>) {
+        match node {
+            Node::Identifier(identifier) => usage.read(identifier.value),
+            Node::Block(block) => {
+                usage.enter();
+                for statement in &block.value {
+                    self.usage(statement, usage);
+                }
+                usage.exit();
+            }
+            Node::Statement(Statement::Let(let_statement)) => {
+                if let Some(init) = &let_statement.value.init {
+                    self.usage(init, usage);
+                }
+                let span = self.let_name_span(let_statement.span, let_statement.value.name);
+                usage.declare(let_statement.value.name, span, Rule::UnusedVariables);
+            }
+            Node::Expression(Expression::Infix {
+                operator: Operator::Assignment,
+                lhs,
+                rhs,
+            }) => {
+                if !matches!(lhs.as_ref(), Node::Identifier(_)) {
+                    self.usage(lhs, usage);
+                }
+                self.usage(rhs, usage);
+            }
+            Node::Expression(Expression::Lambda { parameters, body }) => {
+                usage.enter();
+                for parameter in &parameters.value {
+                    usage.declare(
+                        parameter.name.value,
+                        parameter.name.span,
+                        Rule::UnusedArguments,
+                    );
+                }
+                self.usage(body, usage);
+                usage.exit();
+            }
+            Node::Statement(Statement::For(for_statement)) => {
+                let for_statement = &for_statement.value;
+                self.usage(&for_statement.iterable, usage);
+                usage.enter();
+                usage.declare(
+                    for_statement.binding.value,
+                    for_statement.binding.span,
+                    Rule::UnusedVariables,
+                );
+                self.usage(&for_statement.body, usage);
+                usage.exit();
+            }
+            Node::Expression(Expression::Match { value, arms, .. }) => {
+                self.usage(value, usage);
+                for arm in &arms.value {
+                    usage.enter();
+                    declare_pattern(&arm.pattern, usage);
+                    self.usage(&arm.body, usage);
+                    usage.exit();
+                }
+            }
+            _ => {
+                for child in children(node) {
+                    self.usage(child, usage);
+                }
+            }
+        }
+    }
+    fn snake_case(&self, function: &Function<'a>, lints: &mut Vec<Diagnostic<'a>>) {
+        if is_snake_case(function.name) {
+            return;
+        }
+        let replacement = to_snake_case(function.name);
+        let mut lint = self.lint(
+            ErrorKind::NonSnakeCaseName {
+                name: function.name.to_string(),
+                suggestion: replacement.clone(),
+            },
+            function.name_span,
+        );
+        if !function.public {
+            let mut spans = vec![function.name_span];
+            for other in &self.program.functions {
+                walk(&other.body, &mut |node| {
+                    let name = match node {
+                        Node::Identifier(name)
+                        | Node::Expression(Expression::Member { name, .. }) => name,
+                        _ => return,
+                    };
+                    if name.value == function.name {
+                        spans.push(name.span);
+                    }
+                });
+            }
+            for span in spans {
+                lint = lint.with_suggestion(
+                    format!("rename to `{}`", replacement),
+                    span,
+                    replacement.as_str(),
+                );
+            }
+        }
+        lints.push(lint);
+    }
+    fn unused_functions(&self, lints: &mut Vec<Diagnostic<'a>>) {
+        let mut referenced = HashSet::new();
+        for function in &self.program.functions {
+            walk(&function.body, &mut |node| match node {
+                Node::Identifier(name) | Node::Expression(Expression::Member { name, .. })
+                    if name.value != function.name =>
+                {
+                    referenced.insert(name.value);
+                }
+                _ => {}
+            });
+        }
+        for function in &self.program.functions {
+            if function.public
+                || function.name == "main"
+                || function.name.starts_with('_')
+                || referenced.contains(function.name)
+            {
+                continue;
+            }
+            lints.push(self.lint(
+                ErrorKind::UnusedFunction {
+                    name: function.name.to_string(),
+                },
+                function.name_span,
+            ));
+        }
+    }
+    fn redundant_parentheses(&self, node: &Node<'a>, lints: &mut Vec<Diagnostic<'a>>) {
+        let (inner, required) = match node {
+            Node::Statement(Statement::Let(let_statement)) => {
+                (let_statement.value.init.as_deref(), false)
+            }
+            Node::Statement(Statement::Return(return_statement)) => {
+                (return_statement.value.value.as_deref(), false)
+            }
+            Node::Expression(Expression::Infix {
+                operator: Operator::Assignment,
+                rhs,
+                ..
+            }) => (Some(rhs.as_ref()), false),
+            Node::Statement(Statement::If(if_statement)) => {
+                (Some(if_statement.value.test.as_ref()), true)
+            }
+            Node::Statement(Statement::While(while_statement)) => {
+                (Some(while_statement.value.test.as_ref()), true)
+            }
+            Node::Statement(Statement::For(for_statement)) => {
+                (Some(for_statement.value.iterable.as_ref()), false)
+            }
+            _ => return,
+        };
+        let Some(inner) = inner else {
+            return;
+        };
+        let Some((open, close)) = self.wrapped(inner.calculate_span()) else {
+            return;
+        };
+        if required && self.wrapped(Span::new(open, close + 1)).is_none() {
+            return;
+        }
+        lints.push(
+            self.lint(ErrorKind::RedundantParentheses, Span::new(open, close + 1))
+                .with_suggestion("remove the parentheses", Span::new(open, open + 1), "")
+                .with_suggestion("remove the parentheses", Span::new(close, close + 1), ""),
+        );
+    }
+    fn self_comparison(&self, node: &Node<'a>, lints: &mut Vec<Diagnostic<'a>>) {
+        let Node::Expression(
+            expression @ Expression::Infix {
+                operator:
+                    Operator::Equal
+                    | Operator::LessThen
+                    | Operator::GreaterThen
+                    | Operator::LessThenEqual
+                    | Operator::GreaterThenEqual,
+                lhs,
+                rhs,
+            },
+        ) = node
+        else {
+            return;
+        };
+        if let (Node::Identifier(lhs), Node::Identifier(rhs)) = (lhs.as_ref(), rhs.as_ref()) {
+            if lhs.value == rhs.value {
+                lints.push(self.lint(
+                    ErrorKind::SelfComparison {
+                        name: lhs.value.to_string(),
+                    },
+                    expression.calculate_span(),
+                ));
+            }
+        }
+    }
+    fn empty_blocks(&self, node: &Node<'a>, lints: &mut Vec<Diagnostic<'a>>) {
+        match node {
+            Node::Statement(Statement::While(while_statement)) => {
+                self.empty_block(&while_statement.value.body, "loop body", lints)
+            }
+            Node::Statement(Statement::For(for_statement)) => {
+                self.empty_block(&for_statement.value.body, "loop body", lints)
+            }
+            Node::Statement(Statement::If(if_statement)) => {
+                self.empty_block(&if_statement.value.consequent, "if body", lints);
+                if let Some(alternative) = &if_statement.value.alternative {
+                    if is_empty(alternative) {
+                        let end = alternative.calculate_span().end;
+                        let start = if_statement.value.consequent.calculate_span().end;
+                        lints.push(
+                            self.lint(
+                                ErrorKind::EmptyBlock {
+                                    block: "else branch".to_string(),
+                                },
+                                alternative.calculate_span(),
+                            )
+                            .with_suggestion(
+                                "remove the else branch",
+                                Span::new(start, end),
+                                "",
+                            ),
+                        );
+                    }
+                }
+            }
+            Node::Expression(Expression::Lambda { body, .. }) => {
+                self.empty_block(body, "lambda body", lints)
+            }
+            _ => {}
+        }
+    }
+    fn empty_block(&self, node: &Node<'a>, kind: &str, lints: &mut Vec<Diagnostic<'a>>) {
+        if is_empty(node) {
+            lints.push(self.lint(
+                ErrorKind::EmptyBlock {
+                    block: kind.to_string(),
+                },
+                node.calculate_span(),
+            ));
+        }
+    }
+}
+
+fn is_empty(node: &Node) -> bool {
+    matches!(node, Node::Block(block) if block.value.is_empty())
+}
+fn declare_pattern<'a>(pattern: &Pattern<'a>, usage: &mut Usage<'a>) {
+    match pattern {
+        Pattern::Binding(binding) => {
+            usage.declare(binding.value, binding.span, Rule::UnusedVariables)
+        }
+        Pattern::Variant {
+            fields: Some(fields),
+            ..
+        } => {
+            for field in &fields.value {
+                declare_pattern(field, usage);
+            }
+        }
+        _ => {}
+    }
+}
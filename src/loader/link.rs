@@ -0,0 +1,410 @@
+use std::collections::HashSet;
+
+use crate::{
+    common::{
+        error::{Error, ErrorKind, Result},
+        intern::Interner,
+        source::Source,
+        suggest::closest,
+    },
+    parser::ast::{
+        enumeration::Enum, expression::Expression, function::Function, node::Node, path_segments,
+        pattern::Pattern, spanned::Spanned, statement::Statement, types::TypeExpr, variant_path,
+        Program,
+    },
+};
+
+use super::Module;
+
+#[derive(Clone, Debug)]
+pub struct SymbolTable<'s, 'm> {
+    modules: &'m [Module<'s>],
+    names: &'s Interner,
+}
+
+impl<'s, 'm> SymbolTable<'s, 'm> {
+    pub fn new(modules: &'m [Module<'s>], names: &'s Interner) -> Self {
+        Self { modules, names }
+    }
+    pub fn function(&self, module: usize, name: &str) -> Option<&'m Function<'s>> {
+        self.modules[module]
+            .program
+            .functions
+            .iter()
+            .find(|function| function.name == name)
+    }
+    pub fn enumeration(&self, module: usize, name: &str) -> Option<&'m Enum<'s>> {
+        self.modules[module]
+            .program
+            .enums
+            .iter()
+            .find(|enumeration| enumeration.name == name)
+    }
+    pub fn visible_enums(&self, module: usize) -> Vec<(&'s str, &'m Enum<'s>)> {
+        let mut visible = vec![];
+        for &target in std::iter::once(&module).chain(&self.modules[module].imports) {
+            for enumeration in &self.modules[target].program.enums {
+                let name = format!("{}::{}", self.modules[target].name, enumeration.name);
+                visible.push((self.names.intern(name), enumeration));
+            }
+        }
+        visible
+    }
+    pub fn qualified_name(&self, module: usize, name: &'s str) -> &'s str {
+        if module == 0 {
+            name
+        } else {
+            self.names
+                .intern(format!("{}::{}", self.modules[module].name, name))
+        }
+    }
+    pub fn resolve(&self, module: usize, path: &Spanned<&'s str>) -> Result<'s, Option<&'s str>> {
+        let source = self.modules[module].source;
+        let segments = path_segments(path.value).collect::<Vec<_>>();
+        let (target, name) = match segments.as_slice() {
+            [name] => {
+                return Ok(self
+                    .function(module, name)
+                    .map(|function| self.qualified_name(module, function.name)))
+            }
+            [module_name, name] => match self
+                .modules
+                .iter()
+                .position(|candidate| candidate.name == *module_name)
+            {
+                Some(target) => (target, *name),
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        if target != module && !self.modules[module].imports.contains(&target) {
+            return Err(error(ErrorKind::ModuleNotImported, path, source));
+        }
+        let Some(function) = self.function(target, name) else {
+            let names = self.modules[target]
+                .program
+                .functions
+                .iter()
+                .map(|function| function.name);
+            let mut error = Error::new(ErrorKind::UndefinedFunction, path.span, source);
+            if let Some(similar) = closest(name, names) {
+                let replacement = format!("{}::{}", self.modules[target].name, similar);
+                error = error.with_suggestion(
+                    format!("did you mean `{}`", replacement),
+                    path.span,
+                    replacement,
+                );
+            }
+            return Err(Box::new(error));
+        };
+        if target != module && !function.public {
+            return Err(error(ErrorKind::PrivateFunction, path, source));
+        }
+        Ok(Some(self.qualified_name(target, function.name)))
+    }
+    pub fn resolve_enum(
+        &self,
+        module: usize,
+        path: &Spanned<&'s str>,
+    ) -> Result<'s, Option<&'s str>> {
+        let source = self.modules[module].source;
+        let segments = path_segments(path.value).collect::<Vec<_>>();
+        let (target, name) = match segments.as_slice() {
+            [name] => {
+                return Ok(self
+                    .enumeration(module, name)
+                    .map(|enumeration| self.qualified_name(module, enumeration.name)))
+            }
+            [module_name, name] => match self
+                .modules
+                .iter()
+                .position(|candidate| candidate.name == *module_name)
+            {
+                Some(target) => (target, *name),
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        if target != module && !self.modules[module].imports.contains(&target) {
+            return Err(error(ErrorKind::ModuleNotImported, path, source));
+        }
+        match self.enumeration(target, name) {
+            Some(enumeration) => Ok(Some(self.qualified_name(target, enumeration.name))),
+            None => Err(error(ErrorKind::UnknownEnum, path, source)),
+        }
+    }
+    pub fn resolve_variant(
+        &self,
+        module: usize,
+        path: &Spanned<&'s str>,
+    ) -> Result<'s, Option<&'s str>> {
+        let (enumeration, variant) = variant_path(path.value);
+        if enumeration.is_empty() {
+            return Ok(None);
+        }
+        let enum_path = Spanned::new(enumeration, path.span);
+        Ok(self
+            .resolve_enum(module, &enum_path)?
+            .map(|enumeration| self.names.intern(format!("{}::{}", enumeration, variant))))
+    }
+    pub fn link(&self) -> Result<'s, Program<'s>> {
+        let root = &self.modules[0].program;
+        let mut functions = vec![];
+        let mut enums = vec![];
+        for (index, module) in self.modules.iter().enumerate() {
+            for function in &module.program.functions {
+                let mut linked = function.clone();
+                linked.name = self.qualified_name(index, function.name);
+                let mut linker = Linker {
+                    table: self,
+                    module: index,
+                    generics: function.generics.iter().map(|name| name.value).collect(),
+                    scopes: vec![function
+                        .arguments
+                        .iter()
+                        .map(|argument| argument.name)
+                        .collect()],
+                };
+                for argument in &mut linked.arguments {
+                    linker.rewrite_type(&mut argument.argument_type)?;
+                }
+                if let Some(return_type) = &mut linked.return_type {
+                    linker.rewrite_type(return_type)?;
+                }
+                linker.rewrite(&mut linked.body)?;
+                functions.push(linked);
+            }
+            for enumeration in &module.program.enums {
+                let mut linked = enumeration.clone();
+                linked.name = self.qualified_name(index, enumeration.name);
+                let mut linker = Linker {
+                    table: self,
+                    module: index,
+                    generics: HashSet::new(),
+                    scopes: vec![],
+                };
+                for field in linked
+                    .variants
+                    .iter_mut()
+                    .flat_map(|variant| variant.fields.iter_mut().flatten())
+                {
+                    linker.rewrite_type(field)?;
+                }
+                enums.push(linked);
+            }
+        }
+        Ok(Program {
+            path: root.path,
+            module: root.module.clone(),
+            imports: root.imports.clone(),
+            functions,
+            enums,
+        })
+    }
+}
+
+fn error<'s>(kind: ErrorKind, path: &Spanned<&'s str>, source: Source<'s>) -> Box<Error<'s>> {
+    Box::new(Error::new(kind, path.span, source))
+}
+
+struct Linker<'s, 'm, 't> {
+    table: &'t SymbolTable<'s, 'm>,
+    module: usize,
+    generics: HashSet<&'s str>,
+    scopes: Vec<HashSet<&'s str>>,
+}
+
+impl<'s, 'm, 't> Linker<'s, 'm, 't> {
+    fn bind(&mut self, name: &'s str) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name);
+        }
+    }
+    fn bind_pattern(&mut self, pattern: &mut Pattern<'s>) -> Result<'s, ()> {
+        match pattern {
+            Pattern::Binding(name) => self.bind(name.value),
+            Pattern::Variant { path, fields } => {
+                if let Some(linked) = self.table.resolve_variant(self.module, path)? {
+                    path.value = linked;
+                }
+                if let Some(fields) = fields {
+                    for field in &mut fields.value {
+                        self.bind_pattern(field)?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+    fn rewrite_type(&mut self, type_expr: &mut TypeExpr<'s>) -> Result<'s, ()> {
+        match type_expr {
+            TypeExpr::Path { path, generics } => {
+                if !self.generics.contains(path.value) {
+                    if let Some(linked) = self.table.resolve_enum(self.module, path)? {
+                        path.value = linked;
+                    }
+                }
+                match generics {
+                    Some(generics) => generics
+                        .value
+                        .iter_mut()
+                        .try_for_each(|generic| self.rewrite_type(generic)),
+                    None => Ok(()),
+                }
+            }
+            TypeExpr::Unit(_) => Ok(()),
+            TypeExpr::Tuple(elements) => elements
+                .value
+                .iter_mut()
+                .try_for_each(|element| self.rewrite_type(element)),
+            TypeExpr::Array { element, .. } => self.rewrite_type(element),
+            TypeExpr::Function {
+                arguments,
+                return_type,
+                ..
+            } => {
+                for argument in &mut arguments.value {
+                    self.rewrite_type(argument)?;
+                }
+                match return_type {
+                    Some(return_type) => self.rewrite_type(return_type),
+                    None => Ok(()),
+                }
+            }
+            TypeExpr::Reference { inner, .. } => self.rewrite_type(inner),
+        }
+    }
+    fn scoped<F>(&mut self, scope: HashSet<&'s str>, function: F) -> Result<'s, ()>
+    where
+        F: FnOnce(&mut Self) -> Result<'s, ()>,
+    {
+        self.scopes.push(scope);
+        let result = function(self);
+        self.scopes.pop();
+        result
+    }
+    fn rewrite(&mut self, node: &mut Node<'s>) -> Result<'s, ()> {
+        match node {
+            Node::Integer(_) | Node::Float(_) | Node::String(_) => Ok(()),
+            Node::Identifier(name) => {
+                if self.scopes.iter().any(|scope| scope.contains(name.value)) {
+                    return Ok(());
+                }
+                if let Some(linked) = self.table.resolve_variant(self.module, name)? {
+                    name.value = linked;
+                } else if let Some(linked) = self.table.resolve(self.module, name)? {
+                    name.value = linked;
+                }
+                Ok(())
+            }
+            Node::Block(block) => self.scoped(HashSet::new(), |linker| {
+                block
+                    .value
+                    .iter_mut()
+                    .try_for_each(|statement| linker.rewrite(statement))
+            }),
+            Node::Array(array) => array
+                .value
+                .iter_mut()
+                .try_for_each(|element| self.rewrite(element)),
+            Node::Expression(expression) => match expression {
+                Expression::Infix { lhs, rhs, .. } => {
+                    self.rewrite(lhs)?;
+                    self.rewrite(rhs)
+                }
+                Expression::Prefix { value, .. } => self.rewrite(value),
+                Expression::Call {
+                    callee,
+                    generics,
+                    arguments,
+                } => {
+                    self.rewrite(callee)?;
+                    if let Some(generics) = generics {
+                        for generic in &mut generics.value {
+                            self.rewrite_type(generic)?;
+                        }
+                    }
+                    arguments
+                        .value
+                        .iter_mut()
+                        .try_for_each(|argument| self.rewrite(argument))
+                }
+                Expression::Member { target, .. } => self.rewrite(target),
+                Expression::Lambda { parameters, body } => {
+                    for parameter in &mut parameters.value {
+                        if let Some(parameter_type) = &mut parameter.parameter_type {
+                            self.rewrite_type(parameter_type)?;
+                        }
+                    }
+                    let scope = parameters
+                        .value
+                        .iter()
+                        .map(|parameter| parameter.name.value)
+                        .collect();
+                    self.scoped(scope, |linker| linker.rewrite(body))
+                }
+                Expression::Index { target, index } => {
+                    self.rewrite(target)?;
+                    self.rewrite(&mut index.value)
+                }
+                Expression::Match { value, arms, .. } => {
+                    self.rewrite(value)?;
+                    arms.value.iter_mut().try_for_each(|arm| {
+                        self.scoped(HashSet::new(), |linker| {
+                            linker.bind_pattern(&mut arm.pattern)?;
+                            linker.rewrite(&mut arm.body)
+                        })
+                    })
+                }
+                Expression::Ternary {
+                    test,
+                    consequent,
+                    alternative,
+                } => {
+                    self.rewrite(test)?;
+                    self.rewrite(consequent)?;
+                    self.rewrite(alternative)
+                }
+                Expression::Range { start, end, .. } => {
+                    self.rewrite(start)?;
+                    self.rewrite(end)
+                }
+            },
+            Node::Statement(statement) => match statement {
+                Statement::While(while_statement) => {
+                    self.rewrite(&mut while_statement.value.test)?;
+                    self.rewrite(&mut while_statement.value.body)
+                }
+                Statement::For(for_statement) => {
+                    let for_statement = &mut for_statement.value;
+                    self.rewrite(&mut for_statement.iterable)?;
+                    let scope = HashSet::from([for_statement.binding.value]);
+                    self.scoped(scope, |linker| linker.rewrite(&mut for_statement.body))
+                }
+                Statement::If(if_statement) => {
+                    self.rewrite(&mut if_statement.value.test)?;
+                    self.rewrite(&mut if_statement.value.consequent)?;
+                    match &mut if_statement.value.alternative {
+                        Some(alternative) => self.rewrite(alternative),
+                        None => Ok(()),
+                    }
+                }
+                Statement::Let(let_statement) => {
+                    if let Some(value_type) = &mut let_statement.value.value_type {
+                        self.rewrite_type(value_type)?;
+                    }
+                    if let Some(init) = &mut let_statement.value.init {
+                        self.rewrite(init)?;
+                    }
+                    self.bind(let_statement.value.name);
+                    Ok(())
+                }
+                Statement::Return(return_statement) => match &mut return_statement.value.value {
+                    Some(value) => self.rewrite(value),
+                    None => Ok(()),
+                },
+            },
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::{
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    common::{
+        error::{Error, ErrorKind, Result},
+        intern::Interner,
+        source::Source,
+        span::Span,
+    },
+    lexer::{token::TokenKind, Lexer},
+    parser::{ast::Program, cursor::Cursor, Parser},
+    syntax::{self, lower::lower},
+};
+
+pub mod link;
+
+#[derive(Clone, Debug)]
+struct File {
+    path: PathBuf,
+    name: String,
+    code: String,
+}
+#[derive(Debug, Default)]
+pub struct Loader {
+    files: Vec<File>,
+    names: Interner,
+    lossless: bool,
+}
+#[derive(Clone, Debug)]
+pub struct Module<'s> {
+    pub name: &'s str,
+    pub source: Source<'s>,
+    pub program: Program<'s>,
+    pub imports: Vec<usize>,
+}
+
+impl Loader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn lossless(mut self) -> Self {
+        self.lossless = true;
+        self
+    }
+    pub fn load(&mut self, path: &Path) -> io::Result<()> {
+        let code = fs::read_to_string(path)?;
+        self.add(path.to_path_buf(), code)
+    }
+    pub fn add(&mut self, path: PathBuf, code: String) -> io::Result<()> {
+        let path = normalize(&path);
+        if self.position(&path).is_some() {
+            return Ok(());
+        }
+        let imports = imported_paths(&path, &code);
+        self.files.push(File {
+            name: path.display().to_string(),
+            path,
+            code,
+        });
+        for import in imports {
+            if self.position(&import).is_some() {
+                continue;
+            }
+            match fs::read_to_string(&import) {
+                Ok(code) => self.add(import, code)?,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    return Err(io::Error::new(
+                        err.kind(),
+                        format!("{}: {}", import.display(), err),
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+    pub fn names(&self) -> &Interner {
+        &self.names
+    }
+    pub fn sources(&self) -> impl Iterator<Item = Source<'_>> {
+        self.files.iter().map(|file| Source {
+            code: &file.code,
+            path: &file.name,
+        })
+    }
+    pub fn parse(&self) -> Result<'_, Vec<Module<'_>>> {
+        let mut modules = vec![];
+        for (file, source) in self.files.iter().zip(self.sources()) {
+            let program = if self.lossless {
+                let tree = syntax::parse(source, &self.names)?;
+                lower(source, &self.names, &tree).ok_or_else(|| {
+                    Box::new(Error::new(
+                        ErrorKind::MalformedSyntaxTree,
+                        tree.span(),
+                        source,
+                    ))
+                })?
+            } else {
+                parse(source, &self.names)?
+            };
+            let name = match &program.module {
+                Some(module) => module.value,
+                None => file
+                    .path
+                    .file_stem()
+                    .and_then(OsStr::to_str)
+                    .unwrap_or(&file.name),
+            };
+            modules.push(Module {
+                name,
+                source,
+                program,
+                imports: vec![],
+            });
+        }
+        for (index, file) in self.files.iter().enumerate() {
+            let module = &modules[index];
+            let imports = module
+                .program
+                .imports
+                .iter()
+                .map(|import| {
+                    self.position(&resolve(&file.path, import.path.value))
+                        .ok_or_else(|| {
+                            Box::new(Error::new(
+                                ErrorKind::ModuleNotFound,
+                                import.path.span,
+                                module.source,
+                            ))
+                        })
+                })
+                .collect::<Result<'_, Vec<_>>>()?;
+            modules[index].imports = imports;
+        }
+        for (index, module) in modules.iter().enumerate() {
+            if let Some(first) = modules[..index]
+                .iter()
+                .find(|other| other.name == module.name)
+            {
+                let (span, source) = match &module.program.module {
+                    Some(declaration) => (declaration.span, module.source),
+                    None => importer(&modules, index).unwrap_or((Span::new(0, 0), module.source)),
+                };
+                return Err(Box::new(Error::new(
+                    ErrorKind::DuplicateModule {
+                        name: module.name.to_string(),
+                        first: first.source.path.to_string(),
+                    },
+                    span,
+                    source,
+                )));
+            }
+        }
+        if !modules.is_empty() {
+            detect_cycles(&modules, 0, &mut vec![], &mut vec![false; modules.len()])?;
+        }
+        Ok(modules)
+    }
+    fn position(&self, path: &Path) -> Option<usize> {
+        self.files.iter().position(|file| file.path == path)
+    }
+}
+
+fn parse<'a>(source: Source<'a>, names: &'a Interner) -> Result<'a, Program<'a>> {
+    let lexer = Lexer::new(source);
+    Parser::new(source, Cursor::new(lexer.peekable(), source), names).parse_program()
+}
+fn normalize(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+fn resolve(from: &Path, import: &str) -> PathBuf {
+    normalize(&from.parent().unwrap_or_else(|| Path::new("")).join(import))
+}
+fn imported_paths(path: &Path, code: &str) -> Vec<PathBuf> {
+    let tokens = Lexer::new(Source { code, path: "" })
+        .map_while(|token| token.ok())
+        .collect::<Vec<_>>();
+    tokens
+        .windows(2)
+        .filter(|pair| pair[0].kind == TokenKind::Import && pair[1].kind == TokenKind::String)
+        .map(|pair| {
+            let literal = pair[1].chunk.data;
+            resolve(path, &literal[1..literal.len() - 1])
+        })
+        .collect()
+}
+fn importer<'s>(modules: &[Module<'s>], index: usize) -> Option<(Span, Source<'s>)> {
+    modules.iter().find_map(|module| {
+        module
+            .program
+            .imports
+            .iter()
+            .zip(&module.imports)
+            .find(|(_, target)| **target == index)
+            .map(|(import, _)| (import.path.span, module.source))
+    })
+}
+fn detect_cycles<'s>(
+    modules: &[Module<'s>],
+    index: usize,
+    stack: &mut Vec<usize>,
+    visited: &mut [bool],
+) -> Result<'s, ()> {
+    stack.push(index);
+    let module = &modules[index];
+    for (import, target) in module.program.imports.iter().zip(&module.imports) {
+        if let Some(start) = stack.iter().position(|entry| entry == target) {
+            let chain = stack[start..]
+                .iter()
+                .chain(Some(target))
+                .map(|entry| modules[*entry].source.path.to_string())
+                .collect();
+            return Err(Box::new(Error::new(
+                ErrorKind::ImportCycle { chain },
+                import.path.span,
+                module.source,
+            )));
+        }
+        if !visited[*target] {
+            detect_cycles(modules, *target, stack, visited)?;
+        }
+    }
+    stack.pop();
+    visited[index] = true;
+    Ok(())
+}
@@ -1,44 +1,409 @@
-use lexer::Lexer;
-use parser::{cursor::Cursor, Parser};
+use std::{env, fs, path::PathBuf, process};
 
-use crate::{common::source::Source, lexer::cursor::slice::utf8_slice};
+use lang::{
+    common::{
+        diagnostic::{self, Diagnostic},
+        error::LINTS,
+        explain::{self, EXPLANATIONS},
+        json::{Json, ToJson},
+        span::Span,
+    },
+    linter::{apply_fixes, config::Config},
+    optimizer::OptimizationLevel,
+    syntax::incremental::TextEdit,
+    Compiler, Session,
+};
 
-pub mod common;
-pub mod lexer;
-pub mod parser;
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+const VALUE_OPTIONS: [&str; 5] = ["-o", "--format", "--edit", "-W", "-D"];
+
+fn option<'s>(arguments: &'s [String], name: &str) -> Option<&'s String> {
+    arguments
+        .iter()
+        .position(|argument| argument == name)
+        .and_then(|index| arguments.get(index + 1))
+}
+fn input_path(arguments: &[String]) -> Option<&String> {
+    arguments.iter().enumerate().find_map(|(index, argument)| {
+        let is_value = index > 0 && VALUE_OPTIONS.contains(&arguments[index - 1].as_str());
+        (!argument.starts_with('-') && !is_value).then_some(argument)
+    })
+}
 
 fn main() {
-    let source = Source {
-        code: r#"
-            function main(args: Arguments) {
-                let mut a = 0;
-                while(a < 10) {
-                    if(a == 5) {
-                        print(a);
+    let arguments = env::args().skip(1).collect::<Vec<_>>();
+    let format = match option(&arguments, "--format").map(String::as_str) {
+        None | Some("text") => Format::Text,
+        Some("json") => Format::Json,
+        Some(other) => {
+            println!("unknown format `{}`, expected `text` or `json`", other);
+            process::exit(1);
+        }
+    };
+    if arguments.first().is_some_and(|command| command == "lint") {
+        return lint(&arguments[1..], format);
+    }
+    if arguments
+        .first()
+        .is_some_and(|command| command == "explain")
+    {
+        return explain(input_path(&arguments[1..]), format);
+    }
+    let Some(edits) = arguments
+        .windows(2)
+        .filter(|pair| pair[0] == "--edit")
+        .map(|pair| parse_edit(&pair[1]))
+        .collect::<Option<Vec<_>>>()
+    else {
+        println!("expected `--edit <start>..<end>=<replacement>`");
+        process::exit(1);
+    };
+    let mut compiler = if arguments.iter().any(|argument| argument == "--lossless") {
+        Compiler::new().lossless()
+    } else {
+        Compiler::new()
+    };
+    if !configure(&mut compiler, &arguments) {
+        process::exit(1);
+    }
+    let path = input_path(&arguments);
+    let loaded = match path {
+        Some(path) => compiler.load(&PathBuf::from(path)),
+        None => compiler.add(PathBuf::from("main.lang"), SAMPLE.to_string()),
+    };
+    if let Err(err) = loaded {
+        println!("{}: {}", path.map_or("main.lang", String::as_str), err);
+        process::exit(1);
+    }
+    let mut session = compiler.session();
+    compile(&mut session, &arguments, &edits, format);
+    flush(&mut session, format);
+    if session.has_errors() {
+        process::exit(1);
+    }
+}
+
+fn compile(session: &mut Session, arguments: &[String], edits: &[TextEdit], format: Format) {
+    let flag = |name: &str| arguments.iter().any(|argument| argument == name);
+    if flag("--emit-tokens") {
+        return emit_tokens(session, format);
+    }
+    if flag("--emit-cst") {
+        return emit_cst(session, edits, format);
+    }
+    let Some(modules) = session.parse() else {
+        return;
+    };
+    let ast = session.check(&modules);
+    flush(session, format);
+    let Some(ast) = ast else {
+        return;
+    };
+    if flag("--emit-ast") {
+        match format {
+            Format::Text => println!("{:#?}", ast),
+            Format::Json => return println!("{}", ast.to_json().pretty()),
+        }
+    }
+    let Some(program) = session.lower(&ast) else {
+        return;
+    };
+    let output = option(arguments, "-o");
+    if !flag("--emit-ir") && !flag("--emit-asm") && output.is_none() {
+        session.run(&program);
+        return;
+    }
+    let Some(module) = session.build(&program) else {
+        return;
+    };
+    let level = arguments
+        .iter()
+        .rev()
+        .find_map(|argument| OptimizationLevel::from_flag(argument))
+        .unwrap_or(OptimizationLevel::None);
+    let print_passes = flag("--print-passes");
+    if print_passes {
+        print!("; before optimization\n{}", module);
+    }
+    let Some(module) = session.optimize(module, level, |pass, module| {
+        if print_passes {
+            print!("\n; after {}\n{}", pass, module);
+        }
+    }) else {
+        return;
+    };
+    if flag("--emit-asm") {
+        if let Some(assembly) = session.emit_asm(&module) {
+            print!("{}", assembly);
+        }
+    } else if let Some(output) = output {
+        session.link(&module, &PathBuf::from(output));
+    } else if !print_passes {
+        print!("{}", module);
+    }
+}
+
+const SAMPLE: &str = r#"
+enum Shape {
+    Circle(float),
+    Rectangle(float, float),
+    Empty
+}
+function main() {
+    let mut a = 0;
+    while(a < 10) {
+        if(a == 5) {
+            println(a);
+        }
+        println(sum(2, 3));
+        a = a + 1;
+    }
+    let shape = Shape::Rectangle(2.0, 3.0);
+    match(shape) {
+        Shape::Circle(radius) => println(radius),
+        Shape::Rectangle(width, height) => println(width * height),
+        Shape::Empty => println(0)
+    }
+    let mut squares: [int; 3] = [0, 0, 0];
+    let mut i = 0;
+    while(i < len(squares)) {
+        squares[i] = i * i;
+        i = i + 1;
+    }
+    println(squares);
+    println(max(2, 3), max::<float>(2.5, 1.5));
+    let offset = 10;
+    let add = |a, b| a + b + offset;
+    let adder = |a: int| |b: int| a + b;
+    println(add(1, 2), adder(3)(4), squares.len(), apply(|x: int| x * 2, 21));
+    println(format("sqrt({}) = {}", 16.0, sqrt(16.0)), upper("done"));
+}
+function max<T>(a: T, b: T) -> T {
+    if(a > b) {
+        return a;
+    }
+    return b;
+}
+function apply(f: fn(int) -> int, value: int) -> int {
+    return f(value);
+}
+function sum(a: int, b: int) -> int {
+    return a + b;
+}
+"#;
+
+fn emit_tokens(session: &mut Session, format: Format) {
+    let Some(files) = session.tokenize() else {
+        return;
+    };
+    match format {
+        Format::Text => {
+            for token in files.iter().flat_map(|(_, tokens)| tokens) {
+                println!(
+                    "{:?} {:?} {}.{}",
+                    token.kind, token.chunk.data, token.chunk.span.start, token.chunk.span.end
+                );
+            }
+        }
+        Format::Json => {
+            let files = files
+                .iter()
+                .map(|(source, tokens)| {
+                    Json::object([
+                        ("path", source.path.to_json()),
+                        ("tokens", tokens.to_json()),
+                    ])
+                })
+                .collect();
+            println!("{}", Json::Array(files).pretty());
+        }
+    }
+}
+
+fn parse_edit(edit: &str) -> Option<TextEdit> {
+    let (range, replacement) = edit.split_once('=')?;
+    let (start, end) = range.split_once("..")?;
+    Some(TextEdit {
+        span: Span::new(start.parse().ok()?, end.parse().ok()?),
+        replacement: replacement.to_string(),
+    })
+}
+
+fn emit_cst(session: &mut Session, edits: &[TextEdit], format: Format) {
+    let files = session.syntax(edits);
+    let errors = session.unreported().to_vec();
+    let error = |path: &str| {
+        errors
+            .iter()
+            .find(|diagnostic| diagnostic.source.path == path)
+    };
+    match format {
+        Format::Text => {
+            for file in &files {
+                if let Some(error) = error(file.source.path) {
+                    print(error, format);
+                }
+                for reparse in &file.reparses {
+                    println!("; reparse: {:?}", reparse);
+                }
+                print!("{:?}", file.tree);
+            }
+        }
+        Format::Json => {
+            let files = files
+                .iter()
+                .map(|file| {
+                    let reparses = file
+                        .reparses
+                        .iter()
+                        .map(|reparse| format!("{:?}", reparse))
+                        .collect::<Vec<_>>();
+                    let mut json = Json::object([
+                        ("path", file.source.path.to_json()),
+                        ("reparses", reparses.to_json()),
+                        ("tree", file.tree.to_json()),
+                    ]);
+                    if let (Json::Object(fields), Some(error)) =
+                        (&mut json, error(file.source.path))
+                    {
+                        fields.push(("error".to_string(), error.to_json()));
                     }
-                    print(sum(2, 3));
-                    a = a + 1;
+                    json
+                })
+                .collect();
+            println!("{}", Json::Array(files).pretty());
+        }
+    }
+}
+
+fn lint(arguments: &[String], format: Format) {
+    let Some(path) = input_path(arguments) else {
+        return println!("usage: lang lint <path> [--fix]");
+    };
+    let fix = arguments.iter().any(|argument| argument == "--fix");
+    let config = match Config::find(&PathBuf::from(path)) {
+        Ok(config) => config,
+        Err(err) => {
+            println!("{}", err);
+            process::exit(1);
+        }
+    };
+    let mut compiler = Compiler::new();
+    if let Err(err) = compiler.load(&PathBuf::from(path)) {
+        println!("{}: {}", path, err);
+        process::exit(1);
+    }
+    let mut session = compiler.session();
+    let Some(modules) = session.parse() else {
+        flush(&mut session, format);
+        process::exit(1);
+    };
+    session.lint(&modules, &config);
+    let reported = session.unreported().to_vec();
+    let mut denied = false;
+    for module in &modules {
+        let lints = reported
+            .iter()
+            .filter(|lint| lint.source.path == module.source.path)
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut fixed = vec![];
+        if fix {
+            let code;
+            (code, fixed) = apply_fixes(module.source.code, &lints);
+            if !fixed.is_empty() {
+                if let Err(err) = fs::write(module.source.path, code) {
+                    println!("{}: {}", module.source.path, err);
+                    process::exit(1);
                 }
+                println!("fixed {} problem(s) in {}", fixed.len(), module.source.path);
+            }
+        }
+        for (index, lint) in lints.iter().enumerate() {
+            if fixed.contains(&index) {
+                continue;
             }
-            function sum(a: int, b: int) -> int {
-                return a + b;
+            denied |= lint.severity == diagnostic::Severity::Error;
+            print(lint, format);
+        }
+    }
+    if denied {
+        process::exit(1);
+    }
+}
+
+fn explain(code: Option<&String>, format: Format) {
+    let explanations = match code {
+        Some(code) => match explain::explain(code) {
+            Some(explanation) => vec![explanation],
+            None => {
+                println!("unknown error code `{}`", code);
+                process::exit(1);
             }
-        "#,
-        path: "main.lang",
+        },
+        None => EXPLANATIONS
+            .iter()
+            .map(|(code, name, text)| (*code, *name, *text))
+            .collect(),
     };
-    let lexer = Lexer::new(source);
-    let mut parser = Parser::new(source, Cursor::new(lexer.peekable(), source));
-    match parser.parse_program() {
-        Ok(ast) => println!("{:#?}", ast),
-        Err(err) => {
-            let err = *err;
-            println!(
-                "{:?} at {}.{}\n{}",
-                err.kind,
-                err.span.start,
-                err.span.end,
-                utf8_slice(source.code, err.span.start - 5, err.span.end + 5)
-            )
+    for (known, name, text) in explanations {
+        match format {
+            Format::Text if code.is_some() => println!("{} {}\n\n{}", known, name, text),
+            Format::Text => println!("{} {}", known, name),
+            Format::Json => println!(
+                "{}",
+                Json::object([
+                    ("code", known.to_json()),
+                    ("name", name.to_json()),
+                    ("explanation", text.to_json()),
+                ])
+            ),
+        }
+    }
+}
+fn print(diagnostic: &Diagnostic, format: Format) {
+    match format {
+        Format::Text => println!("{}", diagnostic),
+        Format::Json => println!("{}", diagnostic.to_json()),
+    }
+}
+fn flush(session: &mut Session, format: Format) {
+    for diagnostic in session.unreported() {
+        print(diagnostic, format);
+    }
+}
+fn configure(compiler: &mut Compiler, arguments: &[String]) -> bool {
+    for pair in arguments.windows(2) {
+        let severity = match pair[0].as_str() {
+            "-W" => diagnostic::Severity::Warning,
+            "-D" => diagnostic::Severity::Error,
+            _ => continue,
+        };
+        let name = pair[1].as_str();
+        let kind = EXPLANATIONS
+            .iter()
+            .find(|(code, kind, _)| code.eq_ignore_ascii_case(name) || *kind == name)
+            .map(|(_, kind, _)| *kind);
+        match kind {
+            _ if name == "warnings" => {}
+            Some(kind) if LINTS.contains(&kind) => {}
+            Some(kind) => {
+                println!(
+                    "`{}` is an error and cannot be changed by `{}`",
+                    kind, pair[0]
+                );
+                return false;
+            }
+            None => {
+                println!("unknown diagnostic `{}` for `{}`", name, pair[0]);
+                return false;
+            }
         }
+        compiler.set(name, severity);
     }
+    true
 }
@@ -0,0 +1,584 @@
+use crate::{
+    common::{
+        error::{Error, ErrorKind, Result},
+        span::Span,
+    },
+    parser::ast::{
+        calculate_span::CalculateSpan,
+        expression::{Expression, Operator},
+        function::Function,
+        node::Node,
+        pattern::Pattern,
+        spanned::Spanned,
+        statement::Statement,
+        types::TypeExpr,
+        variant_path,
+    },
+    stdlib,
+};
+
+use super::{Monomorphizer, Scope};
+
+fn named(name: &str, span: Span) -> TypeExpr<'_> {
+    TypeExpr::Path {
+        path: Spanned::new(name, span),
+        generics: None,
+    }
+}
+
+impl<'a, 'p> Monomorphizer<'a, 'p> {
+    pub(super) fn infer(&mut self, node: &Node<'a>) -> Option<TypeExpr<'a>> {
+        let span = node.calculate_span();
+        match node {
+            Node::Integer(_) => Some(named("int", span)),
+            Node::Float(_) => Some(named("float", span)),
+            Node::String(_) => Some(named("string", span)),
+            Node::Identifier(name)
+                if name.value.contains("::") && !self.functions.contains_key(name.value) =>
+            {
+                self.enum_type(name)
+            }
+            Node::Identifier(name) => match self
+                .scopes
+                .iter()
+                .rev()
+                .find_map(|scope| scope.get(name.value))
+            {
+                Some(value_type) => value_type.clone(),
+                None => {
+                    let function = self.functions.get(name.value)?;
+                    if !function.generics.is_empty() {
+                        return None;
+                    }
+                    Some(TypeExpr::Function {
+                        keyword: span,
+                        arguments: Spanned::new(
+                            function
+                                .arguments
+                                .iter()
+                                .map(|argument| argument.argument_type.clone())
+                                .collect(),
+                            span,
+                        ),
+                        return_type: function.return_type.clone().map(Box::new),
+                    })
+                }
+            },
+            Node::Array(array) => Some(TypeExpr::Array {
+                element: Box::new(self.infer(array.value.first()?)?),
+                length: Some(Spanned::new(
+                    self.names.intern(array.value.len().to_string()),
+                    span,
+                )),
+                span,
+            }),
+            Node::Expression(expression) => match expression {
+                Expression::Infix {
+                    operator: Operator::Assignment,
+                    ..
+                } => Some(TypeExpr::Unit(span)),
+                Expression::Infix {
+                    operator:
+                        Operator::Equal
+                        | Operator::LessThen
+                        | Operator::GreaterThen
+                        | Operator::LessThenEqual
+                        | Operator::GreaterThenEqual,
+                    ..
+                } => Some(named("bool", span)),
+                Expression::Infix { lhs, rhs, .. } => self.infer(lhs).or_else(|| self.infer(rhs)),
+                Expression::Prefix { value, .. } => self.infer(value),
+                Expression::Call { callee, .. } => match callee.as_ref() {
+                    Node::Identifier(name)
+                        if name.value.contains("::")
+                            && !self.functions.contains_key(name.value) =>
+                    {
+                        self.enum_type(name)
+                    }
+                    Node::Identifier(name) if self.is_local(name.value) => {
+                        match self.infer(callee)? {
+                            TypeExpr::Function { return_type, .. } => {
+                                Some(return_type.map_or(TypeExpr::Unit(span), |r| *r))
+                            }
+                            _ => None,
+                        }
+                    }
+                    Node::Identifier(name) | Node::Expression(Expression::Member { name, .. }) => {
+                        match self.signatures.get(name.value) {
+                            Some(return_type) => {
+                                Some(return_type.clone().unwrap_or(TypeExpr::Unit(span)))
+                            }
+                            None => stdlib::lookup(name.value)
+                                .and_then(|builtin| builtin.signature.return_type.name())
+                                .map(|name| named(name, span)),
+                        }
+                    }
+                    _ => match self.infer(callee)? {
+                        TypeExpr::Function { return_type, .. } => {
+                            Some(return_type.map_or(TypeExpr::Unit(span), |r| *r))
+                        }
+                        _ => None,
+                    },
+                },
+                Expression::Member { .. } => None,
+                Expression::Lambda { parameters, body } => {
+                    let arguments = parameters
+                        .value
+                        .iter()
+                        .map(|parameter| parameter.parameter_type.clone())
+                        .collect::<Option<Vec<_>>>()?;
+                    self.scopes.push(
+                        parameters
+                            .value
+                            .iter()
+                            .map(|parameter| {
+                                (parameter.name.value, parameter.parameter_type.clone())
+                            })
+                            .collect(),
+                    );
+                    let return_type = self.infer(body);
+                    self.scopes.pop();
+                    let return_type = return_type?;
+                    Some(TypeExpr::Function {
+                        keyword: parameters.span,
+                        arguments: Spanned::new(arguments, parameters.span),
+                        return_type: Some(Box::new(return_type)),
+                    })
+                }
+                Expression::Index { index, .. }
+                    if matches!(
+                        index.value.as_ref(),
+                        Node::Expression(Expression::Range { .. })
+                    ) =>
+                {
+                    None
+                }
+                Expression::Index { target, .. } => match self.infer(target)? {
+                    TypeExpr::Array { element, .. } => Some(*element),
+                    TypeExpr::Reference { inner, .. } => match *inner {
+                        TypeExpr::Array { element, .. } => Some(*element),
+                        _ => None,
+                    },
+                    _ => None,
+                },
+                Expression::Match { .. } => None,
+                Expression::Ternary {
+                    consequent,
+                    alternative,
+                    ..
+                } => self.infer(consequent).or_else(|| self.infer(alternative)),
+                Expression::Range { .. } => Some(TypeExpr::Array {
+                    element: Box::new(named("int", span)),
+                    length: None,
+                    span,
+                }),
+            },
+            Node::Block(_) | Node::Statement(_) => None,
+        }
+    }
+    pub(super) fn infer_generics(
+        &mut self,
+        function: &Function<'a>,
+        arguments: &[Node<'a>],
+        span: Span,
+    ) -> Result<'a, Vec<TypeExpr<'a>>> {
+        let parameters = function
+            .generics
+            .iter()
+            .map(|generic| generic.value)
+            .collect::<Vec<_>>();
+        let mut solution = vec![None; parameters.len()];
+        for (parameter, argument) in function.arguments.iter().zip(arguments) {
+            if let Some(argument_type) = self.infer(argument) {
+                if !unify(
+                    &parameter.argument_type,
+                    &argument_type,
+                    &parameters,
+                    &mut solution,
+                ) {
+                    return Err(Box::new(Error::new(
+                        ErrorKind::TypeMismatch,
+                        argument.calculate_span(),
+                        self.source,
+                    )));
+                }
+            }
+        }
+        solution
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                Box::new(Error::new(
+                    ErrorKind::CannotInferTypeArguments,
+                    span,
+                    self.source,
+                ))
+            })
+    }
+    pub(super) fn expect(
+        &mut self,
+        node: &mut Node<'a>,
+        expected: &TypeExpr<'a>,
+    ) -> Result<'a, ()> {
+        match (node, expected) {
+            (Node::Identifier(name), _) if !self.is_local(name.value) => {
+                let Some(function) = self
+                    .functions
+                    .get(name.value)
+                    .filter(|function| !function.generics.is_empty())
+                    .copied()
+                else {
+                    return Ok(());
+                };
+                let instance = self.infer_value_generics(function, expected, name.span)?;
+                name.value = self.instantiate(function, &instance, name.span)?;
+            }
+            (
+                Node::Expression(Expression::Lambda { parameters, .. }),
+                TypeExpr::Function { arguments, .. },
+            ) if parameters.value.len() == arguments.value.len() => {
+                for (parameter, argument) in parameters.value.iter_mut().zip(&arguments.value) {
+                    if parameter.parameter_type.is_none() {
+                        parameter.parameter_type = Some(argument.clone());
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+    pub(super) fn expect_from_calls(
+        &mut self,
+        statement: &mut Node<'a>,
+        rest: &[Node<'a>],
+    ) -> Result<'a, ()> {
+        let Node::Statement(Statement::Let(let_statement)) = statement else {
+            return Ok(());
+        };
+        let let_statement = &mut let_statement.value;
+        let Some(init) = let_statement.init.as_deref_mut() else {
+            return Ok(());
+        };
+        let untyped = match init {
+            Node::Expression(Expression::Lambda { parameters, .. }) => parameters
+                .value
+                .iter()
+                .any(|parameter| parameter.parameter_type.is_none()),
+            _ => false,
+        };
+        if let_statement.value_type.is_some() || !untyped {
+            return Ok(());
+        }
+        let mut calls = vec![];
+        for node in rest {
+            calls_to(let_statement.name, node, &mut calls);
+            if let Node::Statement(Statement::Let(shadow)) = node {
+                if shadow.value.name == let_statement.name {
+                    break;
+                }
+            }
+        }
+        let mut expected: Option<Vec<TypeExpr<'a>>> = None;
+        for call in calls {
+            let Some(types) = call
+                .iter()
+                .map(|argument| self.infer(argument))
+                .collect::<Option<Vec<_>>>()
+            else {
+                return Ok(());
+            };
+            match &expected {
+                Some(known)
+                    if known.len() != types.len()
+                        || known
+                            .iter()
+                            .zip(&types)
+                            .any(|(known, found)| known.to_string() != found.to_string()) =>
+                {
+                    return Ok(())
+                }
+                Some(_) => {}
+                None => expected = Some(types),
+            }
+        }
+        if let Some(arguments) = expected {
+            let span = init.calculate_span();
+            let expected = TypeExpr::Function {
+                keyword: span,
+                arguments: Spanned::new(arguments, span),
+                return_type: None,
+            };
+            self.expect(init, &expected)?;
+        }
+        Ok(())
+    }
+    pub(super) fn infer_value_generics(
+        &self,
+        function: &Function<'a>,
+        expected: &TypeExpr<'a>,
+        span: Span,
+    ) -> Result<'a, Vec<TypeExpr<'a>>> {
+        let parameters = function
+            .generics
+            .iter()
+            .map(|generic| generic.value)
+            .collect::<Vec<_>>();
+        let mut solution = vec![None; parameters.len()];
+        let function_type = TypeExpr::Function {
+            keyword: span,
+            arguments: Spanned::new(
+                function
+                    .arguments
+                    .iter()
+                    .map(|argument| argument.argument_type.clone())
+                    .collect(),
+                span,
+            ),
+            return_type: function.return_type.clone().map(Box::new),
+        };
+        if !unify(&function_type, expected, &parameters, &mut solution) {
+            return Err(Box::new(Error::new(
+                ErrorKind::TypeMismatch,
+                span,
+                self.source,
+            )));
+        }
+        solution
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                Box::new(Error::new(
+                    ErrorKind::CannotInferTypeArguments,
+                    span,
+                    self.source,
+                ))
+            })
+    }
+    pub(super) fn bind(
+        &self,
+        pattern: &Pattern<'a>,
+        value_type: Option<&TypeExpr<'a>>,
+        scope: &mut Scope<'a>,
+    ) {
+        match pattern {
+            Pattern::Binding(name) => {
+                scope.insert(name.value, value_type.cloned());
+            }
+            Pattern::Variant {
+                path,
+                fields: Some(fields),
+            } => {
+                let (enum_name, variant_name) = variant_path(path.value);
+                let variant = self
+                    .enums
+                    .get(enum_name)
+                    .and_then(|enumeration| enumeration.variant(variant_name));
+                let field_types = variant.and_then(|variant| variant.fields.as_ref());
+                for (index, field) in fields.value.iter().enumerate() {
+                    let field_type = field_types.and_then(|field_types| field_types.get(index));
+                    self.bind(field, field_type, scope);
+                }
+            }
+            Pattern::Wildcard(_)
+            | Pattern::Integer(_)
+            | Pattern::Float(_)
+            | Pattern::Variant { fields: None, .. } => {}
+        }
+    }
+    fn enum_type(&self, path: &Spanned<&'a str>) -> Option<TypeExpr<'a>> {
+        let enumeration = self.enums.get(variant_path(path.value).0)?;
+        Some(named(enumeration.name, path.span))
+    }
+}
+
+fn unify<'a>(
+    parameter: &TypeExpr<'a>,
+    argument: &TypeExpr<'a>,
+    parameters: &[&'a str],
+    solution: &mut [Option<TypeExpr<'a>>],
+) -> bool {
+    let mut unify_all = |parameters_types: &[TypeExpr<'a>], argument_types: &[TypeExpr<'a>]| {
+        parameters_types.len() == argument_types.len()
+            && parameters_types
+                .iter()
+                .zip(argument_types)
+                .all(|(parameter, argument)| unify(parameter, argument, parameters, solution))
+    };
+    match (parameter, argument) {
+        (
+            TypeExpr::Path {
+                path,
+                generics: None,
+            },
+            _,
+        ) if parameters.contains(&path.value) => {
+            let index = parameters
+                .iter()
+                .position(|parameter| *parameter == path.value)
+                .unwrap_or_default();
+            match &solution[index] {
+                Some(solved) => solved.to_string() == argument.to_string(),
+                None => {
+                    solution[index] = Some(argument.clone());
+                    true
+                }
+            }
+        }
+        (
+            TypeExpr::Path {
+                path: parameter_path,
+                generics: parameter_generics,
+            },
+            TypeExpr::Path {
+                path: argument_path,
+                generics: argument_generics,
+            },
+        ) => {
+            parameter_path.value == argument_path.value
+                && match (parameter_generics, argument_generics) {
+                    (Some(parameter_generics), Some(argument_generics)) => {
+                        unify_all(&parameter_generics.value, &argument_generics.value)
+                    }
+                    (None, None) => true,
+                    _ => false,
+                }
+        }
+        (TypeExpr::Unit(_), TypeExpr::Unit(_)) => true,
+        (TypeExpr::Tuple(parameter_elements), TypeExpr::Tuple(argument_elements)) => {
+            unify_all(&parameter_elements.value, &argument_elements.value)
+        }
+        (
+            TypeExpr::Array {
+                element: parameter_element,
+                ..
+            },
+            TypeExpr::Array {
+                element: argument_element,
+                ..
+            },
+        ) => unify(parameter_element, argument_element, parameters, solution),
+        (
+            TypeExpr::Function {
+                arguments: parameter_arguments,
+                return_type: parameter_return,
+                ..
+            },
+            TypeExpr::Function {
+                arguments: argument_arguments,
+                return_type: argument_return,
+                ..
+            },
+        ) => {
+            unify_all(&parameter_arguments.value, &argument_arguments.value)
+                && match (parameter_return, argument_return) {
+                    (Some(parameter_return), Some(argument_return)) => {
+                        unify(parameter_return, argument_return, parameters, solution)
+                    }
+                    (None, None) => true,
+                    _ => false,
+                }
+        }
+        (
+            TypeExpr::Reference {
+                mutable: parameter_mutable,
+                inner: parameter_inner,
+                ..
+            },
+            TypeExpr::Reference {
+                mutable: argument_mutable,
+                inner: argument_inner,
+                ..
+            },
+        ) => {
+            parameter_mutable == argument_mutable
+                && unify(parameter_inner, argument_inner, parameters, solution)
+        }
+        _ => false,
+    }
+}
+
+fn calls_to<'n, 'a>(name: &str, node: &'n Node<'a>, calls: &mut Vec<&'n [Node<'a>]>) {
+    match node {
+        Node::Integer(_) | Node::Float(_) | Node::String(_) | Node::Identifier(_) => {}
+        Node::Block(block) => {
+            for statement in &block.value {
+                calls_to(name, statement, calls);
+            }
+        }
+        Node::Array(array) => {
+            for element in &array.value {
+                calls_to(name, element, calls);
+            }
+        }
+        Node::Expression(expression) => match expression {
+            Expression::Infix { lhs, rhs, .. } => {
+                calls_to(name, lhs, calls);
+                calls_to(name, rhs, calls);
+            }
+            Expression::Prefix { value, .. } => calls_to(name, value, calls),
+            Expression::Index { target, index } => {
+                calls_to(name, target, calls);
+                calls_to(name, &index.value, calls);
+            }
+            Expression::Ternary {
+                test,
+                consequent,
+                alternative,
+            } => {
+                calls_to(name, test, calls);
+                calls_to(name, consequent, calls);
+                calls_to(name, alternative, calls);
+            }
+            Expression::Range { start, end, .. } => {
+                calls_to(name, start, calls);
+                calls_to(name, end, calls);
+            }
+            Expression::Match { value, arms, .. } => {
+                calls_to(name, value, calls);
+                for arm in &arms.value {
+                    calls_to(name, &arm.body, calls);
+                }
+            }
+            Expression::Member { target, .. } => calls_to(name, target, calls),
+            Expression::Lambda { body, .. } => calls_to(name, body, calls),
+            Expression::Call {
+                callee, arguments, ..
+            } => {
+                if matches!(callee.as_ref(), Node::Identifier(callee) if callee.value == name) {
+                    calls.push(&arguments.value);
+                }
+                calls_to(name, callee, calls);
+                for argument in &arguments.value {
+                    calls_to(name, argument, calls);
+                }
+            }
+        },
+        Node::Statement(statement) => match statement {
+            Statement::While(while_statement) => {
+                calls_to(name, &while_statement.value.test, calls);
+                calls_to(name, &while_statement.value.body, calls);
+            }
+            Statement::For(for_statement) => {
+                calls_to(name, &for_statement.value.iterable, calls);
+                calls_to(name, &for_statement.value.body, calls);
+            }
+            Statement::If(if_statement) => {
+                calls_to(name, &if_statement.value.test, calls);
+                calls_to(name, &if_statement.value.consequent, calls);
+                if let Some(alternative) = &if_statement.value.alternative {
+                    calls_to(name, alternative, calls);
+                }
+            }
+            Statement::Let(let_statement) => {
+                if let Some(init) = &let_statement.value.init {
+                    calls_to(name, init, calls);
+                }
+            }
+            Statement::Return(return_statement) => {
+                if let Some(value) = &return_statement.value.value {
+                    calls_to(name, value, calls);
+                }
+            }
+        },
+    }
+}
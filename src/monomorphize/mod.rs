@@ -0,0 +1,476 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{
+    common::{
+        error::{Error, ErrorKind, Result},
+        intern::Interner,
+        source::Source,
+        span::Span,
+    },
+    parser::ast::{
+        calculate_span::CalculateSpan, enumeration::Enum, expression::Expression,
+        function::Function, node::Node, spanned::Spanned, statement::Statement, types::TypeExpr,
+        Program,
+    },
+};
+
+pub mod infer;
+
+pub const MAX_INSTANTIATION_DEPTH: usize = 64;
+
+type Scope<'a> = HashMap<&'a str, Option<TypeExpr<'a>>>;
+
+#[derive(Clone, Debug)]
+pub struct Monomorphizer<'a, 'p> {
+    source: Source<'a>,
+    names: &'a Interner,
+    program: &'p Program<'a>,
+    functions: HashMap<&'a str, &'p Function<'a>>,
+    enums: HashMap<&'a str, &'p Enum<'a>>,
+    signatures: HashMap<&'a str, Option<TypeExpr<'a>>>,
+    queue: VecDeque<(&'p Function<'a>, &'a str, Vec<TypeExpr<'a>>, usize)>,
+    instantiated: HashSet<&'a str>,
+    scopes: Vec<Scope<'a>>,
+    return_type: Option<TypeExpr<'a>>,
+    depth: usize,
+}
+
+impl<'a, 'p> Monomorphizer<'a, 'p> {
+    pub fn new(source: Source<'a>, program: &'p Program<'a>, names: &'a Interner) -> Self {
+        Self {
+            source,
+            names,
+            program,
+            functions: program
+                .functions
+                .iter()
+                .map(|function| (function.name, function))
+                .collect(),
+            enums: program
+                .enums
+                .iter()
+                .map(|enumeration| (enumeration.name, enumeration))
+                .collect(),
+            signatures: program
+                .functions
+                .iter()
+                .filter(|function| function.generics.is_empty())
+                .map(|function| (function.name, function.return_type.clone()))
+                .collect(),
+            queue: VecDeque::new(),
+            instantiated: HashSet::new(),
+            scopes: vec![],
+            return_type: None,
+            depth: 0,
+        }
+    }
+    pub fn run(mut self) -> Result<'a, Program<'a>> {
+        for function in &self.program.functions {
+            if function.generics.is_empty() {
+                self.instantiated.insert(function.name);
+                self.queue.push_back((function, function.name, vec![], 0));
+            }
+        }
+        let mut functions = vec![];
+        while let Some((function, name, arguments, depth)) = self.queue.pop_front() {
+            self.depth = depth;
+            functions.push(self.specialize(function, name, &arguments)?);
+        }
+        Ok(Program {
+            path: self.program.path,
+            module: self.program.module.clone(),
+            imports: self.program.imports.clone(),
+            functions,
+            enums: self.program.enums.clone(),
+        })
+    }
+    fn specialize(
+        &mut self,
+        function: &Function<'a>,
+        name: &'a str,
+        arguments: &[TypeExpr<'a>],
+    ) -> Result<'a, Function<'a>> {
+        let parameters = function
+            .generics
+            .iter()
+            .map(|generic| generic.value)
+            .collect::<Vec<_>>();
+        self.source = function.source;
+        let mut specialized = function.clone();
+        specialized.name = name;
+        specialized.generics = vec![];
+        for argument in &mut specialized.arguments {
+            argument.argument_type = substitute(&argument.argument_type, &parameters, arguments);
+        }
+        specialized.return_type = specialized
+            .return_type
+            .map(|return_type| substitute(&return_type, &parameters, arguments));
+        self.scopes = vec![specialized
+            .arguments
+            .iter()
+            .map(|argument| (argument.name, Some(argument.argument_type.clone())))
+            .collect()];
+        self.return_type = specialized.return_type.clone();
+        self.rewrite(&mut specialized.body, &parameters, arguments)?;
+        Ok(specialized)
+    }
+    fn rewrite(
+        &mut self,
+        node: &mut Node<'a>,
+        parameters: &[&'a str],
+        arguments: &[TypeExpr<'a>],
+    ) -> Result<'a, ()> {
+        match node {
+            Node::Integer(_) | Node::Float(_) | Node::String(_) => Ok(()),
+            Node::Identifier(name) => match self.functions.get(name.value) {
+                Some(function) if !function.generics.is_empty() && !self.is_local(name.value) => {
+                    Err(Box::new(Error::new(
+                        ErrorKind::CannotInferTypeArguments,
+                        name.span,
+                        self.source,
+                    )))
+                }
+                _ => Ok(()),
+            },
+            Node::Block(block) => {
+                self.scopes.push(Scope::new());
+                let mut result = Ok(());
+                for index in 0..block.value.len() {
+                    let (statement, rest) = block.value[index..].split_at_mut(1);
+                    if let Err(err) = self.expect_from_calls(&mut statement[0], rest) {
+                        result = Err(err);
+                        break;
+                    }
+                    result = self.rewrite(&mut statement[0], parameters, arguments);
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                self.scopes.pop();
+                result
+            }
+            Node::Array(array) => array
+                .value
+                .iter_mut()
+                .try_for_each(|element| self.rewrite(element, parameters, arguments)),
+            Node::Expression(expression) => {
+                let span = expression.calculate_span();
+                match expression {
+                    Expression::Infix { lhs, rhs, .. } => {
+                        self.rewrite(lhs, parameters, arguments)?;
+                        self.rewrite(rhs, parameters, arguments)
+                    }
+                    Expression::Prefix { value, .. } => self.rewrite(value, parameters, arguments),
+                    Expression::Index { target, index } => {
+                        self.rewrite(target, parameters, arguments)?;
+                        self.rewrite(&mut index.value, parameters, arguments)
+                    }
+                    Expression::Ternary {
+                        test,
+                        consequent,
+                        alternative,
+                    } => {
+                        self.rewrite(test, parameters, arguments)?;
+                        self.rewrite(consequent, parameters, arguments)?;
+                        self.rewrite(alternative, parameters, arguments)
+                    }
+                    Expression::Range { start, end, .. } => {
+                        self.rewrite(start, parameters, arguments)?;
+                        self.rewrite(end, parameters, arguments)
+                    }
+                    Expression::Match { value, arms, .. } => {
+                        self.rewrite(value, parameters, arguments)?;
+                        let value_type = self.infer(value);
+                        for arm in &mut arms.value {
+                            let mut scope = Scope::new();
+                            self.bind(&arm.pattern, value_type.as_ref(), &mut scope);
+                            self.scopes.push(scope);
+                            let result = self.rewrite(&mut arm.body, parameters, arguments);
+                            self.scopes.pop();
+                            result?;
+                        }
+                        Ok(())
+                    }
+                    Expression::Member { target, .. } => {
+                        self.rewrite(target, parameters, arguments)
+                    }
+                    Expression::Lambda {
+                        parameters: lambda_parameters,
+                        body,
+                    } => {
+                        let mut scope = Scope::new();
+                        for parameter in &mut lambda_parameters.value {
+                            parameter.parameter_type =
+                                parameter.parameter_type.as_ref().map(|parameter_type| {
+                                    substitute(parameter_type, parameters, arguments)
+                                });
+                            scope.insert(parameter.name.value, parameter.parameter_type.clone());
+                        }
+                        self.scopes.push(scope);
+                        let return_type = self.return_type.take();
+                        let result = self.rewrite(body, parameters, arguments);
+                        self.return_type = return_type;
+                        self.scopes.pop();
+                        result
+                    }
+                    Expression::Call {
+                        callee,
+                        generics,
+                        arguments: call_arguments,
+                    } => {
+                        if !matches!(callee.as_ref(), Node::Identifier(_)) {
+                            self.rewrite(callee, parameters, arguments)?;
+                        }
+                        if let Node::Identifier(name) = callee.as_ref() {
+                            if let Some(function) = self
+                                .functions
+                                .get(name.value)
+                                .filter(|function| function.generics.is_empty())
+                                .copied()
+                                .filter(|_| !self.is_local(name.value))
+                            {
+                                for (argument, parameter) in
+                                    call_arguments.value.iter_mut().zip(&function.arguments)
+                                {
+                                    self.expect(argument, &parameter.argument_type)?;
+                                }
+                            }
+                        }
+                        for argument in &mut call_arguments.value {
+                            self.rewrite(argument, parameters, arguments)?;
+                        }
+                        let name = match callee.as_mut() {
+                            Node::Identifier(name) if !self.is_local(name.value) => name,
+                            _ => return Ok(()),
+                        };
+                        let function = match self.functions.get(name.value) {
+                            Some(function) => *function,
+                            None => return Ok(()),
+                        };
+                        let explicit = generics.take().map(|generics| {
+                            generics
+                                .value
+                                .iter()
+                                .map(|generic| substitute(generic, parameters, arguments))
+                                .collect::<Vec<_>>()
+                        });
+                        if function.generics.is_empty() {
+                            return match explicit {
+                                Some(explicit) => Err(Box::new(Error::new(
+                                    ErrorKind::WrongNumberOfTypeArguments {
+                                        expected: 0,
+                                        received: explicit.len(),
+                                    },
+                                    span,
+                                    self.source,
+                                ))),
+                                None => Ok(()),
+                            };
+                        }
+                        let instance = match explicit {
+                            Some(explicit) if explicit.len() != function.generics.len() => {
+                                return Err(Box::new(Error::new(
+                                    ErrorKind::WrongNumberOfTypeArguments {
+                                        expected: function.generics.len(),
+                                        received: explicit.len(),
+                                    },
+                                    span,
+                                    self.source,
+                                )))
+                            }
+                            Some(explicit) => explicit,
+                            None => self.infer_generics(function, &call_arguments.value, span)?,
+                        };
+                        let generics = function
+                            .generics
+                            .iter()
+                            .map(|generic| generic.value)
+                            .collect::<Vec<_>>();
+                        for (argument, parameter) in
+                            call_arguments.value.iter_mut().zip(&function.arguments)
+                        {
+                            let expected =
+                                substitute(&parameter.argument_type, &generics, &instance);
+                            self.expect(argument, &expected)?;
+                        }
+                        name.value = self.instantiate(function, &instance, span)?;
+                        Ok(())
+                    }
+                }
+            }
+            Node::Statement(statement) => match statement {
+                Statement::While(while_statement) => {
+                    self.rewrite(&mut while_statement.value.test, parameters, arguments)?;
+                    self.rewrite(&mut while_statement.value.body, parameters, arguments)
+                }
+                Statement::For(for_statement) => {
+                    let for_statement = &mut for_statement.value;
+                    self.rewrite(&mut for_statement.iterable, parameters, arguments)?;
+                    let element = match self.infer(&for_statement.iterable) {
+                        Some(TypeExpr::Array { element, .. }) => Some(*element),
+                        _ => None,
+                    };
+                    self.scopes
+                        .push(Scope::from([(for_statement.binding.value, element)]));
+                    let result = self.rewrite(&mut for_statement.body, parameters, arguments);
+                    self.scopes.pop();
+                    result
+                }
+                Statement::If(if_statement) => {
+                    self.rewrite(&mut if_statement.value.test, parameters, arguments)?;
+                    self.rewrite(&mut if_statement.value.consequent, parameters, arguments)?;
+                    match &mut if_statement.value.alternative {
+                        Some(alternative) => self.rewrite(alternative, parameters, arguments),
+                        None => Ok(()),
+                    }
+                }
+                Statement::Let(let_statement) => {
+                    let let_statement = &mut let_statement.value;
+                    let_statement.value_type = let_statement
+                        .value_type
+                        .as_ref()
+                        .map(|value_type| substitute(value_type, parameters, arguments));
+                    if let (Some(value_type), Some(init)) =
+                        (&let_statement.value_type, &mut let_statement.init)
+                    {
+                        self.expect(init, value_type)?;
+                    }
+                    if let Some(init) = &mut let_statement.init {
+                        self.rewrite(init, parameters, arguments)?;
+                    }
+                    let value_type = match (&let_statement.value_type, &let_statement.init) {
+                        (Some(value_type), _) => Some(value_type.clone()),
+                        (None, Some(init)) => self.infer(init),
+                        (None, None) => None,
+                    };
+                    if let Some(scope) = self.scopes.last_mut() {
+                        scope.insert(let_statement.name, value_type);
+                    }
+                    Ok(())
+                }
+                Statement::Return(return_statement) => match &mut return_statement.value.value {
+                    Some(value) => {
+                        if let Some(return_type) = self.return_type.clone() {
+                            self.expect(value, &return_type)?;
+                        }
+                        self.rewrite(value, parameters, arguments)
+                    }
+                    None => Ok(()),
+                },
+            },
+        }
+    }
+    fn is_local(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains_key(name))
+    }
+    fn instantiate(
+        &mut self,
+        function: &'p Function<'a>,
+        arguments: &[TypeExpr<'a>],
+        span: Span,
+    ) -> Result<'a, &'a str> {
+        let name = self.names.intern(format!(
+            "{}<{}>",
+            function.name,
+            arguments
+                .iter()
+                .map(|argument| argument.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        if self.instantiated.insert(name) {
+            if self.depth >= MAX_INSTANTIATION_DEPTH {
+                return Err(Box::new(Error::new(
+                    ErrorKind::InstantiationTooDeep {
+                        name: function.name.to_string(),
+                    },
+                    span,
+                    self.source,
+                )));
+            }
+            let parameters = function
+                .generics
+                .iter()
+                .map(|generic| generic.value)
+                .collect::<Vec<_>>();
+            self.signatures.insert(
+                name,
+                function
+                    .return_type
+                    .as_ref()
+                    .map(|return_type| substitute(return_type, &parameters, arguments)),
+            );
+            self.queue
+                .push_back((function, name, arguments.to_vec(), self.depth + 1));
+        }
+        Ok(name)
+    }
+}
+
+pub fn substitute<'a>(
+    type_expr: &TypeExpr<'a>,
+    parameters: &[&'a str],
+    arguments: &[TypeExpr<'a>],
+) -> TypeExpr<'a> {
+    let substitute_all = |types: &[TypeExpr<'a>]| {
+        types
+            .iter()
+            .map(|element| substitute(element, parameters, arguments))
+            .collect::<Vec<_>>()
+    };
+    match type_expr {
+        TypeExpr::Path {
+            path,
+            generics: None,
+        } => match parameters
+            .iter()
+            .position(|parameter| *parameter == path.value)
+        {
+            Some(index) => arguments[index].clone(),
+            None => type_expr.clone(),
+        },
+        TypeExpr::Path {
+            path,
+            generics: Some(generics),
+        } => TypeExpr::Path {
+            path: path.clone(),
+            generics: Some(Spanned::new(substitute_all(&generics.value), generics.span)),
+        },
+        TypeExpr::Unit(_) => type_expr.clone(),
+        TypeExpr::Tuple(elements) => {
+            TypeExpr::Tuple(Spanned::new(substitute_all(&elements.value), elements.span))
+        }
+        TypeExpr::Array {
+            element,
+            length,
+            span,
+        } => TypeExpr::Array {
+            element: Box::new(substitute(element, parameters, arguments)),
+            length: length.clone(),
+            span: *span,
+        },
+        TypeExpr::Function {
+            keyword,
+            arguments: function_arguments,
+            return_type,
+        } => TypeExpr::Function {
+            keyword: *keyword,
+            arguments: Spanned::new(
+                substitute_all(&function_arguments.value),
+                function_arguments.span,
+            ),
+            return_type: return_type
+                .as_ref()
+                .map(|return_type| Box::new(substitute(return_type, parameters, arguments))),
+        },
+        TypeExpr::Reference {
+            ampersand,
+            mutable,
+            inner,
+        } => TypeExpr::Reference {
+            ampersand: *ampersand,
+            mutable: *mutable,
+            inner: Box::new(substitute(inner, parameters, arguments)),
+        },
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ir::{
+    dominators::Dominators,
+    transform::{replace_uses, resolve},
+    BlockId, Function, InstructionKind, ValueId,
+};
+
+pub fn eliminate_common_subexpressions(function: &mut Function) {
+    let dominators = Dominators::new(function);
+    let mut available: HashMap<String, Vec<(BlockId, ValueId)>> = HashMap::new();
+    let mut replacements = HashMap::new();
+    let mut removed = HashSet::new();
+    for block in &mut function.blocks {
+        for phi in &mut block.phis {
+            for (_, value) in &mut phi.incoming {
+                *value = resolve(&replacements, *value);
+            }
+        }
+        for instruction in &mut block.instructions {
+            for operand in instruction.kind.operands_mut() {
+                *operand = resolve(&replacements, *operand);
+            }
+            if matches!(
+                instruction.kind,
+                InstructionKind::Call { .. }
+                    | InstructionKind::CallIndirect { .. }
+                    | InstructionKind::Closure { .. }
+            ) {
+                continue;
+            }
+            let key = format!("{}: {}", instruction.value_type, instruction.kind);
+            let candidates = available.entry(key).or_default();
+            match candidates
+                .iter()
+                .find(|(defined, _)| dominators.dominates(*defined, block.id))
+            {
+                Some((_, existing)) => {
+                    replacements.insert(instruction.value, *existing);
+                    removed.insert(instruction.value);
+                }
+                None => candidates.push((block.id, instruction.value)),
+            }
+        }
+        for operand in block.terminator.operands_mut() {
+            *operand = resolve(&replacements, *operand);
+        }
+    }
+    for block in &mut function.blocks {
+        block
+            .instructions
+            .retain(|instruction| !removed.contains(&instruction.value));
+    }
+    replace_uses(function, &replacements);
+}
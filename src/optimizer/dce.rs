@@ -0,0 +1,35 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ir::{Function, ValueId};
+
+pub fn eliminate_dead_code(function: &mut Function) {
+    let mut operands: HashMap<ValueId, Vec<ValueId>> = HashMap::new();
+    let mut live = HashSet::new();
+    let mut worklist = vec![];
+    for block in &function.blocks {
+        for phi in &block.phis {
+            operands.insert(
+                phi.value,
+                phi.incoming.iter().map(|(_, value)| *value).collect(),
+            );
+        }
+        for instruction in &block.instructions {
+            operands.insert(instruction.value, instruction.kind.operands());
+            if instruction.has_side_effects() {
+                worklist.push(instruction.value);
+            }
+        }
+        worklist.extend(block.terminator.operands());
+    }
+    while let Some(value) = worklist.pop() {
+        if live.insert(value) {
+            worklist.extend(operands.get(&value).into_iter().flatten());
+        }
+    }
+    for block in &mut function.blocks {
+        block.phis.retain(|phi| live.contains(&phi.value));
+        block
+            .instructions
+            .retain(|instruction| live.contains(&instruction.value));
+    }
+}
@@ -0,0 +1,390 @@
+use std::mem;
+
+use crate::{
+    common::{
+        error::{Error, ErrorKind, Result},
+        intern::Interner,
+        source::Source,
+        span::Span,
+    },
+    parser::ast::{
+        calculate_span::CalculateSpan,
+        expression::{Expression, Operator},
+        node::Node,
+        pattern::Pattern,
+        spanned::Spanned,
+        statement::Statement,
+        Program,
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Constant {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+}
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Numeric {
+    Integer,
+    Float,
+}
+
+pub struct Folder<'a> {
+    source: Source<'a>,
+    names: &'a Interner,
+    bindings: Vec<(&'a str, Option<Numeric>)>,
+}
+
+impl<'a> Folder<'a> {
+    pub fn new(source: Source<'a>, names: &'a Interner) -> Self {
+        Self {
+            source,
+            names,
+            bindings: vec![],
+        }
+    }
+    pub fn run(mut self, mut program: Program<'a>) -> Result<'a, Program<'a>> {
+        for function in &mut program.functions {
+            self.source = function.source;
+            self.bindings.clear();
+            self.fold(&mut function.body)?;
+        }
+        Ok(program)
+    }
+    fn fold(&mut self, node: &mut Node<'a>) -> Result<'a, ()> {
+        match node {
+            Node::Integer(_) | Node::Float(_) | Node::String(_) | Node::Identifier(_) => Ok(()),
+            Node::Block(block) => {
+                let scope = self.bindings.len();
+                let result = block
+                    .value
+                    .iter_mut()
+                    .try_for_each(|statement| self.fold(statement));
+                self.bindings.truncate(scope);
+                result
+            }
+            Node::Array(array) => array
+                .value
+                .iter_mut()
+                .try_for_each(|element| self.fold(element)),
+            Node::Expression(expression) => {
+                if let Some(folded) = self.fold_expression(expression)? {
+                    *node = folded;
+                }
+                Ok(())
+            }
+            Node::Statement(statement) => {
+                if let Some(folded) = self.fold_statement(statement)? {
+                    *node = folded;
+                }
+                Ok(())
+            }
+        }
+    }
+    fn fold_expression(&mut self, expression: &mut Expression<'a>) -> Result<'a, Option<Node<'a>>> {
+        let span = expression.calculate_span();
+        match expression {
+            Expression::Infix { operator, lhs, rhs } => {
+                if let Operator::Assignment = operator {
+                    self.fold(rhs)?;
+                    return match lhs.as_mut() {
+                        Node::Expression(Expression::Index { index, .. }) => {
+                            self.fold(&mut index.value).map(|_| None)
+                        }
+                        _ => Ok(None),
+                    };
+                }
+                self.fold(lhs)?;
+                self.fold(rhs)?;
+                match (literal(lhs), literal(rhs)) {
+                    (Some(lhs), Some(rhs)) => Ok(self
+                        .binary(*operator, lhs, rhs, span)?
+                        .and_then(|constant| self.node(constant, span))),
+                    _ => Ok(self.simplify(*operator, lhs, rhs)),
+                }
+            }
+            Expression::Prefix { operator, value } => {
+                self.fold(value)?;
+                match (operator.value, literal(value)) {
+                    (Operator::Plus, Some(constant)) => Ok(self.node(constant, span)),
+                    (Operator::Minus, Some(Constant::Integer(integer))) => {
+                        match integer.checked_neg() {
+                            Some(integer) => Ok(self.node(Constant::Integer(integer), span)),
+                            None => Err(self.error(ErrorKind::IntegerOverflow, span)),
+                        }
+                    }
+                    (Operator::Minus, Some(Constant::Float(float))) => {
+                        Ok(self.node(Constant::Float(-float), span))
+                    }
+                    _ => Ok(None),
+                }
+            }
+            Expression::Call {
+                callee, arguments, ..
+            } => {
+                self.fold(callee)?;
+                arguments
+                    .value
+                    .iter_mut()
+                    .try_for_each(|argument| self.fold(argument))?;
+                Ok(None)
+            }
+            Expression::Member { target, .. } => self.fold(target).map(|_| None),
+            Expression::Lambda { parameters, body } => {
+                let scope = self.bindings.len();
+                self.bindings.extend(
+                    parameters
+                        .value
+                        .iter()
+                        .map(|parameter| (parameter.name.value, None)),
+                );
+                let result = self.fold(body);
+                self.bindings.truncate(scope);
+                result.map(|_| None)
+            }
+            Expression::Index { target, index } => {
+                self.fold(target)?;
+                self.fold(&mut index.value).map(|_| None)
+            }
+            Expression::Match { value, arms, .. } => {
+                self.fold(value)?;
+                arms.value.iter_mut().try_for_each(|arm| {
+                    let scope = self.bindings.len();
+                    shadow(&arm.pattern, &mut self.bindings);
+                    let result = self.fold(&mut arm.body);
+                    self.bindings.truncate(scope);
+                    result
+                })?;
+                Ok(None)
+            }
+            Expression::Ternary {
+                test,
+                consequent,
+                alternative,
+            } => {
+                self.fold(test)?;
+                self.fold(consequent)?;
+                self.fold(alternative)?;
+                match self.constant(test)? {
+                    Some(Constant::Boolean(true)) => {
+                        Ok(Some(mem::replace(&mut **consequent, empty(span))))
+                    }
+                    Some(Constant::Boolean(false)) => {
+                        Ok(Some(mem::replace(&mut **alternative, empty(span))))
+                    }
+                    _ => Ok(None),
+                }
+            }
+            Expression::Range { start, end, .. } => {
+                self.fold(start)?;
+                self.fold(end).map(|_| None)
+            }
+        }
+    }
+    fn fold_statement(&mut self, statement: &mut Statement<'a>) -> Result<'a, Option<Node<'a>>> {
+        match statement {
+            Statement::While(while_statement) => {
+                self.fold(&mut while_statement.value.test)?;
+                if self.constant(&while_statement.value.test)? == Some(Constant::Boolean(false)) {
+                    return Ok(Some(empty(while_statement.span)));
+                }
+                self.fold(&mut while_statement.value.body).map(|_| None)
+            }
+            Statement::For(for_statement) => {
+                let for_statement = &mut for_statement.value;
+                self.fold(&mut for_statement.iterable)?;
+                let scope = self.bindings.len();
+                self.bindings.push((for_statement.binding.value, None));
+                let result = self.fold(&mut for_statement.body);
+                self.bindings.truncate(scope);
+                result.map(|_| None)
+            }
+            Statement::If(if_statement) => {
+                let span = if_statement.span;
+                let if_statement = &mut if_statement.value;
+                self.fold(&mut if_statement.test)?;
+                self.fold(&mut if_statement.consequent)?;
+                if let Some(alternative) = &mut if_statement.alternative {
+                    self.fold(alternative)?;
+                }
+                match self.constant(&if_statement.test)? {
+                    Some(Constant::Boolean(true)) => Ok(Some(mem::replace(
+                        &mut *if_statement.consequent,
+                        empty(span),
+                    ))),
+                    Some(Constant::Boolean(false)) => Ok(Some(
+                        if_statement
+                            .alternative
+                            .take()
+                            .map_or_else(|| empty(span), |alternative| *alternative),
+                    )),
+                    _ => Ok(None),
+                }
+            }
+            Statement::Let(let_statement) => {
+                let let_statement = &mut let_statement.value;
+                if let Some(init) = &mut let_statement.init {
+                    self.fold(init)?;
+                }
+                let numeric = match &let_statement.init {
+                    Some(init) if !let_statement.mutable => self.numeric(init),
+                    _ => None,
+                };
+                self.bindings.push((let_statement.name, numeric));
+                Ok(None)
+            }
+            Statement::Return(return_statement) => match &mut return_statement.value.value {
+                Some(value) => self.fold(value).map(|_| None),
+                None => Ok(None),
+            },
+        }
+    }
+    fn constant(&self, node: &Node<'a>) -> Result<'a, Option<Constant>> {
+        match node {
+            Node::Expression(Expression::Infix { operator, lhs, rhs }) => {
+                match (literal(lhs), literal(rhs)) {
+                    (Some(lhs), Some(rhs)) => {
+                        self.binary(*operator, lhs, rhs, node.calculate_span())
+                    }
+                    _ => Ok(None),
+                }
+            }
+            _ => Ok(literal(node)),
+        }
+    }
+    fn binary(
+        &self,
+        operator: Operator,
+        lhs: Constant,
+        rhs: Constant,
+        span: Span,
+    ) -> Result<'a, Option<Constant>> {
+        let constant = match (operator, lhs, rhs) {
+            (Operator::Equal, lhs, rhs) => Constant::Boolean(lhs == rhs),
+            (Operator::Divide, Constant::Integer(_), Constant::Integer(0)) => {
+                return Err(self.error(ErrorKind::DivisionByZero, span))
+            }
+            (operator, Constant::Integer(lhs), Constant::Integer(rhs)) => {
+                let result = match operator {
+                    Operator::Plus => lhs.checked_add(rhs),
+                    Operator::Minus => lhs.checked_sub(rhs),
+                    Operator::Multiply => lhs.checked_mul(rhs),
+                    Operator::Divide => lhs.checked_div(rhs),
+                    Operator::LessThen => return Ok(Some(Constant::Boolean(lhs < rhs))),
+                    Operator::GreaterThen => return Ok(Some(Constant::Boolean(lhs > rhs))),
+                    Operator::LessThenEqual => return Ok(Some(Constant::Boolean(lhs <= rhs))),
+                    Operator::GreaterThenEqual => return Ok(Some(Constant::Boolean(lhs >= rhs))),
+                    _ => return Ok(None),
+                };
+                match result {
+                    Some(integer) => Constant::Integer(integer),
+                    None => return Err(self.error(ErrorKind::IntegerOverflow, span)),
+                }
+            }
+            (operator, Constant::Float(lhs), Constant::Float(rhs)) => match operator {
+                Operator::Plus => Constant::Float(lhs + rhs),
+                Operator::Minus => Constant::Float(lhs - rhs),
+                Operator::Multiply => Constant::Float(lhs * rhs),
+                Operator::Divide => Constant::Float(lhs / rhs),
+                Operator::LessThen => Constant::Boolean(lhs < rhs),
+                Operator::GreaterThen => Constant::Boolean(lhs > rhs),
+                Operator::LessThenEqual => Constant::Boolean(lhs <= rhs),
+                Operator::GreaterThenEqual => Constant::Boolean(lhs >= rhs),
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(constant))
+    }
+    fn numeric(&self, node: &Node<'a>) -> Option<Numeric> {
+        match node {
+            Node::Integer(_) => Some(Numeric::Integer),
+            Node::Float(_) => Some(Numeric::Float),
+            Node::Identifier(name) => self
+                .bindings
+                .iter()
+                .rev()
+                .find(|(binding, _)| *binding == name.value)
+                .and_then(|(_, numeric)| *numeric),
+            Node::Expression(Expression::Prefix { operator, value })
+                if matches!(operator.value, Operator::Plus | Operator::Minus) =>
+            {
+                self.numeric(value)
+            }
+            Node::Expression(Expression::Infix {
+                operator: Operator::Plus | Operator::Minus | Operator::Multiply | Operator::Divide,
+                lhs,
+                rhs,
+            }) => self
+                .numeric(lhs)
+                .filter(|&lhs| self.numeric(rhs) == Some(lhs)),
+            _ => None,
+        }
+    }
+    fn simplify(
+        &self,
+        operator: Operator,
+        lhs: &mut Node<'a>,
+        rhs: &mut Node<'a>,
+    ) -> Option<Node<'a>> {
+        let identity = |node: &Node, other: &Node, integer: i64, float: f64| match (
+            literal(node),
+            self.numeric(other),
+        ) {
+            (Some(Constant::Integer(value)), Some(Numeric::Integer)) => value == integer,
+            (Some(Constant::Float(value)), Some(Numeric::Float)) => value == float,
+            _ => false,
+        };
+        let take = |node: &mut Node<'a>| Some(mem::replace(node, empty(Span::new(0, 0))));
+        match operator {
+            Operator::Plus if identity(rhs, lhs, 0, 0.0) => take(lhs),
+            Operator::Plus if identity(lhs, rhs, 0, 0.0) => take(rhs),
+            Operator::Minus if identity(rhs, lhs, 0, 0.0) => take(lhs),
+            Operator::Multiply if identity(rhs, lhs, 1, 1.0) => take(lhs),
+            Operator::Multiply if identity(lhs, rhs, 1, 1.0) => take(rhs),
+            Operator::Divide if identity(rhs, lhs, 1, 1.0) => take(lhs),
+            _ => None,
+        }
+    }
+    fn node(&self, constant: Constant, span: Span) -> Option<Node<'a>> {
+        match constant {
+            Constant::Integer(integer) => Some(Node::Integer(Spanned::new(
+                self.names.intern(integer.to_string()),
+                span,
+            ))),
+            Constant::Float(float) => Some(Node::Float(Spanned::new(
+                self.names.intern(format!("{:?}", float)),
+                span,
+            ))),
+            Constant::Boolean(_) => None,
+        }
+    }
+    fn error(&self, kind: ErrorKind, span: Span) -> Box<Error<'a>> {
+        Box::new(Error::new(kind, span, self.source))
+    }
+}
+
+fn literal(node: &Node) -> Option<Constant> {
+    match node {
+        Node::Integer(integer) => integer.value.parse().ok().map(Constant::Integer),
+        Node::Float(float) => float.value.parse().ok().map(Constant::Float),
+        _ => None,
+    }
+}
+fn empty<'a>(span: Span) -> Node<'a> {
+    Node::Block(Spanned::new(vec![], span))
+}
+fn shadow<'a>(pattern: &Pattern<'a>, bindings: &mut Vec<(&'a str, Option<Numeric>)>) {
+    match pattern {
+        Pattern::Binding(name) => bindings.push((name.value, None)),
+        Pattern::Variant {
+            fields: Some(fields),
+            ..
+        } => fields
+            .value
+            .iter()
+            .for_each(|field| shadow(field, bindings)),
+        _ => {}
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ir::{dominators::Dominators, BlockId, Function, InstructionKind, Terminator};
+
+struct Loop {
+    header: BlockId,
+    body: HashSet<BlockId>,
+}
+
+pub fn hoist_loop_invariants(function: &mut Function) {
+    let dominators = Dominators::new(function);
+    let predecessors = function.predecessors();
+    let mut loops = find_loops(function, &dominators, &predecessors);
+    loops.sort_by_key(|natural| natural.body.len());
+    let mut definitions = HashMap::new();
+    for block in &function.blocks {
+        for phi in &block.phis {
+            definitions.insert(phi.value, block.id);
+        }
+        for instruction in &block.instructions {
+            definitions.insert(instruction.value, block.id);
+        }
+    }
+    for natural in loops {
+        let outside = predecessors[&natural.header]
+            .iter()
+            .filter(|predecessor| !natural.body.contains(predecessor))
+            .collect::<Vec<_>>();
+        let preheader = match outside.as_slice() {
+            [preheader]
+                if function.block(**preheader).map(|block| &block.terminator)
+                    == Some(&Terminator::Jump(natural.header)) =>
+            {
+                **preheader
+            }
+            _ => continue,
+        };
+        let mut hoisted = vec![];
+        for block in dominators.reverse_postorder() {
+            if !natural.body.contains(block) {
+                continue;
+            }
+            let Some(current) = function.block_mut(*block) else {
+                continue;
+            };
+            let mut index = 0;
+            while index < current.instructions.len() {
+                let instruction = &current.instructions[index];
+                let invariant = !instruction.has_side_effects()
+                    && !matches!(instruction.kind, InstructionKind::Closure { .. })
+                    && instruction.kind.operands().iter().all(|operand| {
+                        definitions
+                            .get(operand)
+                            .is_none_or(|defined| !natural.body.contains(defined))
+                    });
+                if invariant {
+                    let instruction = current.instructions.remove(index);
+                    definitions.insert(instruction.value, preheader);
+                    hoisted.push(instruction);
+                } else {
+                    index += 1;
+                }
+            }
+        }
+        if let Some(preheader) = function.block_mut(preheader) {
+            preheader.instructions.extend(hoisted);
+        }
+    }
+}
+
+fn find_loops(
+    function: &Function,
+    dominators: &Dominators,
+    predecessors: &HashMap<BlockId, Vec<BlockId>>,
+) -> Vec<Loop> {
+    let mut loops: Vec<Loop> = vec![];
+    for block in &function.blocks {
+        for header in block.terminator.successors() {
+            if !dominators.dominates(header, block.id) {
+                continue;
+            }
+            let mut body = HashSet::from([header]);
+            let mut worklist = vec![block.id];
+            while let Some(current) = worklist.pop() {
+                if body.insert(current) {
+                    worklist.extend(&predecessors[&current]);
+                }
+            }
+            match loops.iter_mut().find(|natural| natural.header == header) {
+                Some(natural) => natural.body.extend(body),
+                None => loops.push(Loop { header, body }),
+            }
+        }
+    }
+    loops
+}
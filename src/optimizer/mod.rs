@@ -0,0 +1,57 @@
+use crate::ir::{Function, Module};
+
+pub mod cse;
+pub mod dce;
+pub mod fold;
+pub mod licm;
+pub mod propagate;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OptimizationLevel {
+    None,
+    Basic,
+    Full,
+}
+
+pub type Pass = fn(&mut Function);
+
+impl OptimizationLevel {
+    pub fn from_flag(flag: &str) -> Option<Self> {
+        match flag {
+            "-O0" => Some(OptimizationLevel::None),
+            "-O1" => Some(OptimizationLevel::Basic),
+            "-O2" => Some(OptimizationLevel::Full),
+            _ => None,
+        }
+    }
+    pub fn passes(self) -> &'static [(&'static str, Pass)] {
+        match self {
+            OptimizationLevel::None => &[],
+            OptimizationLevel::Basic => &[
+                ("propagate", propagate::propagate),
+                ("dce", dce::eliminate_dead_code),
+            ],
+            OptimizationLevel::Full => &[
+                ("propagate", propagate::propagate),
+                ("cse", cse::eliminate_common_subexpressions),
+                ("licm", licm::hoist_loop_invariants),
+                ("propagate", propagate::propagate),
+                ("dce", dce::eliminate_dead_code),
+            ],
+        }
+    }
+}
+
+pub fn optimize(
+    module: &mut Module,
+    level: OptimizationLevel,
+    mut observe: impl FnMut(&str, &Module),
+) {
+    for (name, pass) in level.passes() {
+        module
+            .functions
+            .iter_mut()
+            .for_each(|function| pass(function));
+        observe(name, module);
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ir::{
+    transform::{merge_blocks, remove_trivial_phis, remove_unreachable_blocks, replace_uses},
+    BinaryOperator, Constant, Function, InstructionKind, Terminator,
+};
+
+pub fn propagate(function: &mut Function) {
+    loop {
+        let mut changed = false;
+        let mut constants = HashMap::new();
+        let mut aggregates: HashMap<_, (Option<usize>, Vec<_>)> = HashMap::new();
+        let mut replacements = HashMap::new();
+        let mut replaced = HashSet::new();
+        for block in &mut function.blocks {
+            for instruction in &mut block.instructions {
+                let folded = match &instruction.kind {
+                    InstructionKind::Binary { operator, lhs, rhs } => {
+                        match (constants.get(lhs), constants.get(rhs)) {
+                            (Some(lhs), Some(rhs)) => binary(*operator, lhs, rhs),
+                            _ => None,
+                        }
+                    }
+                    InstructionKind::Negate(value) => match constants.get(value) {
+                        Some(Constant::Int(integer)) => integer.checked_neg().map(Constant::Int),
+                        Some(Constant::Float(float)) => Some(Constant::Float(-float)),
+                        _ => None,
+                    },
+                    InstructionKind::Tag(value) => match aggregates.get(value) {
+                        Some((Some(tag), _)) => Some(Constant::Int(*tag as i64)),
+                        _ => None,
+                    },
+                    InstructionKind::Field { value, index } => {
+                        if let Some((Some(_), fields)) = aggregates.get(value) {
+                            if let Some(field) = fields.get(*index) {
+                                replacements.insert(instruction.value, *field);
+                                replaced.insert(instruction.value);
+                            }
+                        }
+                        None
+                    }
+                    InstructionKind::Extract { array, index } => {
+                        if let (Some((None, elements)), Some(&Constant::Int(position))) =
+                            (aggregates.get(array), constants.get(index))
+                        {
+                            if let Some(element) = usize::try_from(position)
+                                .ok()
+                                .and_then(|index| elements.get(index))
+                            {
+                                replacements.insert(instruction.value, *element);
+                                replaced.insert(instruction.value);
+                            }
+                        }
+                        None
+                    }
+                    _ => None,
+                };
+                if let Some(constant) = folded {
+                    instruction.kind = InstructionKind::Constant(constant);
+                    changed = true;
+                }
+                match &instruction.kind {
+                    InstructionKind::Constant(constant) => {
+                        constants.insert(instruction.value, constant.clone());
+                    }
+                    InstructionKind::Variant { tag, fields, .. } => {
+                        aggregates.insert(instruction.value, (Some(*tag), fields.clone()));
+                    }
+                    InstructionKind::Array(elements) => {
+                        aggregates.insert(instruction.value, (None, elements.clone()));
+                    }
+                    _ => {}
+                }
+            }
+            if let Terminator::Branch {
+                condition,
+                consequent,
+                alternative,
+            } = block.terminator
+            {
+                if let Some(Constant::Bool(condition)) = constants.get(&condition) {
+                    let target = if *condition { consequent } else { alternative };
+                    block.terminator = Terminator::Jump(target);
+                    changed = true;
+                }
+            }
+        }
+        if !replaced.is_empty() {
+            for block in &mut function.blocks {
+                block
+                    .instructions
+                    .retain(|instruction| !replaced.contains(&instruction.value));
+            }
+            replace_uses(function, &replacements);
+            changed = true;
+        }
+        let phis = count_phis(function);
+        remove_unreachable_blocks(function);
+        remove_trivial_phis(function);
+        changed |= merge_blocks(function);
+        if !changed && phis == count_phis(function) {
+            break;
+        }
+    }
+}
+
+fn count_phis(function: &Function) -> usize {
+    function.blocks.iter().map(|block| block.phis.len()).sum()
+}
+fn binary<'a>(
+    operator: BinaryOperator,
+    lhs: &Constant<'a>,
+    rhs: &Constant<'a>,
+) -> Option<Constant<'a>> {
+    let constant = match (operator, lhs, rhs) {
+        (BinaryOperator::Equal, lhs, rhs) => Constant::Bool(lhs == rhs),
+        (operator, Constant::Int(lhs), Constant::Int(rhs)) => match operator {
+            BinaryOperator::Add => Constant::Int(lhs.checked_add(*rhs)?),
+            BinaryOperator::Subtract => Constant::Int(lhs.checked_sub(*rhs)?),
+            BinaryOperator::Multiply => Constant::Int(lhs.checked_mul(*rhs)?),
+            BinaryOperator::Divide => Constant::Int(lhs.checked_div(*rhs)?),
+            BinaryOperator::LessThen => Constant::Bool(lhs < rhs),
+            BinaryOperator::GreaterThen => Constant::Bool(lhs > rhs),
+            BinaryOperator::LessThenEqual => Constant::Bool(lhs <= rhs),
+            BinaryOperator::GreaterThenEqual => Constant::Bool(lhs >= rhs),
+            BinaryOperator::Equal => unreachable!(),
+        },
+        (operator, Constant::Float(lhs), Constant::Float(rhs)) => match operator {
+            BinaryOperator::Add => Constant::Float(lhs + rhs),
+            BinaryOperator::Subtract => Constant::Float(lhs - rhs),
+            BinaryOperator::Multiply => Constant::Float(lhs * rhs),
+            BinaryOperator::Divide => Constant::Float(lhs / rhs),
+            BinaryOperator::LessThen => Constant::Bool(lhs < rhs),
+            BinaryOperator::GreaterThen => Constant::Bool(lhs > rhs),
+            BinaryOperator::LessThenEqual => Constant::Bool(lhs <= rhs),
+            BinaryOperator::GreaterThenEqual => Constant::Bool(lhs >= rhs),
+            BinaryOperator::Equal => unreachable!(),
+        },
+        (BinaryOperator::Add, Constant::String(lhs), Constant::String(rhs)) => {
+            Constant::String(format!("{}{}", lhs, rhs))
+        }
+        _ => return None,
+    };
+    Some(constant)
+}
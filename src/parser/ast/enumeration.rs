@@ -0,0 +1,23 @@
+use super::types::TypeExpr;
+
+#[derive(Clone, Debug)]
+pub struct Variant<'a> {
+    pub name: &'a str,
+    pub fields: Option<Vec<TypeExpr<'a>>>,
+}
+#[derive(Clone, Debug)]
+pub struct Enum<'a> {
+    pub name: &'a str,
+    pub variants: Vec<Variant<'a>>,
+}
+
+impl<'a> Enum<'a> {
+    pub fn variant(&self, name: &str) -> Option<&Variant<'a>> {
+        self.variants.iter().find(|variant| variant.name == name)
+    }
+}
+impl<'a> Variant<'a> {
+    pub fn arity(&self) -> usize {
+        self.fields.as_ref().map_or(0, |fields| fields.len())
+    }
+}
@@ -1,8 +1,12 @@
-use crate::{common::span::Span, lexer::token::TokenKind};
+use std::fmt::{self, Display};
 
-use super::{calculate_span::CalculateSpan, node::Node, spanned::Spanned};
+use crate::{common::span::Span, parser::operator::symbol};
 
-#[derive(Clone, Copy, Debug)]
+use super::{
+    calculate_span::CalculateSpan, node::Node, pattern::Pattern, spanned::Spanned, types::TypeExpr,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Operator {
     Plus,
     Minus,
@@ -16,21 +20,9 @@ pub enum Operator {
     GreaterThenEqual,
 }
 
-impl From<TokenKind> for Operator {
-    fn from(kind: TokenKind) -> Self {
-        match kind {
-            TokenKind::Plus => Operator::Plus,
-            TokenKind::Minus => Operator::Minus,
-            TokenKind::Multiply => Operator::Multiply,
-            TokenKind::Divide => Operator::Divide,
-            TokenKind::Assignment => Operator::Assignment,
-            TokenKind::Equal => Operator::Equal,
-            TokenKind::LessThen => Operator::LessThen,
-            TokenKind::GreaterThen => Operator::GreaterThen,
-            TokenKind::LessThenEqual => Operator::LessThenEqual,
-            TokenKind::GreaterThenEqual => Operator::GreaterThenEqual,
-            _ => unreachable!(),
-        }
+impl Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", symbol(*self))
     }
 }
 #[derive(Clone, Debug)]
@@ -45,9 +37,47 @@ pub enum Expression<'a> {
         value: Box<Node<'a>>,
     },
     Call {
-        name: Spanned<&'a str>,
+        callee: Box<Node<'a>>,
+        generics: Option<Spanned<Vec<TypeExpr<'a>>>>,
         arguments: Spanned<Vec<Node<'a>>>,
     },
+    Member {
+        target: Box<Node<'a>>,
+        name: Spanned<&'a str>,
+    },
+    Lambda {
+        parameters: Spanned<Vec<Parameter<'a>>>,
+        body: Box<Node<'a>>,
+    },
+    Index {
+        target: Box<Node<'a>>,
+        index: Spanned<Box<Node<'a>>>,
+    },
+    Match {
+        keyword: Span,
+        value: Box<Node<'a>>,
+        arms: Spanned<Vec<MatchArm<'a>>>,
+    },
+    Ternary {
+        test: Box<Node<'a>>,
+        consequent: Box<Node<'a>>,
+        alternative: Box<Node<'a>>,
+    },
+    Range {
+        start: Box<Node<'a>>,
+        end: Box<Node<'a>>,
+        inclusive: bool,
+    },
+}
+#[derive(Clone, Debug)]
+pub struct Parameter<'a> {
+    pub name: Spanned<&'a str>,
+    pub parameter_type: Option<TypeExpr<'a>>,
+}
+#[derive(Clone, Debug)]
+pub struct MatchArm<'a> {
+    pub pattern: Pattern<'a>,
+    pub body: Box<Node<'a>>,
 }
 impl<'a> CalculateSpan for Expression<'a> {
     fn calculate_span(&self) -> Span {
@@ -58,7 +88,28 @@ impl<'a> CalculateSpan for Expression<'a> {
             Expression::Prefix { operator, value } => {
                 Span::new(operator.span.start, value.calculate_span().end)
             }
-            Expression::Call { name, arguments } => Span::new(name.span.start, arguments.span.end),
+            Expression::Call {
+                callee, arguments, ..
+            } => Span::new(callee.calculate_span().start, arguments.span.end),
+            Expression::Member { target, name } => {
+                Span::new(target.calculate_span().start, name.span.end)
+            }
+            Expression::Lambda { parameters, body } => {
+                Span::new(parameters.span.start, body.calculate_span().end)
+            }
+            Expression::Index { target, index } => {
+                Span::new(target.calculate_span().start, index.span.end)
+            }
+            Expression::Match { keyword, arms, .. } => Span::new(keyword.start, arms.span.end),
+            Expression::Ternary {
+                test, alternative, ..
+            } => Span::new(
+                test.calculate_span().start,
+                alternative.calculate_span().end,
+            ),
+            Expression::Range { start, end, .. } => {
+                Span::new(start.calculate_span().start, end.calculate_span().end)
+            }
         }
     }
 }
@@ -1,14 +1,21 @@
-use super::node::Node;
+use crate::common::{source::Source, span::Span};
+
+use super::{node::Node, spanned::Spanned, types::TypeExpr};
 
 #[derive(Clone, Debug)]
 pub struct Argument<'a> {
     pub name: &'a str,
-    pub argument_type: &'a str,
+    pub argument_type: TypeExpr<'a>,
+    pub span: Span,
 }
 #[derive(Clone, Debug)]
 pub struct Function<'a> {
+    pub public: bool,
     pub name: &'a str,
+    pub name_span: Span,
+    pub generics: Vec<Spanned<&'a str>>,
     pub arguments: Vec<Argument<'a>>,
     pub body: Node<'a>,
-    pub return_type: Option<&'a str>,
+    pub return_type: Option<TypeExpr<'a>>,
+    pub source: Source<'a>,
 }
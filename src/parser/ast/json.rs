@@ -0,0 +1,323 @@
+use crate::common::json::{Json, ToJson};
+
+use super::{
+    calculate_span::CalculateSpan,
+    enumeration::{Enum, Variant},
+    expression::{Expression, MatchArm, Operator, Parameter},
+    function::{Argument, Function},
+    node::Node,
+    pattern::Pattern,
+    statement::Statement,
+    types::TypeExpr,
+    Import, Program,
+};
+
+impl<'a> ToJson for Program<'a> {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("path", self.path.to_json()),
+            ("module", self.module.to_json()),
+            ("imports", self.imports.to_json()),
+            ("functions", self.functions.to_json()),
+            ("enums", self.enums.to_json()),
+        ])
+    }
+}
+impl<'a> ToJson for Import<'a> {
+    fn to_json(&self) -> Json {
+        Json::object([("path", self.path.to_json())])
+    }
+}
+impl<'a> ToJson for Enum<'a> {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("name", self.name.to_json()),
+            ("variants", self.variants.to_json()),
+        ])
+    }
+}
+impl<'a> ToJson for Variant<'a> {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("name", self.name.to_json()),
+            ("fields", self.fields.to_json()),
+        ])
+    }
+}
+impl<'a> ToJson for Function<'a> {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("public", self.public.to_json()),
+            ("name", self.name.to_json()),
+            ("name_span", self.name_span.to_json()),
+            ("generics", self.generics.to_json()),
+            ("arguments", self.arguments.to_json()),
+            ("return_type", self.return_type.to_json()),
+            ("body", self.body.to_json()),
+        ])
+    }
+}
+impl<'a> ToJson for Argument<'a> {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("name", self.name.to_json()),
+            ("type", self.argument_type.to_json()),
+            ("span", self.span.to_json()),
+        ])
+    }
+}
+impl<'a> ToJson for Parameter<'a> {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("name", self.name.to_json()),
+            ("type", self.parameter_type.to_json()),
+        ])
+    }
+}
+impl<'a> ToJson for MatchArm<'a> {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("pattern", self.pattern.to_json()),
+            ("body", self.body.to_json()),
+        ])
+    }
+}
+impl ToJson for Operator {
+    fn to_json(&self) -> Json {
+        Json::String(format!("{:?}", self))
+    }
+}
+
+fn tagged<const N: usize>(kind: &str, fields: [(&str, Json); N], span: Json) -> Json {
+    let mut object = vec![("kind".to_string(), kind.to_json())];
+    object.extend(
+        fields
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value)),
+    );
+    object.push(("span".to_string(), span));
+    Json::Object(object)
+}
+
+impl<'a> ToJson for Node<'a> {
+    fn to_json(&self) -> Json {
+        let span = self.calculate_span().to_json();
+        match self {
+            Node::Integer(literal) => tagged("Integer", [("value", literal.value.to_json())], span),
+            Node::Float(literal) => tagged("Float", [("value", literal.value.to_json())], span),
+            Node::String(literal) => tagged("String", [("value", literal.value.to_json())], span),
+            Node::Identifier(identifier) => {
+                tagged("Identifier", [("name", identifier.value.to_json())], span)
+            }
+            Node::Block(block) => tagged("Block", [("statements", block.value.to_json())], span),
+            Node::Array(array) => tagged("Array", [("elements", array.value.to_json())], span),
+            Node::Expression(expression) => expression.to_json(),
+            Node::Statement(statement) => statement.to_json(),
+        }
+    }
+}
+impl<'a> ToJson for Expression<'a> {
+    fn to_json(&self) -> Json {
+        let span = self.calculate_span().to_json();
+        match self {
+            Expression::Infix { operator, lhs, rhs } => tagged(
+                "Infix",
+                [
+                    ("operator", operator.to_json()),
+                    ("lhs", lhs.to_json()),
+                    ("rhs", rhs.to_json()),
+                ],
+                span,
+            ),
+            Expression::Prefix { operator, value } => tagged(
+                "Prefix",
+                [
+                    ("operator", operator.value.to_json()),
+                    ("value", value.to_json()),
+                ],
+                span,
+            ),
+            Expression::Call {
+                callee,
+                generics,
+                arguments,
+            } => tagged(
+                "Call",
+                [
+                    ("callee", callee.to_json()),
+                    ("generics", generics.to_json()),
+                    ("arguments", arguments.value.to_json()),
+                ],
+                span,
+            ),
+            Expression::Member { target, name } => tagged(
+                "Member",
+                [("target", target.to_json()), ("name", name.to_json())],
+                span,
+            ),
+            Expression::Lambda { parameters, body } => tagged(
+                "Lambda",
+                [
+                    ("parameters", parameters.value.to_json()),
+                    ("body", body.to_json()),
+                ],
+                span,
+            ),
+            Expression::Index { target, index } => tagged(
+                "Index",
+                [
+                    ("target", target.to_json()),
+                    ("index", index.value.to_json()),
+                ],
+                span,
+            ),
+            Expression::Match {
+                keyword,
+                value,
+                arms,
+            } => tagged(
+                "Match",
+                [
+                    ("keyword", keyword.to_json()),
+                    ("value", value.to_json()),
+                    ("arms", arms.value.to_json()),
+                ],
+                span,
+            ),
+            Expression::Ternary {
+                test,
+                consequent,
+                alternative,
+            } => tagged(
+                "Ternary",
+                [
+                    ("test", test.to_json()),
+                    ("consequent", consequent.to_json()),
+                    ("alternative", alternative.to_json()),
+                ],
+                span,
+            ),
+            Expression::Range {
+                start,
+                end,
+                inclusive,
+            } => tagged(
+                "Range",
+                [
+                    ("start", start.to_json()),
+                    ("end", end.to_json()),
+                    ("inclusive", inclusive.to_json()),
+                ],
+                span,
+            ),
+        }
+    }
+}
+impl<'a> ToJson for Statement<'a> {
+    fn to_json(&self) -> Json {
+        let span = self.calculate_span().to_json();
+        match self {
+            Statement::While(while_statement) => tagged(
+                "While",
+                [
+                    ("test", while_statement.value.test.to_json()),
+                    ("body", while_statement.value.body.to_json()),
+                ],
+                span,
+            ),
+            Statement::For(for_statement) => tagged(
+                "For",
+                [
+                    ("binding", for_statement.value.binding.to_json()),
+                    ("iterable", for_statement.value.iterable.to_json()),
+                    ("body", for_statement.value.body.to_json()),
+                ],
+                span,
+            ),
+            Statement::If(if_statement) => tagged(
+                "If",
+                [
+                    ("test", if_statement.value.test.to_json()),
+                    ("consequent", if_statement.value.consequent.to_json()),
+                    ("alternative", if_statement.value.alternative.to_json()),
+                ],
+                span,
+            ),
+            Statement::Let(let_statement) => tagged(
+                "Let",
+                [
+                    ("mutable", let_statement.value.mutable.to_json()),
+                    ("name", let_statement.value.name.to_json()),
+                    ("type", let_statement.value.value_type.to_json()),
+                    ("init", let_statement.value.init.to_json()),
+                ],
+                span,
+            ),
+            Statement::Return(return_statement) => tagged(
+                "Return",
+                [("value", return_statement.value.value.to_json())],
+                span,
+            ),
+        }
+    }
+}
+impl<'a> ToJson for Pattern<'a> {
+    fn to_json(&self) -> Json {
+        let span = self.calculate_span().to_json();
+        match self {
+            Pattern::Wildcard(_) => tagged("Wildcard", [], span),
+            Pattern::Integer(literal) => {
+                tagged("Integer", [("value", literal.value.to_json())], span)
+            }
+            Pattern::Float(literal) => tagged("Float", [("value", literal.value.to_json())], span),
+            Pattern::Binding(binding) => {
+                tagged("Binding", [("name", binding.value.to_json())], span)
+            }
+            Pattern::Variant { path, fields } => tagged(
+                "Variant",
+                [("path", path.to_json()), ("fields", fields.to_json())],
+                span,
+            ),
+        }
+    }
+}
+impl<'a> ToJson for TypeExpr<'a> {
+    fn to_json(&self) -> Json {
+        let span = self.calculate_span().to_json();
+        match self {
+            TypeExpr::Path { path, generics } => tagged(
+                "Path",
+                [("path", path.to_json()), ("generics", generics.to_json())],
+                span,
+            ),
+            TypeExpr::Unit(_) => tagged("Unit", [], span),
+            TypeExpr::Tuple(elements) => {
+                tagged("Tuple", [("elements", elements.value.to_json())], span)
+            }
+            TypeExpr::Array {
+                element, length, ..
+            } => tagged(
+                "Array",
+                [("element", element.to_json()), ("length", length.to_json())],
+                span,
+            ),
+            TypeExpr::Function {
+                arguments,
+                return_type,
+                ..
+            } => tagged(
+                "Function",
+                [
+                    ("arguments", arguments.value.to_json()),
+                    ("return_type", return_type.to_json()),
+                ],
+                span,
+            ),
+            TypeExpr::Reference { mutable, inner, .. } => tagged(
+                "Reference",
+                [("mutable", mutable.to_json()), ("inner", inner.to_json())],
+                span,
+            ),
+        }
+    }
+}
@@ -1,14 +1,34 @@
-use self::{function::Function, node::Node};
+use self::{enumeration::Enum, function::Function, node::Node, spanned::Spanned};
 
 pub mod calculate_span;
+pub mod enumeration;
 pub mod expression;
 pub mod function;
+pub mod json;
 pub mod node;
+pub mod pattern;
 pub mod spanned;
 pub mod statement;
+pub mod types;
 pub type Block<'a> = Vec<Node<'a>>;
 #[derive(Clone, Debug)]
+pub struct Import<'a> {
+    pub path: Spanned<&'a str>,
+}
+#[derive(Clone, Debug)]
 pub struct Program<'a> {
     pub path: &'a str,
+    pub module: Option<Spanned<&'a str>>,
+    pub imports: Vec<Import<'a>>,
     pub functions: Vec<Function<'a>>,
+    pub enums: Vec<Enum<'a>>,
+}
+pub fn path_segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split("::").map(str::trim)
+}
+pub fn variant_path(path: &str) -> (&str, &str) {
+    match path.rsplit_once("::") {
+        Some((enumeration, variant)) => (enumeration.trim(), variant.trim()),
+        None => ("", path.trim()),
+    }
 }
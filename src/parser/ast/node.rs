@@ -9,8 +9,10 @@ use super::{
 pub enum Node<'a> {
     Integer(Spanned<&'a str>),
     Float(Spanned<&'a str>),
+    String(Spanned<&'a str>),
     Identifier(Spanned<&'a str>),
     Block(Spanned<Block<'a>>),
+    Array(Spanned<Vec<Node<'a>>>),
     Expression(Expression<'a>),
     Statement(Statement<'a>),
 }
@@ -19,8 +21,10 @@ impl<'a> CalculateSpan for Node<'a> {
         match self {
             Node::Integer(integer) => integer.span,
             Node::Float(float) => float.span,
+            Node::String(string) => string.span,
             Node::Identifier(id) => id.span,
             Node::Block(block) => block.span,
+            Node::Array(array) => array.span,
             Node::Expression(expression) => expression.calculate_span(),
             Node::Statement(statement) => statement.calculate_span(),
         }
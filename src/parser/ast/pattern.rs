@@ -0,0 +1,29 @@
+use crate::common::span::Span;
+
+use super::{calculate_span::CalculateSpan, spanned::Spanned};
+
+#[derive(Clone, Debug)]
+pub enum Pattern<'a> {
+    Wildcard(Span),
+    Integer(Spanned<&'a str>),
+    Float(Spanned<&'a str>),
+    Binding(Spanned<&'a str>),
+    Variant {
+        path: Spanned<&'a str>,
+        fields: Option<Spanned<Vec<Pattern<'a>>>>,
+    },
+}
+impl<'a> CalculateSpan for Pattern<'a> {
+    fn calculate_span(&self) -> Span {
+        match self {
+            Pattern::Wildcard(span) => *span,
+            Pattern::Integer(integer) => integer.span,
+            Pattern::Float(float) => float.span,
+            Pattern::Binding(binding) => binding.span,
+            Pattern::Variant { path, fields } => match fields {
+                Some(fields) => Span::new(path.span.start, fields.span.end),
+                None => path.span,
+            },
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use std::fmt::Debug;
 
-use crate::common::span::Span;
+use crate::common::{
+    json::{Json, ToJson},
+    span::Span,
+};
 
 #[derive(Clone, Debug)]
 pub struct Spanned<T: Clone + Debug> {
@@ -13,3 +16,11 @@ impl<T: Clone + Debug> Spanned<T> {
         Self { value, span }
     }
 }
+impl<T: Clone + Debug + ToJson> ToJson for Spanned<T> {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("value", self.value.to_json()),
+            ("span", self.span.to_json()),
+        ])
+    }
+}
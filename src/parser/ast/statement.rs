@@ -1,10 +1,11 @@
 use crate::common::span::Span;
 
-use super::{calculate_span::CalculateSpan, node::Node, spanned::Spanned};
+use super::{calculate_span::CalculateSpan, node::Node, spanned::Spanned, types::TypeExpr};
 
 #[derive(Clone, Debug)]
 pub enum Statement<'a> {
     While(Spanned<WhileStatement<'a>>),
+    For(Spanned<ForStatement<'a>>),
     If(Spanned<IfStatement<'a>>),
     Let(Spanned<LetStatement<'a>>),
     Return(Spanned<ReturnStatement<'a>>),
@@ -15,6 +16,12 @@ pub struct WhileStatement<'a> {
     pub body: Box<Node<'a>>,
 }
 #[derive(Clone, Debug)]
+pub struct ForStatement<'a> {
+    pub binding: Spanned<&'a str>,
+    pub iterable: Box<Node<'a>>,
+    pub body: Box<Node<'a>>,
+}
+#[derive(Clone, Debug)]
 pub struct IfStatement<'a> {
     pub test: Box<Node<'a>>,
     pub consequent: Box<Node<'a>>,
@@ -24,7 +31,7 @@ pub struct IfStatement<'a> {
 pub struct LetStatement<'a> {
     pub mutable: bool,
     pub name: &'a str,
-    pub value_type: Option<&'a str>,
+    pub value_type: Option<TypeExpr<'a>>,
     pub init: Option<Box<Node<'a>>>,
 }
 #[derive(Clone, Debug)]
@@ -35,6 +42,7 @@ impl<'a> CalculateSpan for Statement<'a> {
     fn calculate_span(&self) -> Span {
         match self {
             Statement::While(while_statement) => while_statement.span,
+            Statement::For(for_statement) => for_statement.span,
             Statement::If(if_statement) => if_statement.span,
             Statement::Let(let_statement) => let_statement.span,
             Statement::Return(return_statement) => return_statement.span,
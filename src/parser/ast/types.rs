@@ -0,0 +1,113 @@
+use std::fmt::{self, Display};
+
+use crate::common::span::Span;
+
+use super::{calculate_span::CalculateSpan, spanned::Spanned};
+
+#[derive(Clone, Debug)]
+pub enum TypeExpr<'a> {
+    Path {
+        path: Spanned<&'a str>,
+        generics: Option<Spanned<Vec<TypeExpr<'a>>>>,
+    },
+    Unit(Span),
+    Tuple(Spanned<Vec<TypeExpr<'a>>>),
+    Array {
+        element: Box<TypeExpr<'a>>,
+        length: Option<Spanned<&'a str>>,
+        span: Span,
+    },
+    Function {
+        keyword: Span,
+        arguments: Spanned<Vec<TypeExpr<'a>>>,
+        return_type: Option<Box<TypeExpr<'a>>>,
+    },
+    Reference {
+        ampersand: Span,
+        mutable: bool,
+        inner: Box<TypeExpr<'a>>,
+    },
+}
+impl<'a> CalculateSpan for TypeExpr<'a> {
+    fn calculate_span(&self) -> Span {
+        match self {
+            TypeExpr::Path { path, generics } => match generics {
+                Some(generics) => Span::new(path.span.start, generics.span.end),
+                None => path.span,
+            },
+            TypeExpr::Unit(span) => *span,
+            TypeExpr::Tuple(elements) => elements.span,
+            TypeExpr::Array { span, .. } => *span,
+            TypeExpr::Function {
+                keyword,
+                arguments,
+                return_type,
+            } => match return_type {
+                Some(return_type) => Span::new(keyword.start, return_type.calculate_span().end),
+                None => Span::new(keyword.start, arguments.span.end),
+            },
+            TypeExpr::Reference {
+                ampersand, inner, ..
+            } => Span::new(ampersand.start, inner.calculate_span().end),
+        }
+    }
+}
+fn list(f: &mut fmt::Formatter<'_>, types: &[TypeExpr]) -> fmt::Result {
+    for (index, element) in types.iter().enumerate() {
+        if index != 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", element)?;
+    }
+    Ok(())
+}
+impl<'a> Display for TypeExpr<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeExpr::Path { path, generics } => {
+                write!(f, "{}", path.value)?;
+                if let Some(generics) = generics {
+                    write!(f, "<")?;
+                    list(f, &generics.value)?;
+                    write!(f, ">")?;
+                }
+                Ok(())
+            }
+            TypeExpr::Unit(_) => write!(f, "()"),
+            TypeExpr::Tuple(elements) => {
+                write!(f, "(")?;
+                list(f, &elements.value)?;
+                if elements.value.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            TypeExpr::Array {
+                element, length, ..
+            } => match length {
+                Some(length) => write!(f, "[{}; {}]", element, length.value),
+                None => write!(f, "[{}]", element),
+            },
+            TypeExpr::Function {
+                arguments,
+                return_type,
+                ..
+            } => {
+                write!(f, "fn(")?;
+                list(f, &arguments.value)?;
+                write!(f, ")")?;
+                match return_type {
+                    Some(return_type) => write!(f, " -> {}", return_type),
+                    None => Ok(()),
+                }
+            }
+            TypeExpr::Reference { mutable, inner, .. } => {
+                if *mutable {
+                    write!(f, "&mut {}", inner)
+                } else {
+                    write!(f, "&{}", inner)
+                }
+            }
+        }
+    }
+}
@@ -10,6 +10,7 @@ use crate::{
         token::{Token, TokenKind},
         Lexer,
     },
+    syntax::builder::Builder,
 };
 
 #[derive(Clone, Debug)]
@@ -17,6 +18,8 @@ pub struct Cursor<'a> {
     pub lexer: Peekable<Lexer<'a>>,
     pub source: Source<'a>,
     pub current_span: Option<Span>,
+    pub previous: Option<Token<'a>>,
+    pub syntax: Option<Builder<'a>>,
 }
 
 impl<'a> Cursor<'a> {
@@ -25,26 +28,35 @@ impl<'a> Cursor<'a> {
             lexer,
             source,
             current_span: None,
+            previous: None,
+            syntax: None,
         }
     }
+    pub fn with_syntax(mut self) -> Self {
+        self.syntax = Some(Builder::new(self.source.code));
+        self
+    }
     pub fn next_token(&mut self) -> Result<'a, Token<'a>> {
         let token = self.lexer.next();
         match token {
             Some(token) => match token {
                 Ok(token) => {
                     self.update_span(token.chunk.span);
+                    self.previous = Some(token);
+                    if let Some(syntax) = &mut self.syntax {
+                        syntax.token(token.kind.into(), token.chunk.span);
+                    }
                     Ok(token)
                 }
                 Err(err) => Err(err),
             },
-            None => Err(Box::new(Error::new(
-                ErrorKind::UnexpectedEndOfInput,
-                self.current_span.expect("Empty input"),
-                self.source,
-            ))),
+            None => Err(end_of_input(self.source, self.current_span)),
         }
     }
     #[inline]
+    pub fn is_at_end(&mut self) -> bool {
+        self.lexer.peek().is_none()
+    }
     pub fn test(&mut self, kind: TokenKind) -> bool {
         match self.peek() {
             Ok(token) => token.kind == kind,
@@ -75,11 +87,7 @@ impl<'a> Cursor<'a> {
                 Ok(token) => Ok(token),
                 Err(err) => Err(err.to_owned()),
             },
-            None => Err(Box::new(Error::new(
-                ErrorKind::UnexpectedEndOfInput,
-                self.current_span.expect("Empty input"),
-                self.source,
-            ))),
+            None => Err(end_of_input(self.source, self.current_span)),
         }
     }
     #[inline]
@@ -119,3 +127,12 @@ impl<'a> Cursor<'a> {
         }
     }
 }
+
+fn end_of_input<'a>(source: Source<'a>, span: Option<Span>) -> Box<Error<'a>> {
+    let end = source.code.chars().count();
+    Box::new(Error::new(
+        ErrorKind::UnexpectedEndOfInput,
+        span.unwrap_or(Span::new(end, end)),
+        source,
+    ))
+}
@@ -4,148 +4,320 @@ use crate::{
         span::Span,
     },
     lexer::token::TokenKind,
+    parentheses,
+    syntax::{builder::Checkpoint, kind::SyntaxKind},
 };
 
 use super::{
     ast::{
-        expression::{Expression, Operator},
+        expression::{Expression, MatchArm, Parameter},
         node::Node,
         spanned::Spanned,
     },
+    operator::{Fixity, OperatorEntry},
     Parser,
 };
 
 impl<'a> Parser<'a> {
     pub(super) fn parse_expression(&mut self, minimum_binding_power: u8) -> Result<'a, Node<'a>> {
+        self.nested(|parser| parser.expression(minimum_binding_power))
+    }
+    fn expression(&mut self, minimum_binding_power: u8) -> Result<'a, Node<'a>> {
+        let checkpoint = self.checkpoint();
         let lhs_token = self.cursor.next_token()?;
+        let mut generics = None;
         let mut lhs = match lhs_token.kind {
             TokenKind::Integer => {
+                self.wrap(checkpoint, SyntaxKind::Literal);
                 Node::Integer(Spanned::new(lhs_token.chunk.data, lhs_token.chunk.span))
             }
             TokenKind::Float => {
+                self.wrap(checkpoint, SyntaxKind::Literal);
                 Node::Float(Spanned::new(lhs_token.chunk.data, lhs_token.chunk.span))
             }
+            TokenKind::String => {
+                self.wrap(checkpoint, SyntaxKind::Literal);
+                Node::String(Spanned::new(lhs_token.chunk.data, lhs_token.chunk.span))
+            }
             TokenKind::Identifier => {
-                Node::Identifier(Spanned::new(lhs_token.chunk.data, lhs_token.chunk.span))
+                let path = self.parse_path(lhs_token)?;
+                self.wrap(checkpoint, SyntaxKind::Path);
+                if self.cursor.optional(TokenKind::DoubleColon)? {
+                    let types_checkpoint = self.checkpoint();
+                    let lt = self.cursor.consume(TokenKind::LessThen)?;
+                    let types = self.type_list()?;
+                    let gt = self.cursor.consume(TokenKind::GreaterThen)?;
+                    self.wrap(types_checkpoint, SyntaxKind::TypeArguments);
+                    generics = Some(Spanned::new(
+                        types,
+                        Span::new(lt.chunk.span.start, gt.chunk.span.end),
+                    ));
+                    if !self.cursor.test(TokenKind::LeftParenthesis) {
+                        self.cursor.consume(TokenKind::LeftParenthesis)?;
+                    }
+                }
+                Node::Identifier(path)
             }
-            TokenKind::LeftParenthesis => {
-                let expression = self.parse_expression(0)?;
-                self.cursor.consume(TokenKind::RightParenthesis)?;
-                expression
+            TokenKind::Match => {
+                let node = self.parse_match(lhs_token.chunk.span)?;
+                self.wrap(checkpoint, SyntaxKind::MatchExpression);
+                node
             }
-            TokenKind::Plus | TokenKind::Minus => {
-                let operator = Operator::from(lhs_token.kind);
-                let ((), right_binding_power) = Self::prefix_binding_power(operator).unwrap();
-                let rhs = self.parse_expression(right_binding_power)?;
-                Node::Expression(Expression::Prefix {
-                    operator: Spanned::new(operator, lhs_token.chunk.span),
-                    value: Box::new(rhs),
-                })
+            TokenKind::Pipe => {
+                let node = self.parse_lambda(lhs_token.chunk.span, checkpoint)?;
+                self.wrap(checkpoint, SyntaxKind::Lambda);
+                node
             }
-            _ => {
-                return Err(Box::new(Error::new(
-                    ErrorKind::UnexpectedToken {
-                        expected: vec![
+            TokenKind::LeftSquareBracket => {
+                let elements = if self.cursor.test(TokenKind::RightSquareBracket) {
+                    vec![]
+                } else {
+                    self.arguments(
+                        |parser| parser.parse_expression(0),
+                        vec![
+                            TokenKind::LeftParenthesis,
+                            TokenKind::LeftSquareBracket,
                             TokenKind::Identifier,
-                            TokenKind::Integer,
                             TokenKind::Float,
-                            TokenKind::LeftParenthesis,
-                            TokenKind::Plus,
-                            TokenKind::Minus,
+                            TokenKind::Integer,
                         ],
-                        received: lhs_token.kind,
-                    },
-                    lhs_token.chunk.span,
-                    self.source,
-                )))
+                    )?
+                };
+                let rsb = self.cursor.consume(TokenKind::RightSquareBracket)?;
+                self.wrap(checkpoint, SyntaxKind::Array);
+                Node::Array(Spanned::new(
+                    elements,
+                    Span::new(lhs_token.chunk.span.start, rsb.chunk.span.end),
+                ))
             }
+            TokenKind::LeftParenthesis => {
+                let expression = self.parse_expression(0)?;
+                self.cursor.consume(TokenKind::RightParenthesis)?;
+                self.wrap(checkpoint, SyntaxKind::Parenthesized);
+                expression
+            }
+            kind => match self.operators.find(kind, Fixity::Prefix) {
+                Some(
+                    entry @ OperatorEntry {
+                        operator: Some(operator),
+                        ..
+                    },
+                ) => {
+                    let (_, right_binding_power) = entry.binding_power();
+                    let rhs = self.parse_expression(right_binding_power)?;
+                    self.wrap(checkpoint, SyntaxKind::Prefix);
+                    Node::Expression(Expression::Prefix {
+                        operator: Spanned::new(operator, lhs_token.chunk.span),
+                        value: Box::new(rhs),
+                    })
+                }
+                _ => {
+                    let mut expected = vec![
+                        TokenKind::Identifier,
+                        TokenKind::Integer,
+                        TokenKind::Float,
+                        TokenKind::String,
+                        TokenKind::LeftParenthesis,
+                    ];
+                    expected.extend(self.operators.tokens(Fixity::Prefix));
+                    expected.extend([
+                        TokenKind::Match,
+                        TokenKind::LeftSquareBracket,
+                        TokenKind::Pipe,
+                    ]);
+                    return Err(Box::new(Error::new(
+                        ErrorKind::UnexpectedToken {
+                            expected,
+                            received: kind,
+                        },
+                        lhs_token.chunk.span,
+                        self.source,
+                    )));
+                }
+            },
         };
         while let Ok(operator_token) = self.cursor.peek() {
-            if let Some((left_binding_power, ())) = Self::postfix_binding_power(operator_token.kind)
-            {
+            if let Some(entry) = self.operators.find(operator_token.kind, Fixity::Postfix) {
+                let (left_binding_power, _) = entry.binding_power();
                 if operator_token.kind == TokenKind::LeftParenthesis {
                     if left_binding_power < minimum_binding_power {
                         break;
                     }
+                    let arguments_checkpoint = self.checkpoint();
                     let lp = self.cursor.next_token()?;
-                    let arguments = self.arguments(
-                        |parser| parser.parse_expression(0),
-                        vec![
-                            TokenKind::LeftParenthesis,
-                            TokenKind::Identifier,
-                            TokenKind::Float,
-                            TokenKind::Integer,
-                        ],
-                    )?;
-                    let rp = self.cursor.consume(TokenKind::RightParenthesis)?;
-                    if lhs_token.kind == TokenKind::Identifier {
-                        lhs = Node::Expression(Expression::Call {
-                            name: Spanned::new(lhs_token.chunk.data, lhs_token.chunk.span),
-                            arguments: Spanned::new(
-                                arguments,
-                                Span::new(lp.chunk.span.start, rp.chunk.span.end),
-                            ),
-                        })
+                    let arguments = if self.cursor.test(TokenKind::RightParenthesis) {
+                        vec![]
                     } else {
-                        return Err(Box::new(Error::new(
-                            ErrorKind::UnexpectedToken {
-                                expected: vec![TokenKind::Identifier],
-                                received: lhs_token.kind,
-                            },
-                            lhs_token.chunk.span,
-                            self.source,
-                        )));
+                        self.arguments(
+                            |parser| parser.parse_expression(0),
+                            vec![
+                                TokenKind::LeftParenthesis,
+                                TokenKind::Identifier,
+                                TokenKind::Float,
+                                TokenKind::Integer,
+                            ],
+                        )?
+                    };
+                    let rp = self.cursor.consume(TokenKind::RightParenthesis)?;
+                    self.wrap(arguments_checkpoint, SyntaxKind::ArgumentList);
+                    self.wrap(checkpoint, SyntaxKind::Call);
+                    lhs = Node::Expression(Expression::Call {
+                        callee: Box::new(lhs),
+                        generics: generics.take(),
+                        arguments: Spanned::new(
+                            arguments,
+                            Span::new(lp.chunk.span.start, rp.chunk.span.end),
+                        ),
+                    });
+                    continue;
+                }
+                if operator_token.kind == TokenKind::Dot {
+                    if left_binding_power < minimum_binding_power {
+                        break;
                     }
+                    self.cursor.next_token()?;
+                    let name = self.cursor.consume(TokenKind::Identifier)?;
+                    self.wrap(checkpoint, SyntaxKind::Member);
+                    lhs = Node::Expression(Expression::Member {
+                        target: Box::new(lhs),
+                        name: Spanned::new(name.chunk.data, name.chunk.span),
+                    });
+                    continue;
+                }
+                if operator_token.kind == TokenKind::LeftSquareBracket {
+                    if left_binding_power < minimum_binding_power {
+                        break;
+                    }
+                    let lsb = self.cursor.next_token()?;
+                    let index = self.parse_expression(0)?;
+                    let rsb = self.cursor.consume(TokenKind::RightSquareBracket)?;
+                    self.wrap(checkpoint, SyntaxKind::Index);
+                    lhs = Node::Expression(Expression::Index {
+                        target: Box::new(lhs),
+                        index: Spanned::new(
+                            Box::new(index),
+                            Span::new(lsb.chunk.span.start, rsb.chunk.span.end),
+                        ),
+                    });
                     continue;
                 }
             }
-            if let Some((left_binding_power, right_binding_power)) =
-                Self::infix_binding_power(operator_token.kind)
-            {
+            if let Some(entry) = self.operators.find(operator_token.kind, Fixity::Infix) {
+                let (left_binding_power, right_binding_power) = entry.binding_power();
                 if left_binding_power < minimum_binding_power {
                     break;
                 }
-                let operator = Operator::from(operator_token.kind);
-                self.cursor.next_token()?;
-                let rhs = self.parse_expression(right_binding_power)?;
-                lhs = Node::Expression(Expression::Infix {
-                    operator,
-                    lhs: Box::new(lhs),
-                    rhs: Box::new(rhs),
-                });
+                let token = self.cursor.next_token()?;
+                lhs = match (entry.operator, token.kind) {
+                    (Some(operator), _) => {
+                        let rhs = self.parse_expression(right_binding_power)?;
+                        self.wrap(checkpoint, SyntaxKind::Infix);
+                        Node::Expression(Expression::Infix {
+                            operator,
+                            lhs: Box::new(lhs),
+                            rhs: Box::new(rhs),
+                        })
+                    }
+                    (None, TokenKind::Question) => {
+                        let consequent = self.parse_expression(0)?;
+                        self.cursor.consume(TokenKind::Colon)?;
+                        let alternative = self.parse_expression(right_binding_power)?;
+                        self.wrap(checkpoint, SyntaxKind::Ternary);
+                        Node::Expression(Expression::Ternary {
+                            test: Box::new(lhs),
+                            consequent: Box::new(consequent),
+                            alternative: Box::new(alternative),
+                        })
+                    }
+                    (None, kind) => {
+                        let end = self.parse_expression(right_binding_power)?;
+                        self.wrap(checkpoint, SyntaxKind::Range);
+                        Node::Expression(Expression::Range {
+                            start: Box::new(lhs),
+                            end: Box::new(end),
+                            inclusive: kind == TokenKind::DotDotEqual,
+                        })
+                    }
+                };
                 continue;
             }
             break;
         }
         Ok(lhs)
     }
-    #[inline]
-    pub(super) fn postfix_binding_power(kind: TokenKind) -> Option<(u8, ())> {
-        match kind {
-            TokenKind::LeftParenthesis => Some((13, ())),
-            _ => None,
-        }
-    }
-    #[inline]
-    pub(super) fn infix_binding_power(kind: TokenKind) -> Option<(u8, u8)> {
-        match kind {
-            TokenKind::Assignment => Some((1, 2)),
-            TokenKind::Plus | TokenKind::Minus => Some((2, 3)),
-            TokenKind::Multiply | TokenKind::Divide => Some((4, 5)),
-            TokenKind::LessThen
-            | TokenKind::GreaterThen
-            | TokenKind::LessThenEqual
-            | TokenKind::GreaterThenEqual => Some((6, 7)),
-            TokenKind::Equal => Some((8, 9)),
-
-            _ => None,
-        }
+    pub(super) fn parse_match(&mut self, match_kw_span: Span) -> Result<'a, Node<'a>> {
+        parentheses!(let value = self.parse_expression(0)?; self);
+        let lcb = self.cursor.consume(TokenKind::LeftCurlyBrace)?;
+        let arms = if self.cursor.test(TokenKind::RightCurlyBrace) {
+            vec![]
+        } else {
+            self.arguments(
+                |parser| {
+                    parser.node(SyntaxKind::MatchArm, |parser| {
+                        let pattern = parser.parse_pattern()?;
+                        parser.cursor.consume(TokenKind::FatArrow)?;
+                        let body = if parser.cursor.test(TokenKind::LeftCurlyBrace) {
+                            parser.parse_block()?
+                        } else {
+                            parser.parse_expression(0)?
+                        };
+                        Ok(MatchArm {
+                            pattern,
+                            body: Box::new(body),
+                        })
+                    })
+                },
+                vec![
+                    TokenKind::Underscore,
+                    TokenKind::Identifier,
+                    TokenKind::Integer,
+                    TokenKind::Float,
+                ],
+            )?
+        };
+        let rcb = self.cursor.consume(TokenKind::RightCurlyBrace)?;
+        Ok(Node::Expression(Expression::Match {
+            keyword: match_kw_span,
+            value: Box::new(value),
+            arms: Spanned::new(arms, Span::new(lcb.chunk.span.start, rcb.chunk.span.end)),
+        }))
     }
-    #[inline]
-    pub(super) fn prefix_binding_power(operator: Operator) -> Option<((), u8)> {
-        match operator {
-            Operator::Plus | Operator::Minus => Some(((), 12)),
-            _ => None,
-        }
+    pub(super) fn parse_lambda(
+        &mut self,
+        pipe_span: Span,
+        checkpoint: Checkpoint,
+    ) -> Result<'a, Node<'a>> {
+        let parameters = if self.cursor.test(TokenKind::Pipe) {
+            vec![]
+        } else {
+            self.arguments(
+                |parser| {
+                    parser.node(SyntaxKind::Parameter, |parser| {
+                        let name = parser.cursor.consume(TokenKind::Identifier)?;
+                        let parameter_type = if parser.cursor.optional(TokenKind::Colon)? {
+                            Some(parser.parse_type()?)
+                        } else {
+                            None
+                        };
+                        Ok(Parameter {
+                            name: Spanned::new(name.chunk.data, name.chunk.span),
+                            parameter_type,
+                        })
+                    })
+                },
+                vec![TokenKind::Identifier],
+            )?
+        };
+        let pipe = self.cursor.consume(TokenKind::Pipe)?;
+        self.wrap(checkpoint, SyntaxKind::ParameterList);
+        let body = if self.cursor.test(TokenKind::LeftCurlyBrace) {
+            self.parse_block()?
+        } else {
+            self.parse_expression(0)?
+        };
+        Ok(Node::Expression(Expression::Lambda {
+            parameters: Spanned::new(parameters, Span::new(pipe_span.start, pipe.chunk.span.end)),
+            body: Box::new(body),
+        }))
     }
 }
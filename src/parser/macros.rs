@@ -1,7 +1,7 @@
 #[macro_export]
 macro_rules! parentheses {
     ($expr: stmt; $self: ident) => {
-        use crate::lexer::token::TokenKind;
+        use $crate::lexer::token::TokenKind;
         $self.cursor.consume(TokenKind::LeftParenthesis)?;
         $expr
         $self.cursor.consume(TokenKind::RightParenthesis)?;
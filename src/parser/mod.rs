@@ -1,43 +1,119 @@
 pub mod ast;
 pub mod cursor;
 pub mod expression;
+pub mod operator;
+pub mod pattern;
 pub mod statement;
+pub mod suggest;
+pub mod types;
 pub mod utils;
 #[macro_use]
 pub mod macros;
 use crate::{
     common::{
         error::{Error, ErrorKind, Result},
+        intern::Interner,
         source::Source,
+        span::Span,
     },
     lexer::token::TokenKind,
-    parser::ast::function::{Argument, Function},
+    parser::ast::{
+        calculate_span::CalculateSpan,
+        enumeration::{Enum, Variant},
+        function::{Argument, Function},
+        spanned::Spanned,
+        Import,
+    },
+    syntax::{builder::Builder, kind::SyntaxKind},
 };
 
-use self::{ast::Program, cursor::Cursor};
+use self::{ast::Program, cursor::Cursor, operator::OperatorTable};
+
+pub const MAX_NESTING_DEPTH: usize = 128;
+
 #[derive(Clone, Debug)]
 pub struct Parser<'a> {
     source: Source<'a>,
     cursor: Cursor<'a>,
+    names: &'a Interner,
+    operators: OperatorTable,
+    depth: usize,
 }
 
 impl<'a> Parser<'a> {
-    pub fn new(source: Source<'a>, cursor: Cursor<'a>) -> Self {
-        Self { source, cursor }
+    pub fn new(source: Source<'a>, cursor: Cursor<'a>, names: &'a Interner) -> Self {
+        Self {
+            source,
+            cursor,
+            names,
+            operators: OperatorTable::default(),
+            depth: 0,
+        }
+    }
+    pub fn with_operators(mut self, operators: OperatorTable) -> Self {
+        self.operators = operators;
+        self
+    }
+    pub fn syntax(self) -> Option<Builder<'a>> {
+        self.cursor.syntax
     }
     pub fn parse_program(&mut self) -> Result<'a, Program<'a>> {
+        self.parse_items().map_err(|err| self.suggest(err))
+    }
+    fn parse_items(&mut self) -> Result<'a, Program<'a>> {
         let mut functions = vec![];
-        while self.cursor.peek().is_ok() {
+        let mut enums = vec![];
+        let mut module = None;
+        let mut imports = vec![];
+        while !self.cursor.is_at_end() {
             let token = self.cursor.peek()?;
             match token.kind {
                 TokenKind::Function => {
-                    self.cursor.next_token()?;
-                    functions.push(self.parse_function()?);
+                    functions.push(self.node(SyntaxKind::FunctionDefinition, |parser| {
+                        parser.cursor.next_token()?;
+                        parser.parse_function(false)
+                    })?)
+                }
+                TokenKind::Pub => {
+                    functions.push(self.node(SyntaxKind::FunctionDefinition, |parser| {
+                        parser.cursor.next_token()?;
+                        parser.cursor.consume(TokenKind::Function)?;
+                        parser.parse_function(true)
+                    })?)
+                }
+                TokenKind::Import => {
+                    imports.push(self.node(SyntaxKind::ImportDeclaration, |parser| {
+                        parser.cursor.next_token()?;
+                        let path = parser.cursor.consume(TokenKind::String)?;
+                        parser.cursor.consume(TokenKind::Semicolon)?;
+                        let span = Span::new(path.chunk.span.start + 1, path.chunk.span.end - 1);
+                        Ok(Import {
+                            path: Spanned::new(span.slice(parser.source.code), span),
+                        })
+                    })?)
                 }
+                TokenKind::Module => {
+                    module = Some(self.node(SyntaxKind::ModuleDeclaration, |parser| {
+                        parser.cursor.next_token()?;
+                        let name = parser.cursor.consume(TokenKind::Identifier)?;
+                        parser.cursor.consume(TokenKind::Semicolon)?;
+                        Ok(Spanned::new(name.chunk.data, name.chunk.span))
+                    })?);
+                }
+                TokenKind::Enum => enums.push(self.node(SyntaxKind::EnumDefinition, |parser| {
+                    parser.cursor.next_token()?;
+                    parser.parse_enum()
+                })?),
                 _ => {
                     return Err(Box::new(Error::new(
                         ErrorKind::UnexpectedToken {
-                            expected: vec![TokenKind::Function],
+                            expected: vec![
+                                TokenKind::Function,
+                                TokenKind::Pub,
+                                TokenKind::Enum,
+                                TokenKind::Import,
+                                TokenKind::Module,
+                            ],
                             received: token.kind,
                         },
                         token.chunk.span,
@@ -48,37 +124,102 @@ impl<'a> Parser<'a> {
         }
         Ok(Program {
             path: self.source.path,
+            module,
+            imports,
             functions,
+            enums,
         })
     }
-    pub(self) fn parse_function(&mut self) -> Result<'a, Function<'a>> {
-        let name = self.cursor.consume(TokenKind::Identifier)?.chunk.data;
-        self.cursor.consume(TokenKind::LeftParenthesis)?;
-
-        let arguments = self.arguments(
-            |parser| {
-                let argument_name = parser.cursor.consume(TokenKind::Identifier)?;
-                parser.cursor.consume(TokenKind::Colon)?;
-                let argument_type = parser.cursor.consume(TokenKind::Identifier)?;
-                Ok(Argument {
-                    name: argument_name.chunk.data,
-                    argument_type: argument_type.chunk.data,
-                })
-            },
-            vec![TokenKind::Identifier],
-        )?;
-        self.cursor.consume(TokenKind::RightParenthesis)?;
-        let return_type = if self.cursor.optional(TokenKind::Arrow)? {
-            Some(self.cursor.consume(TokenKind::Identifier)?.chunk.data)
+    pub(self) fn parse_function(&mut self, public: bool) -> Result<'a, Function<'a>> {
+        let name = self.cursor.consume(TokenKind::Identifier)?.chunk;
+        let generics = if self.cursor.test(TokenKind::LessThen) {
+            self.node(SyntaxKind::GenericParameters, |parser| {
+                parser.cursor.next_token()?;
+                let generics = parser.arguments(
+                    |parser| {
+                        let generic = parser.cursor.consume(TokenKind::Identifier)?;
+                        Ok(Spanned::new(generic.chunk.data, generic.chunk.span))
+                    },
+                    vec![TokenKind::Identifier],
+                )?;
+                parser.cursor.consume(TokenKind::GreaterThen)?;
+                Ok(generics)
+            })?
+        } else {
+            vec![]
+        };
+        let arguments = self.node(SyntaxKind::ArgumentList, |parser| {
+            parser.cursor.consume(TokenKind::LeftParenthesis)?;
+            let arguments = if parser.cursor.test(TokenKind::RightParenthesis) {
+                vec![]
+            } else {
+                parser.arguments(
+                    |parser| {
+                        parser.node(SyntaxKind::Argument, |parser| {
+                            let argument_name = parser.cursor.consume(TokenKind::Identifier)?;
+                            parser.cursor.consume(TokenKind::Colon)?;
+                            let argument_type = parser.parse_type()?;
+                            let span = Span::new(
+                                argument_name.chunk.span.start,
+                                argument_type.calculate_span().end,
+                            );
+                            Ok(Argument {
+                                name: argument_name.chunk.data,
+                                argument_type,
+                                span,
+                            })
+                        })
+                    },
+                    vec![TokenKind::Identifier],
+                )?
+            };
+            parser.cursor.consume(TokenKind::RightParenthesis)?;
+            Ok(arguments)
+        })?;
+        let return_type = if self.cursor.test(TokenKind::Arrow) {
+            Some(self.node(SyntaxKind::ReturnType, |parser| {
+                parser.cursor.next_token()?;
+                parser.parse_type()
+            })?)
         } else {
             None
         };
         let body = self.parse_block()?;
         Ok(Function {
-            name,
+            public,
+            name: name.data,
+            name_span: name.span,
+            generics,
             arguments,
             body,
             return_type,
+            source: self.source,
         })
     }
+    pub(self) fn parse_enum(&mut self) -> Result<'a, Enum<'a>> {
+        let name = self.cursor.consume(TokenKind::Identifier)?.chunk.data;
+        self.cursor.consume(TokenKind::LeftCurlyBrace)?;
+        let variants = if self.cursor.test(TokenKind::RightCurlyBrace) {
+            vec![]
+        } else {
+            self.arguments(
+                |parser| {
+                    parser.node(SyntaxKind::Variant, |parser| {
+                        let name = parser.cursor.consume(TokenKind::Identifier)?.chunk.data;
+                        let fields = if parser.cursor.optional(TokenKind::LeftParenthesis)? {
+                            let fields = parser.type_list()?;
+                            parser.cursor.consume(TokenKind::RightParenthesis)?;
+                            Some(fields)
+                        } else {
+                            None
+                        };
+                        Ok(Variant { name, fields })
+                    })
+                },
+                vec![TokenKind::Identifier],
+            )?
+        };
+        self.cursor.consume(TokenKind::RightCurlyBrace)?;
+        Ok(Enum { name, variants })
+    }
 }
@@ -0,0 +1,200 @@
+use crate::lexer::token::TokenKind;
+
+use super::ast::expression::Operator;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fixity {
+    Prefix,
+    Infix,
+    Postfix,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct OperatorEntry {
+    pub token: TokenKind,
+    pub symbol: &'static str,
+    pub operator: Option<Operator>,
+    pub fixity: Fixity,
+    pub precedence: u8,
+    pub associativity: Associativity,
+}
+#[derive(Clone, Debug)]
+pub struct OperatorTable {
+    entries: Vec<OperatorEntry>,
+}
+
+const fn infix(
+    token: TokenKind,
+    symbol: &'static str,
+    operator: Operator,
+    precedence: u8,
+    associativity: Associativity,
+) -> OperatorEntry {
+    OperatorEntry {
+        token,
+        symbol,
+        operator: Some(operator),
+        fixity: Fixity::Infix,
+        precedence,
+        associativity,
+    }
+}
+const fn prefix(token: TokenKind, symbol: &'static str, operator: Operator) -> OperatorEntry {
+    OperatorEntry {
+        operator: Some(operator),
+        fixity: Fixity::Prefix,
+        precedence: 8,
+        associativity: Associativity::Right,
+        ..postfix(token, symbol)
+    }
+}
+const fn postfix(token: TokenKind, symbol: &'static str) -> OperatorEntry {
+    OperatorEntry {
+        token,
+        symbol,
+        operator: None,
+        fixity: Fixity::Postfix,
+        precedence: 9,
+        associativity: Associativity::Left,
+    }
+}
+const fn special(
+    token: TokenKind,
+    symbol: &'static str,
+    precedence: u8,
+    associativity: Associativity,
+) -> OperatorEntry {
+    OperatorEntry {
+        operator: None,
+        fixity: Fixity::Infix,
+        precedence,
+        associativity,
+        ..postfix(token, symbol)
+    }
+}
+
+pub const OPERATORS: [OperatorEntry; 18] = {
+    use Associativity::{Left, Right};
+    [
+        infix(TokenKind::Assignment, "=", Operator::Assignment, 1, Right),
+        special(TokenKind::Question, "? :", 2, Right),
+        special(TokenKind::DotDot, "..", 3, Left),
+        special(TokenKind::DotDotEqual, "..=", 3, Left),
+        infix(TokenKind::Plus, "+", Operator::Plus, 4, Left),
+        infix(TokenKind::Minus, "-", Operator::Minus, 4, Left),
+        infix(TokenKind::Multiply, "*", Operator::Multiply, 5, Left),
+        infix(TokenKind::Divide, "/", Operator::Divide, 5, Left),
+        infix(TokenKind::LessThen, "<", Operator::LessThen, 6, Left),
+        infix(TokenKind::GreaterThen, ">", Operator::GreaterThen, 6, Left),
+        infix(
+            TokenKind::LessThenEqual,
+            "<=",
+            Operator::LessThenEqual,
+            6,
+            Left,
+        ),
+        infix(
+            TokenKind::GreaterThenEqual,
+            ">=",
+            Operator::GreaterThenEqual,
+            6,
+            Left,
+        ),
+        infix(TokenKind::Equal, "==", Operator::Equal, 7, Left),
+        prefix(TokenKind::Plus, "+", Operator::Plus),
+        prefix(TokenKind::Minus, "-", Operator::Minus),
+        postfix(TokenKind::LeftParenthesis, "("),
+        postfix(TokenKind::LeftSquareBracket, "["),
+        postfix(TokenKind::Dot, "."),
+    ]
+};
+
+impl OperatorEntry {
+    pub fn binding_power(&self) -> (u8, u8) {
+        let power = self.precedence * 2;
+        match (self.fixity, self.associativity) {
+            (Fixity::Prefix, _) => (0, power),
+            (Fixity::Postfix, _) => (power, 0),
+            (Fixity::Infix, Associativity::Left) => (power, power + 1),
+            (Fixity::Infix, Associativity::Right) => (power, power),
+        }
+    }
+}
+
+impl Default for OperatorTable {
+    fn default() -> Self {
+        Self {
+            entries: OPERATORS.to_vec(),
+        }
+    }
+}
+impl OperatorTable {
+    pub fn entries(&self) -> &[OperatorEntry] {
+        &self.entries
+    }
+    pub fn find(&self, token: TokenKind, fixity: Fixity) -> Option<OperatorEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.token == token && entry.fixity == fixity)
+            .copied()
+    }
+    pub fn tokens(&self, fixity: Fixity) -> impl Iterator<Item = TokenKind> + '_ {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.fixity == fixity)
+            .map(|entry| entry.token)
+    }
+    pub fn set_precedence(
+        &mut self,
+        token: TokenKind,
+        fixity: Fixity,
+        precedence: u8,
+        associativity: Associativity,
+    ) -> bool {
+        let found = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.token == token && entry.fixity == fixity);
+        match found {
+            Some(entry) => {
+                entry.precedence = precedence;
+                entry.associativity = associativity;
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn markdown(&self) -> String {
+        let mut entries = self.entries.clone();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.precedence));
+        let mut table = String::from(
+            "| Operator | Token | Fixity | Precedence | Associativity |\n\
+             | --- | --- | --- | --- | --- |\n",
+        );
+        for entry in entries {
+            table.push_str(&format!(
+                "| `{}` | `{:?}` | {:?} | {} | {:?} |\n",
+                entry.symbol, entry.token, entry.fixity, entry.precedence, entry.associativity
+            ));
+        }
+        table
+    }
+}
+
+pub fn operator(token: TokenKind, fixity: Fixity) -> Option<Operator> {
+    OPERATORS
+        .iter()
+        .find(|entry| entry.token == token && entry.fixity == fixity)
+        .and_then(|entry| entry.operator)
+}
+pub fn symbol(operator: Operator) -> &'static str {
+    OPERATORS
+        .iter()
+        .find(|entry| entry.operator == Some(operator))
+        .map(|entry| entry.symbol)
+        .unwrap_or_default()
+}
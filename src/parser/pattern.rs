@@ -0,0 +1,108 @@
+use crate::{
+    common::{
+        error::{Error, ErrorKind, Result},
+        span::Span,
+    },
+    lexer::token::TokenKind,
+    syntax::kind::SyntaxKind,
+};
+
+use super::{
+    ast::{pattern::Pattern, spanned::Spanned},
+    Parser,
+};
+
+impl<'a> Parser<'a> {
+    pub(super) fn parse_pattern(&mut self) -> Result<'a, Pattern<'a>> {
+        self.nested(Self::pattern)
+    }
+    fn pattern(&mut self) -> Result<'a, Pattern<'a>> {
+        let checkpoint = self.checkpoint();
+        let token = self.cursor.next_token()?;
+        match token.kind {
+            TokenKind::Underscore => {
+                self.wrap(checkpoint, SyntaxKind::WildcardPattern);
+                Ok(Pattern::Wildcard(token.chunk.span))
+            }
+            TokenKind::Integer => {
+                self.wrap(checkpoint, SyntaxKind::LiteralPattern);
+                Ok(Pattern::Integer(Spanned::new(
+                    token.chunk.data,
+                    token.chunk.span,
+                )))
+            }
+            TokenKind::Float => {
+                self.wrap(checkpoint, SyntaxKind::LiteralPattern);
+                Ok(Pattern::Float(Spanned::new(
+                    token.chunk.data,
+                    token.chunk.span,
+                )))
+            }
+            TokenKind::Minus => {
+                let literal = self.cursor.next_token()?;
+                self.wrap(checkpoint, SyntaxKind::LiteralPattern);
+                let span = Span::new(token.chunk.span.start, literal.chunk.span.end);
+                let value =
+                    Spanned::new(self.names.intern(format!("-{}", literal.chunk.data)), span);
+                match literal.kind {
+                    TokenKind::Integer => Ok(Pattern::Integer(value)),
+                    TokenKind::Float => Ok(Pattern::Float(value)),
+                    _ => Err(Box::new(Error::new(
+                        ErrorKind::UnexpectedToken {
+                            expected: vec![TokenKind::Integer, TokenKind::Float],
+                            received: literal.kind,
+                        },
+                        literal.chunk.span,
+                        self.source,
+                    ))),
+                }
+            }
+            TokenKind::Identifier => {
+                if !self.cursor.test(TokenKind::DoubleColon) {
+                    self.wrap(checkpoint, SyntaxKind::BindingPattern);
+                    return Ok(Pattern::Binding(Spanned::new(
+                        token.chunk.data,
+                        token.chunk.span,
+                    )));
+                }
+                let path = self.parse_path(token)?;
+                self.wrap(checkpoint, SyntaxKind::Path);
+                let fields = if self.cursor.test(TokenKind::LeftParenthesis) {
+                    let lp = self.cursor.next_token()?;
+                    let fields = self.arguments(
+                        |parser| parser.parse_pattern(),
+                        vec![
+                            TokenKind::Underscore,
+                            TokenKind::Identifier,
+                            TokenKind::Integer,
+                            TokenKind::Float,
+                        ],
+                    )?;
+                    let rp = self.cursor.consume(TokenKind::RightParenthesis)?;
+                    Some(Spanned::new(
+                        fields,
+                        Span::new(lp.chunk.span.start, rp.chunk.span.end),
+                    ))
+                } else {
+                    None
+                };
+                self.wrap(checkpoint, SyntaxKind::VariantPattern);
+                Ok(Pattern::Variant { path, fields })
+            }
+            _ => Err(Box::new(Error::new(
+                ErrorKind::UnexpectedToken {
+                    expected: vec![
+                        TokenKind::Underscore,
+                        TokenKind::Identifier,
+                        TokenKind::Integer,
+                        TokenKind::Float,
+                        TokenKind::Minus,
+                    ],
+                    received: token.kind,
+                },
+                token.chunk.span,
+                self.source,
+            ))),
+        }
+    }
+}
@@ -1,12 +1,17 @@
 use crate::{
-    common::{error::Result, span::Span},
+    common::{
+        error::{Error, ErrorKind, Result},
+        span::Span,
+    },
     lexer::token::TokenKind,
     parentheses,
     parser::ast::{
         calculate_span::CalculateSpan,
-        statement::{IfStatement, WhileStatement},
+        expression::{Expression, Operator},
+        statement::{ForStatement, IfStatement, WhileStatement},
     },
     statement,
+    syntax::kind::SyntaxKind,
 };
 
 use super::{
@@ -20,25 +25,49 @@ use super::{
 
 impl<'a> Parser<'a> {
     pub(super) fn parse_statement(&mut self) -> Result<'a, Node<'a>> {
+        self.nested(Self::statement)
+    }
+    fn statement(&mut self) -> Result<'a, Node<'a>> {
         let token = self.cursor.peek()?;
         match token.kind {
-            TokenKind::While => statement!(parse_while; self),
+            TokenKind::While => self.node(
+                SyntaxKind::WhileStatement,
+                |parser| statement!(parse_while; parser),
+            ),
+            TokenKind::For => self.node(
+                SyntaxKind::ForStatement,
+                |parser| statement!(parse_for; parser),
+            ),
             TokenKind::LeftCurlyBrace => self.parse_block(),
-            TokenKind::If => statement!(parse_if; self),
-            TokenKind::Return => statement!(parse_return; self),
-            TokenKind::Let => statement!(parse_let; self),
-            _ => {
-                let expression = self.parse_expression(0)?;
-                self.cursor.consume(TokenKind::Semicolon)?;
+            TokenKind::If => self.node(
+                SyntaxKind::IfStatement,
+                |parser| statement!(parse_if; parser),
+            ),
+            TokenKind::Return => self.node(
+                SyntaxKind::ReturnStatement,
+                |parser| statement!(parse_return; parser),
+            ),
+            TokenKind::Let => self.node(
+                SyntaxKind::LetStatement,
+                |parser| statement!(parse_let; parser),
+            ),
+            TokenKind::Match => self.node(SyntaxKind::ExpressionStatement, |parser| {
+                let expression = parser.parse_expression(0)?;
+                parser.cursor.optional(TokenKind::Semicolon)?;
                 Ok(expression)
-            }
+            }),
+            _ => self.node(SyntaxKind::ExpressionStatement, |parser| {
+                let expression = parser.parse_expression(0)?;
+                parser.cursor.consume(TokenKind::Semicolon)?;
+                Ok(expression)
+            }),
         }
     }
     pub(self) fn parse_let(&mut self, let_kw_span: Span) -> Result<'a, Node<'a>> {
         let mutable = self.cursor.optional(TokenKind::Mut)?;
         let name = self.cursor.consume(TokenKind::Identifier)?.chunk.data;
         let value_type = if self.cursor.optional(TokenKind::Colon)? {
-            Some(self.cursor.consume(TokenKind::Identifier)?.chunk.data)
+            Some(self.parse_type()?)
         } else {
             None
         };
@@ -61,6 +90,7 @@ impl<'a> Parser<'a> {
     }
     pub(self) fn parse_while(&mut self, while_kw_span: Span) -> Result<'a, Node<'a>> {
         parentheses!(let test = self.parse_expression(0)?; self);
+        self.check_condition(&test)?;
         let block = self.parse_statement()?;
         let block_span = block.calculate_span();
         Ok(Node::Statement(Statement::While(Spanned::new(
@@ -71,6 +101,21 @@ impl<'a> Parser<'a> {
             Span::new(while_kw_span.start, block_span.end),
         ))))
     }
+    pub(self) fn parse_for(&mut self, for_kw_span: Span) -> Result<'a, Node<'a>> {
+        let binding = self.cursor.consume(TokenKind::Identifier)?.chunk;
+        self.cursor.consume(TokenKind::In)?;
+        let iterable = self.parse_expression(0)?;
+        let body = self.parse_statement()?;
+        let body_span = body.calculate_span();
+        Ok(Node::Statement(Statement::For(Spanned::new(
+            ForStatement {
+                binding: Spanned::new(binding.data, binding.span),
+                iterable: Box::new(iterable),
+                body: Box::new(body),
+            },
+            Span::new(for_kw_span.start, body_span.end),
+        ))))
+    }
     pub(self) fn parse_return(&mut self, return_kw_span: Span) -> Result<'a, Node<'a>> {
         if self.cursor.test(TokenKind::Semicolon) {
             let semicolon_token = self.cursor.next_token()?;
@@ -91,9 +136,10 @@ impl<'a> Parser<'a> {
     }
     pub(self) fn parse_if(&mut self, if_kw_span: Span) -> Result<'a, Node<'a>> {
         parentheses!(let test = self.parse_expression(0)?; self);
+        self.check_condition(&test)?;
         let consequent = self.parse_statement()?;
         let consequent_span = consequent.calculate_span();
-        let alternative = if self.cursor.test_and_next(TokenKind::Else)? {
+        let alternative = if self.cursor.optional(TokenKind::Else)? {
             Some(Box::new(self.parse_statement()?))
         } else {
             None
@@ -107,16 +153,43 @@ impl<'a> Parser<'a> {
             Span::new(if_kw_span.start, consequent_span.end),
         ))))
     }
-    pub(super) fn parse_block(&mut self) -> Result<'a, Node<'a>> {
-        let lcb = self.cursor.consume(TokenKind::LeftCurlyBrace)?;
-        let mut statements = vec![];
-        while !self.cursor.test(TokenKind::RightCurlyBrace) && self.cursor.peek().is_ok() {
-            statements.push(self.parse_statement()?);
-        }
-        let rcb = self.cursor.next_token()?;
-        Ok(Node::Block(Spanned::new(
-            statements,
-            Span::new(lcb.chunk.span.start, rcb.chunk.span.end),
-        )))
+    fn check_condition(&self, test: &Node<'a>) -> Result<'a, ()> {
+        let Node::Expression(Expression::Infix {
+            operator: Operator::Assignment,
+            lhs,
+            rhs,
+        }) = test
+        else {
+            return Ok(());
+        };
+        let between = Span::new(lhs.calculate_span().end, rhs.calculate_span().start);
+        let offset = between
+            .slice(self.source.code)
+            .chars()
+            .position(|char| char == '=')
+            .unwrap_or_default();
+        let operator = Span::new(between.start + offset, between.start + offset + 1);
+        Err(Box::new(
+            Error::new(
+                ErrorKind::AssignmentInCondition,
+                test.calculate_span(),
+                self.source,
+            )
+            .with_suggestion("compare with `==`", operator, "=="),
+        ))
+    }
+    pub fn parse_block(&mut self) -> Result<'a, Node<'a>> {
+        self.node(SyntaxKind::Block, |parser| {
+            let lcb = parser.cursor.consume(TokenKind::LeftCurlyBrace)?;
+            let mut statements = vec![];
+            while !parser.cursor.test(TokenKind::RightCurlyBrace) && parser.cursor.peek().is_ok() {
+                statements.push(parser.parse_statement()?);
+            }
+            let rcb = parser.cursor.next_token()?;
+            Ok(Node::Block(Spanned::new(
+                statements,
+                Span::new(lcb.chunk.span.start, rcb.chunk.span.end),
+            )))
+        })
     }
 }
@@ -0,0 +1,69 @@
+use crate::{
+    common::{
+        error::{Error, ErrorKind},
+        span::Span,
+        suggest::closest,
+    },
+    lexer::{token::TokenKind, KEYWORDS},
+};
+
+use super::Parser;
+
+impl<'a> Parser<'a> {
+    pub(super) fn suggest(&self, mut error: Box<Error<'a>>) -> Box<Error<'a>> {
+        let ErrorKind::UnexpectedToken { expected, received } = &error.kind else {
+            return error;
+        };
+        let (expected, received) = (expected.clone(), *received);
+        let text = error.span.slice(self.source.code);
+        let keyword = |text: &str, accept: &dyn Fn(TokenKind) -> bool| {
+            closest(
+                text,
+                KEYWORDS
+                    .iter()
+                    .filter(|(keyword, kind)| keyword.len() > 1 && accept(*kind))
+                    .map(|(keyword, _)| *keyword),
+            )
+        };
+        if received == TokenKind::Identifier {
+            if let Some(keyword) = keyword(text, &|kind| expected.contains(&kind)) {
+                let span = error.span;
+                return Box::new(error.with_suggestion(
+                    format!("did you mean `{}`", keyword),
+                    span,
+                    keyword,
+                ));
+            }
+        }
+        let Some(previous) = self
+            .cursor
+            .previous
+            .filter(|previous| previous.chunk.span.start < error.span.start)
+        else {
+            return error;
+        };
+        let after = Span::new(previous.chunk.span.end, previous.chunk.span.end);
+        if previous.kind == TokenKind::Identifier {
+            if let Some(keyword) = keyword(previous.chunk.data, &|_| true) {
+                error = Box::new(error.with_suggestion(
+                    format!("did you mean `{}`", keyword),
+                    previous.chunk.span,
+                    keyword,
+                ));
+            }
+        }
+        let line_break = Span::new(previous.chunk.span.end, error.span.start)
+            .slice(self.source.code)
+            .contains('\n');
+        if expected.contains(&TokenKind::Semicolon)
+            && (received == TokenKind::RightCurlyBrace || line_break)
+        {
+            error = Box::new(error.with_suggestion("add the missing `;`", after, ";"));
+        }
+        if expected.contains(&TokenKind::RightParenthesis) && received == TokenKind::LeftCurlyBrace
+        {
+            error = Box::new(error.with_suggestion("close the condition with `)`", after, ")"));
+        }
+        error
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{
+    common::{
+        error::{Error, ErrorKind, Result},
+        span::Span,
+    },
+    lexer::token::TokenKind,
+    syntax::kind::SyntaxKind,
+};
+
+use super::{
+    ast::{spanned::Spanned, types::TypeExpr},
+    Parser,
+};
+
+impl<'a> Parser<'a> {
+    pub(super) fn parse_type(&mut self) -> Result<'a, TypeExpr<'a>> {
+        self.nested(Self::type_expr)
+    }
+    fn type_expr(&mut self) -> Result<'a, TypeExpr<'a>> {
+        let checkpoint = self.checkpoint();
+        let token = self.cursor.next_token()?;
+        match token.kind {
+            TokenKind::Identifier => {
+                let path = self.parse_path(token)?;
+                self.wrap(checkpoint, SyntaxKind::Path);
+                let generics = if self.cursor.test(TokenKind::LessThen) {
+                    let generics_checkpoint = self.checkpoint();
+                    let lt = self.cursor.next_token()?;
+                    let generics = self.type_list()?;
+                    let gt = self.cursor.consume(TokenKind::GreaterThen)?;
+                    self.wrap(generics_checkpoint, SyntaxKind::TypeArguments);
+                    Some(Spanned::new(
+                        generics,
+                        Span::new(lt.chunk.span.start, gt.chunk.span.end),
+                    ))
+                } else {
+                    None
+                };
+                self.wrap(checkpoint, SyntaxKind::PathType);
+                Ok(TypeExpr::Path { path, generics })
+            }
+            TokenKind::LeftParenthesis => {
+                if self.cursor.test(TokenKind::RightParenthesis) {
+                    let rp = self.cursor.next_token()?;
+                    self.wrap(checkpoint, SyntaxKind::UnitType);
+                    return Ok(TypeExpr::Unit(Span::new(
+                        token.chunk.span.start,
+                        rp.chunk.span.end,
+                    )));
+                }
+                let mut elements = vec![self.parse_type()?];
+                let comma = self.cursor.optional(TokenKind::Comma)?;
+                if comma && !self.cursor.test(TokenKind::RightParenthesis) {
+                    elements.extend(self.type_list()?);
+                }
+                let rp = self.cursor.consume(TokenKind::RightParenthesis)?;
+                if elements.len() == 1 && !comma {
+                    self.wrap(checkpoint, SyntaxKind::ParenthesizedType);
+                    Ok(elements.remove(0))
+                } else {
+                    self.wrap(checkpoint, SyntaxKind::TupleType);
+                    Ok(TypeExpr::Tuple(Spanned::new(
+                        elements,
+                        Span::new(token.chunk.span.start, rp.chunk.span.end),
+                    )))
+                }
+            }
+            TokenKind::LeftSquareBracket => {
+                let element = self.parse_type()?;
+                let length = if self.cursor.optional(TokenKind::Semicolon)? {
+                    let length = self.cursor.consume(TokenKind::Integer)?;
+                    Some(Spanned::new(length.chunk.data, length.chunk.span))
+                } else {
+                    None
+                };
+                let rsb = self.cursor.consume(TokenKind::RightSquareBracket)?;
+                self.wrap(checkpoint, SyntaxKind::ArrayType);
+                Ok(TypeExpr::Array {
+                    element: Box::new(element),
+                    length,
+                    span: Span::new(token.chunk.span.start, rsb.chunk.span.end),
+                })
+            }
+            TokenKind::Fn => {
+                let lp = self.cursor.consume(TokenKind::LeftParenthesis)?;
+                let arguments = if self.cursor.test(TokenKind::RightParenthesis) {
+                    vec![]
+                } else {
+                    self.type_list()?
+                };
+                let rp = self.cursor.consume(TokenKind::RightParenthesis)?;
+                let return_type = if self.cursor.optional(TokenKind::Arrow)? {
+                    Some(Box::new(self.parse_type()?))
+                } else {
+                    None
+                };
+                self.wrap(checkpoint, SyntaxKind::FunctionType);
+                Ok(TypeExpr::Function {
+                    keyword: token.chunk.span,
+                    arguments: Spanned::new(
+                        arguments,
+                        Span::new(lp.chunk.span.start, rp.chunk.span.end),
+                    ),
+                    return_type,
+                })
+            }
+            TokenKind::Ampersand => {
+                let mutable = self.cursor.optional(TokenKind::Mut)?;
+                let inner = self.parse_type()?;
+                self.wrap(checkpoint, SyntaxKind::ReferenceType);
+                Ok(TypeExpr::Reference {
+                    ampersand: token.chunk.span,
+                    mutable,
+                    inner: Box::new(inner),
+                })
+            }
+            _ => Err(Box::new(Error::new(
+                ErrorKind::UnexpectedToken {
+                    expected: vec![
+                        TokenKind::Identifier,
+                        TokenKind::LeftParenthesis,
+                        TokenKind::LeftSquareBracket,
+                        TokenKind::Fn,
+                        TokenKind::Ampersand,
+                    ],
+                    received: token.kind,
+                },
+                token.chunk.span,
+                self.source,
+            ))),
+        }
+    }
+    pub(super) fn type_list(&mut self) -> Result<'a, Vec<TypeExpr<'a>>> {
+        self.arguments(
+            |parser| parser.parse_type(),
+            vec![
+                TokenKind::Identifier,
+                TokenKind::LeftParenthesis,
+                TokenKind::LeftSquareBracket,
+                TokenKind::Fn,
+                TokenKind::Ampersand,
+            ],
+        )
+    }
+}
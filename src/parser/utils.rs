@@ -1,9 +1,13 @@
 use crate::{
-    common::error::{Error, ErrorKind, Result},
-    lexer::token::TokenKind,
+    common::{
+        error::{Error, ErrorKind, Result},
+        span::Span,
+    },
+    lexer::token::{Token, TokenKind},
+    syntax::{builder::Checkpoint, kind::SyntaxKind},
 };
 
-use super::Parser;
+use super::{ast::spanned::Spanned, Parser, MAX_NESTING_DEPTH};
 
 impl<'a> Parser<'a> {
     pub(super) fn arguments<F, T>(
@@ -34,4 +38,63 @@ impl<'a> Parser<'a> {
         }
         Ok(args)
     }
+    pub(super) fn checkpoint(&mut self) -> Checkpoint {
+        match &mut self.cursor.syntax {
+            Some(syntax) => syntax.checkpoint(),
+            None => Checkpoint::default(),
+        }
+    }
+    pub(super) fn wrap(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+        if let Some(syntax) = &mut self.cursor.syntax {
+            syntax.start_node_at(checkpoint, kind);
+            syntax.finish_node();
+        }
+    }
+    pub(super) fn node<F, T>(&mut self, kind: SyntaxKind, function: F) -> Result<'a, T>
+    where
+        F: FnOnce(&mut Self) -> Result<'a, T>,
+    {
+        if let Some(syntax) = &mut self.cursor.syntax {
+            syntax.start_node(kind);
+        }
+        let result = function(self)?;
+        if let Some(syntax) = &mut self.cursor.syntax {
+            syntax.finish_node();
+        }
+        Ok(result)
+    }
+    pub(super) fn nested<F, T>(&mut self, function: F) -> Result<'a, T>
+    where
+        F: FnOnce(&mut Self) -> Result<'a, T>,
+    {
+        if self.depth >= MAX_NESTING_DEPTH {
+            let span = self.cursor.peek()?.chunk.span;
+            return Err(Box::new(Error::new(
+                ErrorKind::NestingTooDeep,
+                span,
+                self.source,
+            )));
+        }
+        self.depth += 1;
+        let result = function(self);
+        self.depth -= 1;
+        result
+    }
+    pub(super) fn parse_path(&mut self, first: Token<'a>) -> Result<'a, Spanned<&'a str>> {
+        let mut span = first.chunk.span;
+        let mut segments = vec![first.chunk.data];
+        while self.cursor.test(TokenKind::DoubleColon)
+            && matches!(self.cursor.lookup(1), Some(Ok(token)) if token.kind == TokenKind::Identifier)
+        {
+            self.cursor.next_token()?;
+            let segment = self.cursor.next_token()?;
+            span = Span::new(span.start, segment.chunk.span.end);
+            segments.push(segment.chunk.data);
+        }
+        let path = match segments.as_slice() {
+            [segment] => segment,
+            _ => self.names.intern(segments.join("::")),
+        };
+        Ok(Spanned::new(path, span))
+    }
 }
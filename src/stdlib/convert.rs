@@ -0,0 +1,54 @@
+use crate::{
+    common::{
+        error::{ErrorKind, Result},
+        source::Source,
+        span::Span,
+    },
+    interpreter::value::Value,
+};
+
+use super::error;
+
+pub fn to_int<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    let integer = match arguments.as_slice() {
+        [Value::Integer(integer)] => Some(*integer),
+        [Value::Float(float)] if float.is_finite() && float.abs() < i64::MAX as f64 => {
+            Some(*float as i64)
+        }
+        [Value::Boolean(boolean)] => Some(*boolean as i64),
+        [Value::String(string)] => string.trim().parse().ok(),
+        _ => None,
+    };
+    integer
+        .map(Value::Integer)
+        .ok_or_else(|| error(ErrorKind::InvalidConversion, span, source))
+}
+pub fn to_float<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    let float = match arguments.as_slice() {
+        [Value::Integer(integer)] => Some(*integer as f64),
+        [Value::Float(float)] => Some(*float),
+        [Value::String(string)] => string.trim().parse().ok(),
+        _ => None,
+    };
+    float
+        .map(Value::Float)
+        .ok_or_else(|| error(ErrorKind::InvalidConversion, span, source))
+}
+pub fn to_string<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [value] => Ok(Value::String(value.to_string())),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
@@ -0,0 +1,84 @@
+use std::{
+    fs,
+    io::{self, Write},
+};
+
+use crate::{
+    common::{
+        error::{ErrorKind, Result},
+        source::Source,
+        span::Span,
+    },
+    interpreter::value::Value,
+};
+
+use super::error;
+
+fn join(arguments: &[Value]) -> String {
+    arguments
+        .iter()
+        .map(|argument| argument.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+fn io_error<'a>(
+    err: io::Error,
+    span: Span,
+    source: Source<'a>,
+) -> Box<crate::common::error::Error<'a>> {
+    error(
+        ErrorKind::Io {
+            message: err.to_string(),
+        },
+        span,
+        source,
+    )
+}
+pub fn print<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    let mut stdout = io::stdout();
+    write!(stdout, "{}", join(&arguments))
+        .and_then(|_| stdout.flush())
+        .map_err(|err| io_error(err, span, source))?;
+    Ok(Value::Unit)
+}
+pub fn println<'a>(arguments: Vec<Value<'a>>, _: Span, _: Source<'a>) -> Result<'a, Value<'a>> {
+    println!("{}", join(&arguments));
+    Ok(Value::Unit)
+}
+pub fn read_line<'a>(_: Vec<Value<'a>>, span: Span, source: Source<'a>) -> Result<'a, Value<'a>> {
+    let mut line = String::new();
+    io::stdin()
+        .read_line(&mut line)
+        .map_err(|err| io_error(err, span, source))?;
+    let length = line.trim_end_matches(['\n', '\r']).len();
+    line.truncate(length);
+    Ok(Value::String(line))
+}
+pub fn read_file<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::String(path)] => fs::read_to_string(path)
+            .map(Value::String)
+            .map_err(|err| io_error(err, span, source)),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
+pub fn write_file<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::String(path), Value::String(content)] => fs::write(path, content)
+            .map(|_| Value::Unit)
+            .map_err(|err| io_error(err, span, source)),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{
+    common::{
+        error::{ErrorKind, Result},
+        source::Source,
+        span::Span,
+    },
+    interpreter::value::Value,
+};
+
+use super::error;
+
+pub fn abs<'a>(arguments: Vec<Value<'a>>, span: Span, source: Source<'a>) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::Integer(integer)] => integer
+            .checked_abs()
+            .map(Value::Integer)
+            .ok_or_else(|| error(ErrorKind::IntegerOverflow, span, source)),
+        [Value::Float(float)] => Ok(Value::Float(float.abs())),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
+pub fn sqrt<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::Float(float)] => Ok(Value::Float(float.sqrt())),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
+pub fn pow<'a>(arguments: Vec<Value<'a>>, span: Span, source: Source<'a>) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::Float(base), Value::Float(exponent)] => Ok(Value::Float(base.powf(*exponent))),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
+pub fn min<'a>(arguments: Vec<Value<'a>>, span: Span, source: Source<'a>) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::Integer(lhs), Value::Integer(rhs)] => Ok(Value::Integer(*lhs.min(rhs))),
+        [Value::Float(lhs), Value::Float(rhs)] => Ok(Value::Float(lhs.min(*rhs))),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
+pub fn max<'a>(arguments: Vec<Value<'a>>, span: Span, source: Source<'a>) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::Integer(lhs), Value::Integer(rhs)] => Ok(Value::Integer(*lhs.max(rhs))),
+        [Value::Float(lhs), Value::Float(rhs)] => Ok(Value::Float(lhs.max(*rhs))),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    common::{
+        error::{Error, ErrorKind, Result},
+        source::Source,
+        span::Span,
+    },
+    interpreter::value::Value,
+};
+
+use self::signature::{Signature, Type};
+
+pub mod convert;
+pub mod io;
+pub mod math;
+pub mod runtime;
+pub mod signature;
+pub mod string;
+
+pub type Implementation = for<'a> fn(Vec<Value<'a>>, Span, Source<'a>) -> Result<'a, Value<'a>>;
+
+#[derive(Clone, Copy)]
+pub struct Builtin {
+    pub name: &'static str,
+    pub signature: Signature,
+    pub implementation: Implementation,
+}
+
+impl Builtin {
+    pub fn call<'a>(
+        &self,
+        arguments: Vec<Value<'a>>,
+        span: Span,
+        source: Source<'a>,
+    ) -> Result<'a, Value<'a>> {
+        self.signature.check(&arguments, span, source)?;
+        (self.implementation)(arguments, span, source)
+    }
+}
+
+macro_rules! builtin {
+    ($name: ident($($parameter: ident),*) -> $return_type: ident; $implementation: path) => {
+        Builtin {
+            name: stringify!($name),
+            signature: Signature {
+                parameters: &[$(Type::$parameter),*],
+                variadic: None,
+                return_type: Type::$return_type,
+            },
+            implementation: $implementation,
+        }
+    };
+    ($name: ident($($parameter: ident,)* ..$variadic: ident) -> $return_type: ident; $implementation: path) => {
+        Builtin {
+            name: stringify!($name),
+            signature: Signature {
+                parameters: &[$(Type::$parameter),*],
+                variadic: Some(Type::$variadic),
+                return_type: Type::$return_type,
+            },
+            implementation: $implementation,
+        }
+    };
+}
+
+pub static BUILTINS: &[Builtin] = &[
+    builtin!(print(..Any) -> Unit; io::print),
+    builtin!(println(..Any) -> Unit; io::println),
+    builtin!(read_line() -> String; io::read_line),
+    builtin!(read_file(String) -> String; io::read_file),
+    builtin!(write_file(String, String) -> Unit; io::write_file),
+    builtin!(format(String, ..Any) -> String; string::format),
+    builtin!(concat(String, String) -> String; string::concat),
+    builtin!(substring(String, Int, Int) -> String; string::substring),
+    builtin!(contains(String, String) -> Bool; string::contains),
+    builtin!(upper(String) -> String; string::upper),
+    builtin!(lower(String) -> String; string::lower),
+    builtin!(trim(String) -> String; string::trim),
+    builtin!(split(String, String) -> Array; string::split),
+    builtin!(abs(Number) -> Number; math::abs),
+    builtin!(sqrt(Float) -> Float; math::sqrt),
+    builtin!(pow(Float, Float) -> Float; math::pow),
+    builtin!(min(Number, Number) -> Number; math::min),
+    builtin!(max(Number, Number) -> Number; math::max),
+    builtin!(to_int(Any) -> Int; convert::to_int),
+    builtin!(to_float(Any) -> Float; convert::to_float),
+    builtin!(to_string(Any) -> String; convert::to_string),
+    builtin!(len(Any) -> Int; runtime::len),
+    builtin!(range(Int, Int) -> Array; runtime::range),
+    builtin!(slice(Any, Int, Int) -> Any; runtime::slice),
+    builtin!(assert(Bool) -> Unit; runtime::assert),
+    builtin!(exit(Int) -> Unit; runtime::exit),
+];
+
+pub fn lookup(name: &str) -> Option<&'static Builtin> {
+    BUILTINS.iter().find(|builtin| builtin.name == name)
+}
+fn error<'a>(kind: ErrorKind, span: Span, source: Source<'a>) -> Box<Error<'a>> {
+    Box::new(Error::new(kind, span, source))
+}
@@ -0,0 +1,97 @@
+use std::process;
+
+use crate::{
+    common::{
+        error::{ErrorKind, Result},
+        source::Source,
+        span::Span,
+    },
+    interpreter::value::Value,
+};
+
+use super::error;
+
+pub const MAX_RANGE_LENGTH: u64 = 1 << 22;
+
+pub fn len<'a>(arguments: Vec<Value<'a>>, span: Span, source: Source<'a>) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::Array(elements)] => Ok(Value::Integer(elements.len() as i64)),
+        [Value::String(string)] => Ok(Value::Integer(string.chars().count() as i64)),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
+pub fn range<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::Integer(start), Value::Integer(end)] => {
+            if end > start && end.abs_diff(*start) > MAX_RANGE_LENGTH {
+                return Err(error(
+                    ErrorKind::RangeTooLarge {
+                        start: *start,
+                        end: *end,
+                    },
+                    span,
+                    source,
+                ));
+            }
+            Ok(Value::Array((*start..*end).map(Value::Integer).collect()))
+        }
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
+pub fn slice<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    let (target, start, end) = match arguments.as_slice() {
+        [target, Value::Integer(start), Value::Integer(end)] => (target, *start, *end),
+        _ => return Err(error(ErrorKind::TypeMismatch, span, source)),
+    };
+    let length = match target {
+        Value::Array(elements) => elements.len(),
+        Value::String(string) => string.chars().count(),
+        _ => return Err(error(ErrorKind::TypeMismatch, span, source)),
+    };
+    for index in [start, end] {
+        if usize::try_from(index).map_or(true, |index| index > length) {
+            return Err(error(
+                ErrorKind::IndexOutOfBounds { index, length },
+                span,
+                source,
+            ));
+        }
+    }
+    let (start, end) = (start as usize, (end as usize).max(start as usize));
+    match target {
+        Value::String(string) => Ok(Value::String(
+            string.chars().skip(start).take(end - start).collect(),
+        )),
+        Value::Array(elements) => Ok(Value::Array(elements[start..end].to_vec())),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
+pub fn assert<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::Boolean(true)] => Ok(Value::Unit),
+        [Value::Boolean(false)] => Err(error(ErrorKind::AssertionFailed, span, source)),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
+pub fn exit<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::Integer(code)] => process::exit(*code as i32),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
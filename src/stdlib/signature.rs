@@ -0,0 +1,123 @@
+use std::fmt::{self, Display};
+
+use crate::{
+    common::{
+        error::{Error, ErrorKind, Result},
+        source::Source,
+        span::Span,
+    },
+    interpreter::value::Value,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Type {
+    Int,
+    Float,
+    Bool,
+    String,
+    Unit,
+    Number,
+    Array,
+    Any,
+}
+
+impl Type {
+    pub fn accepts(self, value: &Value) -> bool {
+        matches!(
+            (self, value),
+            (Type::Any, _)
+                | (Type::Int, Value::Integer(_))
+                | (Type::Float, Value::Float(_))
+                | (Type::Bool, Value::Boolean(_))
+                | (Type::String, Value::String(_))
+                | (Type::Unit, Value::Unit)
+                | (Type::Number, Value::Integer(_) | Value::Float(_))
+                | (Type::Array, Value::Array(_))
+        )
+    }
+    pub fn name(self) -> Option<&'static str> {
+        match self {
+            Type::Int => Some("int"),
+            Type::Float => Some("float"),
+            Type::Bool => Some("bool"),
+            Type::String => Some("string"),
+            Type::Unit | Type::Number | Type::Array | Type::Any => None,
+        }
+    }
+}
+impl Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Int => write!(f, "int"),
+            Type::Float => write!(f, "float"),
+            Type::Bool => write!(f, "bool"),
+            Type::String => write!(f, "string"),
+            Type::Unit => write!(f, "()"),
+            Type::Number => write!(f, "number"),
+            Type::Array => write!(f, "[_]"),
+            Type::Any => write!(f, "_"),
+        }
+    }
+}
+#[derive(Clone, Copy, Debug)]
+pub struct Signature {
+    pub parameters: &'static [Type],
+    pub variadic: Option<Type>,
+    pub return_type: Type,
+}
+
+impl Signature {
+    pub fn check<'a>(
+        &self,
+        arguments: &[Value<'a>],
+        span: Span,
+        source: Source<'a>,
+    ) -> Result<'a, ()> {
+        let arity_matches = match self.variadic {
+            Some(_) => arguments.len() >= self.parameters.len(),
+            None => arguments.len() == self.parameters.len(),
+        };
+        if !arity_matches {
+            return Err(Box::new(Error::new(
+                ErrorKind::WrongNumberOfArguments {
+                    expected: self.parameters.len(),
+                    received: arguments.len(),
+                },
+                span,
+                source,
+            )));
+        }
+        let expected = self
+            .parameters
+            .iter()
+            .copied()
+            .chain(self.variadic.into_iter().cycle());
+        if arguments
+            .iter()
+            .zip(expected)
+            .all(|(argument, expected)| expected.accepts(argument))
+        {
+            Ok(())
+        } else {
+            Err(Box::new(Error::new(ErrorKind::TypeMismatch, span, source)))
+        }
+    }
+}
+impl Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (index, parameter) in self.parameters.iter().enumerate() {
+            if index != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", parameter)?;
+        }
+        if let Some(variadic) = self.variadic {
+            if !self.parameters.is_empty() {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}...", variadic)?;
+        }
+        write!(f, ") -> {}", self.return_type)
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    common::{
+        error::{ErrorKind, Result},
+        source::Source,
+        span::Span,
+    },
+    interpreter::value::Value,
+};
+
+use super::error;
+
+pub fn format<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    let (template, values) = match arguments.split_first() {
+        Some((Value::String(template), values)) => (template, values),
+        _ => return Err(error(ErrorKind::TypeMismatch, span, source)),
+    };
+    let mut result = String::new();
+    let mut values = values.iter();
+    let mut placeholders = 0;
+    let mut chars = template.chars().peekable();
+    while let Some(char) = chars.next() {
+        match (char, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                result.push(char);
+            }
+            ('{', Some('}')) => {
+                chars.next();
+                placeholders += 1;
+                if let Some(value) = values.next() {
+                    result.push_str(&value.to_string());
+                }
+            }
+            _ => result.push(char),
+        }
+    }
+    if placeholders != arguments.len() - 1 {
+        return Err(error(
+            ErrorKind::WrongNumberOfArguments {
+                expected: placeholders + 1,
+                received: arguments.len(),
+            },
+            span,
+            source,
+        ));
+    }
+    Ok(Value::String(result))
+}
+pub fn concat<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::String(lhs), Value::String(rhs)] => Ok(Value::String(format!("{}{}", lhs, rhs))),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
+pub fn substring<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::String(string), Value::Integer(start), Value::Integer(end)] => {
+            let length = string.chars().count();
+            for index in [*start, *end] {
+                if usize::try_from(index).map_or(true, |index| index > length) {
+                    return Err(error(
+                        ErrorKind::IndexOutOfBounds { index, length },
+                        span,
+                        source,
+                    ));
+                }
+            }
+            Ok(Value::String(
+                string
+                    .chars()
+                    .skip(*start as usize)
+                    .take((*end - *start).max(0) as usize)
+                    .collect(),
+            ))
+        }
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
+pub fn contains<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::String(string), Value::String(pattern)] => {
+            Ok(Value::Boolean(string.contains(pattern.as_str())))
+        }
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
+fn map_string<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+    function: fn(&str) -> String,
+) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::String(string)] => Ok(Value::String(function(string))),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
+pub fn upper<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    map_string(arguments, span, source, str::to_uppercase)
+}
+pub fn lower<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    map_string(arguments, span, source, str::to_lowercase)
+}
+pub fn trim<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    map_string(arguments, span, source, |string| string.trim().to_string())
+}
+pub fn split<'a>(
+    arguments: Vec<Value<'a>>,
+    span: Span,
+    source: Source<'a>,
+) -> Result<'a, Value<'a>> {
+    match arguments.as_slice() {
+        [Value::String(string), Value::String(separator)] => Ok(Value::Array(
+            string
+                .split(separator.as_str())
+                .map(|part| Value::String(part.to_string()))
+                .collect(),
+        )),
+        _ => Err(error(ErrorKind::TypeMismatch, span, source)),
+    }
+}
@@ -0,0 +1,102 @@
+use std::rc::Rc;
+
+use crate::common::span::Span;
+
+use super::{
+    green::{GreenElement, GreenNode, GreenToken},
+    kind::SyntaxKind,
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Checkpoint(usize);
+
+#[derive(Clone, Debug)]
+pub struct Builder<'a> {
+    code: &'a str,
+    byte: usize,
+    position: usize,
+    parents: Vec<(SyntaxKind, usize)>,
+    children: Vec<GreenElement>,
+}
+
+impl<'a> Builder<'a> {
+    pub fn new(code: &'a str) -> Self {
+        Self {
+            code,
+            byte: 0,
+            position: 0,
+            parents: vec![],
+            children: vec![],
+        }
+    }
+    pub fn start_node(&mut self, kind: SyntaxKind) {
+        self.trivia();
+        self.parents.push((kind, self.children.len()));
+    }
+    pub fn checkpoint(&mut self) -> Checkpoint {
+        self.trivia();
+        Checkpoint(self.children.len())
+    }
+    pub fn start_node_at(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+        self.parents.push((kind, checkpoint.0));
+    }
+    pub fn finish_node(&mut self) {
+        let (kind, first) = self.parents.pop().expect("unbalanced `finish_node`");
+        let children = self.children.split_off(first);
+        self.children
+            .push(GreenElement::Node(Rc::new(GreenNode::new(kind, children))));
+    }
+    pub fn token(&mut self, kind: SyntaxKind, span: Span) {
+        self.trivia();
+        if self.position < span.start {
+            self.leaf(SyntaxKind::Error, span.start - self.position);
+        }
+        self.leaf(kind, span.end - span.start);
+    }
+    pub fn finish(mut self, kind: SyntaxKind) -> GreenNode {
+        self.trivia();
+        let rest = self.code[self.byte..].chars().count();
+        if rest > 0 {
+            self.leaf(SyntaxKind::Error, rest);
+        }
+        while !self.parents.is_empty() {
+            self.finish_node();
+        }
+        GreenNode::new(kind, self.children)
+    }
+    fn trivia(&mut self) {
+        loop {
+            let rest = &self.code[self.byte..];
+            let width = if rest.starts_with("//") {
+                rest.find('\n').map_or(rest.len(), |end| end)
+            } else {
+                rest.find(|char| !matches!(char, ' ' | '\t' | '\n' | '\r'))
+                    .unwrap_or(rest.len())
+            };
+            if width == 0 {
+                return;
+            }
+            let kind = if rest.starts_with("//") {
+                SyntaxKind::Comment
+            } else {
+                SyntaxKind::Whitespace
+            };
+            let text = &rest[..width];
+            self.push(kind, text);
+        }
+    }
+    fn leaf(&mut self, kind: SyntaxKind, chars: usize) {
+        let rest = &self.code[self.byte..];
+        let width = rest
+            .char_indices()
+            .nth(chars)
+            .map_or(rest.len(), |(index, _)| index);
+        self.push(kind, &rest[..width]);
+    }
+    fn push(&mut self, kind: SyntaxKind, text: &str) {
+        let token = GreenToken::new(kind, text);
+        self.byte += text.len();
+        self.position += token.width();
+        self.children.push(GreenElement::Token(Rc::new(token)));
+    }
+}
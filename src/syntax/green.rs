@@ -0,0 +1,85 @@
+use std::rc::Rc;
+
+use super::kind::SyntaxKind;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GreenToken {
+    kind: SyntaxKind,
+    text: Rc<str>,
+    width: usize,
+}
+#[derive(Clone, Debug, PartialEq)]
+pub struct GreenNode {
+    kind: SyntaxKind,
+    width: usize,
+    children: Vec<GreenElement>,
+}
+#[derive(Clone, Debug, PartialEq)]
+pub enum GreenElement {
+    Node(Rc<GreenNode>),
+    Token(Rc<GreenToken>),
+}
+
+impl GreenToken {
+    pub fn new(kind: SyntaxKind, text: &str) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+            width: text.chars().count(),
+        }
+    }
+    pub fn kind(&self) -> SyntaxKind {
+        self.kind
+    }
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+}
+impl GreenNode {
+    pub fn new(kind: SyntaxKind, children: Vec<GreenElement>) -> Self {
+        Self {
+            kind,
+            width: children.iter().map(GreenElement::width).sum(),
+            children,
+        }
+    }
+    pub fn kind(&self) -> SyntaxKind {
+        self.kind
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn children(&self) -> &[GreenElement] {
+        &self.children
+    }
+    pub fn replace_child(&self, index: usize, child: GreenElement) -> Self {
+        let mut children = self.children.clone();
+        children[index] = child;
+        Self::new(self.kind, children)
+    }
+    pub fn write_text(&self, output: &mut String) {
+        for child in &self.children {
+            match child {
+                GreenElement::Node(node) => node.write_text(output),
+                GreenElement::Token(token) => output.push_str(token.text()),
+            }
+        }
+    }
+}
+impl GreenElement {
+    pub fn kind(&self) -> SyntaxKind {
+        match self {
+            GreenElement::Node(node) => node.kind(),
+            GreenElement::Token(token) => token.kind(),
+        }
+    }
+    pub fn width(&self) -> usize {
+        match self {
+            GreenElement::Node(node) => node.width(),
+            GreenElement::Token(token) => token.width(),
+        }
+    }
+}
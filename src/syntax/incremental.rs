@@ -0,0 +1,225 @@
+use std::rc::Rc;
+
+use unicode_xid::UnicodeXID;
+
+use crate::{
+    common::{intern::Interner, source::Source, span::Span},
+    lexer::{token::TokenKind, Lexer},
+    parser::{ast::Program, cursor::Cursor, Parser},
+};
+
+use super::{
+    green::{GreenElement, GreenNode, GreenToken},
+    kind::SyntaxKind,
+    lower::lower,
+    parse,
+    red::{SyntaxNode, SyntaxToken},
+};
+
+#[derive(Clone, Debug)]
+pub struct TextEdit {
+    pub span: Span,
+    pub replacement: String,
+}
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reparse {
+    Token,
+    Node(SyntaxKind),
+    Full,
+}
+#[derive(Debug)]
+pub struct Document {
+    path: String,
+    text: String,
+    tree: Option<SyntaxNode>,
+    names: Interner,
+}
+
+impl TextEdit {
+    pub fn apply(&self, text: &str) -> String {
+        let byte = |position: usize| {
+            text.char_indices()
+                .nth(position)
+                .map_or(text.len(), |(index, _)| index)
+        };
+        let mut output = text[..byte(self.span.start)].to_string();
+        output.push_str(&self.replacement);
+        output.push_str(&text[byte(self.span.end)..]);
+        output
+    }
+    fn relative_to(&self, start: usize) -> Self {
+        Self {
+            span: Span::new(self.span.start - start, self.span.end - start),
+            replacement: self.replacement.clone(),
+        }
+    }
+}
+
+impl Document {
+    pub fn new(path: &str, text: &str) -> Self {
+        let tree = parse(Source { code: text, path }, &Interner::new()).ok();
+        Self {
+            path: path.to_string(),
+            text: text.to_string(),
+            tree,
+            names: Interner::new(),
+        }
+    }
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+    pub fn tree(&self) -> Option<&SyntaxNode> {
+        self.tree.as_ref()
+    }
+    pub fn program(&self) -> Option<Program<'_>> {
+        let source = Source {
+            code: &self.text,
+            path: &self.path,
+        };
+        lower(source, &self.names, self.tree.as_ref()?)
+    }
+    pub fn edit(&mut self, edit: &TextEdit) -> Reparse {
+        self.text = edit.apply(&self.text);
+        if let Some((tree, reparse)) = self.tree.as_ref().and_then(|tree| reparse(tree, edit)) {
+            self.tree = Some(tree);
+            return reparse;
+        }
+        let source = Source {
+            code: &self.text,
+            path: &self.path,
+        };
+        self.tree = parse(source, &Interner::new()).ok();
+        Reparse::Full
+    }
+}
+
+pub fn reparse(tree: &SyntaxNode, edit: &TextEdit) -> Option<(SyntaxNode, Reparse)> {
+    if edit.span.start > edit.span.end || edit.span.end > tree.span().end {
+        return None;
+    }
+    if let Some(tree) = reparse_token(tree, edit) {
+        return Some((tree, Reparse::Token));
+    }
+    let mut node = tree.covering_node(edit.span);
+    loop {
+        let span = node.span();
+        let kind = node.kind();
+        if matches!(kind, SyntaxKind::Block | SyntaxKind::FunctionDefinition)
+            && span.start < edit.span.start
+            && edit.span.end < span.end
+        {
+            let text = edit.relative_to(span.start).apply(&node.text());
+            if let Some(green) = parse_fragment(kind, &text) {
+                return Some((replace(&node, green), Reparse::Node(kind)));
+            }
+        }
+        node = node.parent()?.clone();
+    }
+}
+
+fn reparse_token(tree: &SyntaxNode, edit: &TextEdit) -> Option<SyntaxNode> {
+    tree.covering_node(edit.span)
+        .descendant_tokens()
+        .into_iter()
+        .filter(|token| token.span().start <= edit.span.start && edit.span.end <= token.span().end)
+        .find_map(|token| relex_token(&token, edit))
+}
+
+fn relex_token(token: &SyntaxToken, edit: &TextEdit) -> Option<SyntaxNode> {
+    let text = edit.relative_to(token.span().start).apply(token.text());
+    let relexed = match token.kind() {
+        SyntaxKind::Whitespace => (!text.is_empty()
+            && text
+                .chars()
+                .all(|char| matches!(char, ' ' | '\t' | '\n' | '\r'))
+            && (text.starts_with('\n') || !follows_comment(token)))
+        .then_some(SyntaxKind::Whitespace),
+        SyntaxKind::Comment => {
+            (text.starts_with("//") && !text.contains('\n')).then_some(SyntaxKind::Comment)
+        }
+        SyntaxKind::Token(TokenKind::Identifier)
+            if text
+                .chars()
+                .all(|char| char == '_' || char.is_xid_continue()) =>
+        {
+            relex(&text)
+        }
+        SyntaxKind::Token(TokenKind::Integer | TokenKind::Float)
+            if text
+                .chars()
+                .all(|char| char == '.' || char.is_ascii_digit()) =>
+        {
+            relex(&text)
+        }
+        SyntaxKind::Token(TokenKind::String) if text.len() > 1 && text.ends_with('"') => {
+            relex(&text)
+        }
+        _ => None,
+    }?;
+    if relexed != token.kind() {
+        return None;
+    }
+    let parent = token.parent();
+    let green = GreenToken::new(relexed, &text);
+    let green = parent
+        .green()
+        .replace_child(token.index(), GreenElement::Token(Rc::new(green)));
+    Some(replace(parent, Rc::new(green)))
+}
+
+fn relex(text: &str) -> Option<SyntaxKind> {
+    if text.is_empty() {
+        return None;
+    }
+    let source = Source {
+        code: text,
+        path: "",
+    };
+    let mut tokens = Lexer::new(source);
+    let token = tokens.next()?.ok()?;
+    if tokens.next().is_some() || token.chunk.span.start != 0 {
+        return None;
+    }
+    (token.chunk.span.end == text.chars().count()).then_some(token.kind.into())
+}
+
+fn follows_comment(token: &SyntaxToken) -> bool {
+    token.index() > 0
+        && token.parent().green().children()[token.index() - 1].kind() == SyntaxKind::Comment
+}
+
+fn parse_fragment(kind: SyntaxKind, text: &str) -> Option<Rc<GreenNode>> {
+    let source = Source {
+        code: text,
+        path: "",
+    };
+    let names = Interner::new();
+    let cursor = Cursor::new(Lexer::new(source).peekable(), source).with_syntax();
+    let mut parser = Parser::new(source, cursor, &names);
+    match kind {
+        SyntaxKind::Block => {
+            parser.parse_block().ok()?;
+        }
+        SyntaxKind::FunctionDefinition => {
+            parser.parse_program().ok()?;
+        }
+        _ => return None,
+    }
+    let root = parser.syntax()?.finish(SyntaxKind::Program);
+    match root.children() {
+        [GreenElement::Node(node)] if node.kind() == kind => Some(node.clone()),
+        _ => None,
+    }
+}
+
+fn replace(node: &SyntaxNode, green: Rc<GreenNode>) -> SyntaxNode {
+    match node.parent() {
+        Some(parent) => {
+            let parent_green = parent
+                .green()
+                .replace_child(node.index(), GreenElement::Node(green));
+            replace(parent, Rc::new(parent_green))
+        }
+        None => SyntaxNode::new_root(green),
+    }
+}
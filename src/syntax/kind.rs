@@ -0,0 +1,132 @@
+use std::fmt::{self, Display};
+
+use crate::lexer::token::TokenKind;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SyntaxKind {
+    Token(TokenKind),
+    Whitespace,
+    Comment,
+    Error,
+
+    Program,
+    ModuleDeclaration,
+    ImportDeclaration,
+    FunctionDefinition,
+    GenericParameters,
+    ArgumentList,
+    Argument,
+    ReturnType,
+    EnumDefinition,
+    Variant,
+
+    Block,
+    LetStatement,
+    IfStatement,
+    WhileStatement,
+    ForStatement,
+    ReturnStatement,
+    ExpressionStatement,
+
+    Literal,
+    Path,
+    TypeArguments,
+    Parenthesized,
+    Array,
+    Prefix,
+    Infix,
+    Ternary,
+    Range,
+    Call,
+    Member,
+    Index,
+    Lambda,
+    ParameterList,
+    Parameter,
+    MatchExpression,
+    MatchArm,
+
+    WildcardPattern,
+    LiteralPattern,
+    BindingPattern,
+    VariantPattern,
+
+    PathType,
+    UnitType,
+    TupleType,
+    ParenthesizedType,
+    ArrayType,
+    FunctionType,
+    ReferenceType,
+}
+
+impl SyntaxKind {
+    pub fn is_trivia(self) -> bool {
+        matches!(self, SyntaxKind::Whitespace | SyntaxKind::Comment)
+    }
+    pub fn is_statement(self) -> bool {
+        matches!(
+            self,
+            SyntaxKind::Block
+                | SyntaxKind::LetStatement
+                | SyntaxKind::IfStatement
+                | SyntaxKind::WhileStatement
+                | SyntaxKind::ForStatement
+                | SyntaxKind::ReturnStatement
+                | SyntaxKind::ExpressionStatement
+        )
+    }
+    pub fn is_expression(self) -> bool {
+        matches!(
+            self,
+            SyntaxKind::Literal
+                | SyntaxKind::Path
+                | SyntaxKind::Parenthesized
+                | SyntaxKind::Array
+                | SyntaxKind::Prefix
+                | SyntaxKind::Infix
+                | SyntaxKind::Ternary
+                | SyntaxKind::Range
+                | SyntaxKind::Call
+                | SyntaxKind::Member
+                | SyntaxKind::Index
+                | SyntaxKind::Lambda
+                | SyntaxKind::MatchExpression
+                | SyntaxKind::Block
+        )
+    }
+    pub fn is_pattern(self) -> bool {
+        matches!(
+            self,
+            SyntaxKind::WildcardPattern
+                | SyntaxKind::LiteralPattern
+                | SyntaxKind::BindingPattern
+                | SyntaxKind::VariantPattern
+        )
+    }
+    pub fn is_type(self) -> bool {
+        matches!(
+            self,
+            SyntaxKind::PathType
+                | SyntaxKind::UnitType
+                | SyntaxKind::TupleType
+                | SyntaxKind::ParenthesizedType
+                | SyntaxKind::ArrayType
+                | SyntaxKind::FunctionType
+                | SyntaxKind::ReferenceType
+        )
+    }
+}
+impl Display for SyntaxKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyntaxKind::Token(kind) => write!(f, "{:?}", kind),
+            kind => write!(f, "{:?}", kind),
+        }
+    }
+}
+impl From<TokenKind> for SyntaxKind {
+    fn from(kind: TokenKind) -> Self {
+        SyntaxKind::Token(kind)
+    }
+}
@@ -0,0 +1,494 @@
+use crate::{
+    common::{intern::Interner, source::Source, span::Span},
+    lexer::token::TokenKind,
+    parser::{
+        ast::{
+            calculate_span::CalculateSpan,
+            enumeration::{Enum, Variant},
+            expression::{Expression, MatchArm, Parameter},
+            function::{Argument, Function},
+            node::Node,
+            pattern::Pattern,
+            spanned::Spanned,
+            statement::{
+                ForStatement, IfStatement, LetStatement, ReturnStatement, Statement, WhileStatement,
+            },
+            types::TypeExpr,
+            Import, Program,
+        },
+        operator::{self, Fixity},
+    },
+};
+
+use super::{
+    kind::SyntaxKind,
+    red::{SyntaxElement, SyntaxNode, SyntaxToken},
+};
+
+pub fn lower<'a>(
+    source: Source<'a>,
+    names: &'a Interner,
+    root: &SyntaxNode,
+) -> Option<Program<'a>> {
+    Lowering { source, names }.program(root)
+}
+
+struct Lowering<'a> {
+    source: Source<'a>,
+    names: &'a Interner,
+}
+
+impl<'a> Lowering<'a> {
+    fn text(&self, span: Span) -> &'a str {
+        span.slice(self.source.code)
+    }
+    fn spanned(&self, span: Span) -> Spanned<&'a str> {
+        Spanned::new(self.text(span), span)
+    }
+    fn path(&self, node: &SyntaxNode) -> Spanned<&'a str> {
+        let segments = node
+            .tokens()
+            .iter()
+            .filter(|token| token.kind() == TokenKind::Identifier.into())
+            .map(|token| self.text(token.span()))
+            .collect::<Vec<_>>();
+        let path = match segments.as_slice() {
+            [segment] => segment,
+            _ => self.names.intern(segments.join("::")),
+        };
+        Spanned::new(path, node.span())
+    }
+    fn program(&self, root: &SyntaxNode) -> Option<Program<'a>> {
+        let mut program = Program {
+            path: self.source.path,
+            module: None,
+            imports: vec![],
+            functions: vec![],
+            enums: vec![],
+        };
+        for item in root.children() {
+            match item.kind() {
+                SyntaxKind::FunctionDefinition => program.functions.push(self.function(&item)?),
+                SyntaxKind::EnumDefinition => program.enums.push(self.enumeration(&item)?),
+                SyntaxKind::ImportDeclaration => {
+                    let path = item.token(TokenKind::String)?.span();
+                    program.imports.push(Import {
+                        path: self.spanned(Span::new(path.start + 1, path.end - 1)),
+                    });
+                }
+                SyntaxKind::ModuleDeclaration => {
+                    program.module = Some(self.spanned(item.token(TokenKind::Identifier)?.span()));
+                }
+                _ => return None,
+            }
+        }
+        Some(program)
+    }
+    fn function(&self, node: &SyntaxNode) -> Option<Function<'a>> {
+        let name = node.token(TokenKind::Identifier)?.span();
+        let mut generics = vec![];
+        let mut arguments = vec![];
+        let mut return_type = None;
+        let mut body = None;
+        for child in node.children() {
+            match child.kind() {
+                SyntaxKind::GenericParameters => {
+                    generics = child
+                        .tokens()
+                        .iter()
+                        .filter(|token| token.kind() == TokenKind::Identifier.into())
+                        .map(|token| self.spanned(token.span()))
+                        .collect();
+                }
+                SyntaxKind::ArgumentList => {
+                    for argument in child.children() {
+                        let name = argument.token(TokenKind::Identifier)?.span();
+                        let argument_type = self.type_expr(argument.children().first()?)?;
+                        arguments.push(Argument {
+                            name: self.text(name),
+                            span: Span::new(name.start, argument_type.calculate_span().end),
+                            argument_type,
+                        });
+                    }
+                }
+                SyntaxKind::ReturnType => {
+                    return_type = Some(self.type_expr(child.children().first()?)?);
+                }
+                SyntaxKind::Block => body = Some(self.statement(&child)?),
+                _ => return None,
+            }
+        }
+        Some(Function {
+            public: node.token(TokenKind::Pub).is_some(),
+            name: self.text(name),
+            name_span: name,
+            generics,
+            arguments,
+            body: body?,
+            return_type,
+            source: self.source,
+        })
+    }
+    fn enumeration(&self, node: &SyntaxNode) -> Option<Enum<'a>> {
+        let variants = node
+            .children()
+            .iter()
+            .map(|variant| {
+                let fields = match variant.token(TokenKind::LeftParenthesis) {
+                    Some(_) => Some(self.types(variant)?),
+                    None => None,
+                };
+                Some(Variant {
+                    name: self.text(variant.token(TokenKind::Identifier)?.span()),
+                    fields,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Enum {
+            name: self.text(node.token(TokenKind::Identifier)?.span()),
+            variants,
+        })
+    }
+    fn statement(&self, node: &SyntaxNode) -> Option<Node<'a>> {
+        let children = node.children();
+        Some(match node.kind() {
+            SyntaxKind::Block => Node::Block(Spanned::new(
+                children
+                    .iter()
+                    .map(|statement| self.statement(statement))
+                    .collect::<Option<Vec<_>>>()?,
+                node.span(),
+            )),
+            SyntaxKind::LetStatement => {
+                let value_type = match children.iter().find(|child| child.kind().is_type()) {
+                    Some(value_type) => Some(self.type_expr(value_type)?),
+                    None => None,
+                };
+                let init = match children.iter().find(|child| child.kind().is_expression()) {
+                    Some(init) => Some(Box::new(self.expression(init)?)),
+                    None => None,
+                };
+                Node::Statement(Statement::Let(Spanned::new(
+                    LetStatement {
+                        mutable: node.token(TokenKind::Mut).is_some(),
+                        name: self.text(node.token(TokenKind::Identifier)?.span()),
+                        value_type,
+                        init,
+                    },
+                    node.span(),
+                )))
+            }
+            SyntaxKind::IfStatement => {
+                let consequent = self.statement(children.get(1)?)?;
+                let alternative = match children.get(2) {
+                    Some(alternative) => Some(Box::new(self.statement(alternative)?)),
+                    None => None,
+                };
+                let span = Span::new(
+                    node.token(TokenKind::If)?.span().start,
+                    consequent.calculate_span().end,
+                );
+                Node::Statement(Statement::If(Spanned::new(
+                    IfStatement {
+                        test: Box::new(self.expression(children.first()?)?),
+                        consequent: Box::new(consequent),
+                        alternative,
+                    },
+                    span,
+                )))
+            }
+            SyntaxKind::WhileStatement => {
+                let body = self.statement(children.get(1)?)?;
+                let span = Span::new(
+                    node.token(TokenKind::While)?.span().start,
+                    body.calculate_span().end,
+                );
+                Node::Statement(Statement::While(Spanned::new(
+                    WhileStatement {
+                        test: Box::new(self.expression(children.first()?)?),
+                        body: Box::new(body),
+                    },
+                    span,
+                )))
+            }
+            SyntaxKind::ForStatement => {
+                let body = self.statement(children.get(1)?)?;
+                let span = Span::new(
+                    node.token(TokenKind::For)?.span().start,
+                    body.calculate_span().end,
+                );
+                Node::Statement(Statement::For(Spanned::new(
+                    ForStatement {
+                        binding: self.spanned(node.token(TokenKind::Identifier)?.span()),
+                        iterable: Box::new(self.expression(children.first()?)?),
+                        body: Box::new(body),
+                    },
+                    span,
+                )))
+            }
+            SyntaxKind::ReturnStatement => {
+                let value = match children.first() {
+                    Some(value) => Some(Box::new(self.expression(value)?)),
+                    None => None,
+                };
+                Node::Statement(Statement::Return(Spanned::new(
+                    ReturnStatement { value },
+                    node.span(),
+                )))
+            }
+            SyntaxKind::ExpressionStatement => self.expression(children.first()?)?,
+            _ => return None,
+        })
+    }
+    fn expression(&self, node: &SyntaxNode) -> Option<Node<'a>> {
+        let children = node.children();
+        let child = |index: usize| -> Option<Box<Node<'a>>> {
+            Some(Box::new(self.expression(children.get(index)?)?))
+        };
+        Some(match node.kind() {
+            SyntaxKind::Literal => {
+                let literal = node.tokens().into_iter().next()?;
+                let value = self.spanned(literal.span());
+                match token_kind(&literal)? {
+                    TokenKind::Integer => Node::Integer(value),
+                    TokenKind::Float => Node::Float(value),
+                    TokenKind::String => Node::String(value),
+                    _ => return None,
+                }
+            }
+            SyntaxKind::Path => Node::Identifier(self.path(node)),
+            SyntaxKind::Parenthesized => *child(0)?,
+            SyntaxKind::Block => self.statement(node)?,
+            SyntaxKind::Array => Node::Array(Spanned::new(
+                children
+                    .iter()
+                    .map(|element| self.expression(element))
+                    .collect::<Option<Vec<_>>>()?,
+                node.span(),
+            )),
+            SyntaxKind::Prefix => {
+                let operator = node.tokens().into_iter().next()?;
+                Node::Expression(Expression::Prefix {
+                    operator: Spanned::new(
+                        operator::operator(token_kind(&operator)?, Fixity::Prefix)?,
+                        operator.span(),
+                    ),
+                    value: child(0)?,
+                })
+            }
+            SyntaxKind::Infix => Node::Expression(Expression::Infix {
+                operator: operator::operator(token_kind(node.tokens().first()?)?, Fixity::Infix)?,
+                lhs: child(0)?,
+                rhs: child(1)?,
+            }),
+            SyntaxKind::Ternary => Node::Expression(Expression::Ternary {
+                test: child(0)?,
+                consequent: child(1)?,
+                alternative: child(2)?,
+            }),
+            SyntaxKind::Range => Node::Expression(Expression::Range {
+                start: child(0)?,
+                end: child(1)?,
+                inclusive: node.token(TokenKind::DotDotEqual).is_some(),
+            }),
+            SyntaxKind::Call => {
+                let generics = match children
+                    .iter()
+                    .find(|child| child.kind() == SyntaxKind::TypeArguments)
+                {
+                    Some(generics) => Some(Spanned::new(self.types(generics)?, generics.span())),
+                    None => None,
+                };
+                let arguments = children.last()?;
+                Node::Expression(Expression::Call {
+                    callee: child(0)?,
+                    generics,
+                    arguments: Spanned::new(
+                        arguments
+                            .children()
+                            .iter()
+                            .map(|argument| self.expression(argument))
+                            .collect::<Option<Vec<_>>>()?,
+                        arguments.span(),
+                    ),
+                })
+            }
+            SyntaxKind::Member => Node::Expression(Expression::Member {
+                target: child(0)?,
+                name: self.spanned(node.token(TokenKind::Identifier)?.span()),
+            }),
+            SyntaxKind::Index => Node::Expression(Expression::Index {
+                target: child(0)?,
+                index: Spanned::new(
+                    child(1)?,
+                    Span::new(
+                        node.token(TokenKind::LeftSquareBracket)?.span().start,
+                        node.token(TokenKind::RightSquareBracket)?.span().end,
+                    ),
+                ),
+            }),
+            SyntaxKind::Lambda => {
+                let parameters = children.first()?;
+                Node::Expression(Expression::Lambda {
+                    parameters: Spanned::new(
+                        parameters
+                            .children()
+                            .iter()
+                            .map(|parameter| {
+                                let parameter_type = match parameter.children().first() {
+                                    Some(parameter_type) => Some(self.type_expr(parameter_type)?),
+                                    None => None,
+                                };
+                                Some(Parameter {
+                                    name: self
+                                        .spanned(parameter.token(TokenKind::Identifier)?.span()),
+                                    parameter_type,
+                                })
+                            })
+                            .collect::<Option<Vec<_>>>()?,
+                        parameters.span(),
+                    ),
+                    body: child(1)?,
+                })
+            }
+            SyntaxKind::MatchExpression => Node::Expression(Expression::Match {
+                keyword: node.token(TokenKind::Match)?.span(),
+                value: child(0)?,
+                arms: Spanned::new(
+                    children[1..]
+                        .iter()
+                        .map(|arm| {
+                            let parts = arm.children();
+                            Some(MatchArm {
+                                pattern: self.pattern(parts.first()?)?,
+                                body: Box::new(self.expression(parts.get(1)?)?),
+                            })
+                        })
+                        .collect::<Option<Vec<_>>>()?,
+                    Span::new(
+                        node.token(TokenKind::LeftCurlyBrace)?.span().start,
+                        node.token(TokenKind::RightCurlyBrace)?.span().end,
+                    ),
+                ),
+            }),
+            _ => return None,
+        })
+    }
+    fn pattern(&self, node: &SyntaxNode) -> Option<Pattern<'a>> {
+        Some(match node.kind() {
+            SyntaxKind::WildcardPattern => Pattern::Wildcard(node.span()),
+            SyntaxKind::LiteralPattern => {
+                let tokens = node.tokens();
+                let literal = tokens.last()?;
+                let value = match tokens.as_slice() {
+                    [_] => self.spanned(node.span()),
+                    _ => Spanned::new(
+                        self.names.intern(format!("-{}", self.text(literal.span()))),
+                        node.span(),
+                    ),
+                };
+                match token_kind(literal)? {
+                    TokenKind::Integer => Pattern::Integer(value),
+                    TokenKind::Float => Pattern::Float(value),
+                    _ => return None,
+                }
+            }
+            SyntaxKind::BindingPattern => Pattern::Binding(self.spanned(node.span())),
+            SyntaxKind::VariantPattern => {
+                let children = node.children();
+                let fields = match (
+                    node.token(TokenKind::LeftParenthesis),
+                    node.token(TokenKind::RightParenthesis),
+                ) {
+                    (Some(lp), Some(rp)) => Some(Spanned::new(
+                        children[1..]
+                            .iter()
+                            .map(|field| self.pattern(field))
+                            .collect::<Option<Vec<_>>>()?,
+                        Span::new(lp.span().start, rp.span().end),
+                    )),
+                    _ => None,
+                };
+                Pattern::Variant {
+                    path: self.path(children.first()?),
+                    fields,
+                }
+            }
+            _ => return None,
+        })
+    }
+    fn types(&self, node: &SyntaxNode) -> Option<Vec<TypeExpr<'a>>> {
+        node.children()
+            .iter()
+            .map(|element| self.type_expr(element))
+            .collect()
+    }
+    fn type_expr(&self, node: &SyntaxNode) -> Option<TypeExpr<'a>> {
+        let children = node.children();
+        Some(match node.kind() {
+            SyntaxKind::PathType => {
+                let generics = match children.get(1) {
+                    Some(generics) => Some(Spanned::new(self.types(generics)?, generics.span())),
+                    None => None,
+                };
+                TypeExpr::Path {
+                    path: self.path(children.first()?),
+                    generics,
+                }
+            }
+            SyntaxKind::UnitType => TypeExpr::Unit(node.span()),
+            SyntaxKind::TupleType => TypeExpr::Tuple(Spanned::new(self.types(node)?, node.span())),
+            SyntaxKind::ParenthesizedType => self.type_expr(children.first()?)?,
+            SyntaxKind::ArrayType => TypeExpr::Array {
+                element: Box::new(self.type_expr(children.first()?)?),
+                length: node
+                    .token(TokenKind::Integer)
+                    .map(|length| self.spanned(length.span())),
+                span: node.span(),
+            },
+            SyntaxKind::FunctionType => {
+                let mut arguments = vec![];
+                let mut return_type = None;
+                let mut returns = false;
+                for child in node.children_with_tokens() {
+                    match child {
+                        SyntaxElement::Token(token)
+                            if token_kind(&token) == Some(TokenKind::Arrow) =>
+                        {
+                            returns = true;
+                        }
+                        SyntaxElement::Node(child) if returns => {
+                            return_type = Some(Box::new(self.type_expr(&child)?));
+                        }
+                        SyntaxElement::Node(child) => arguments.push(self.type_expr(&child)?),
+                        SyntaxElement::Token(_) => {}
+                    }
+                }
+                TypeExpr::Function {
+                    keyword: node.token(TokenKind::Fn)?.span(),
+                    arguments: Spanned::new(
+                        arguments,
+                        Span::new(
+                            node.token(TokenKind::LeftParenthesis)?.span().start,
+                            node.token(TokenKind::RightParenthesis)?.span().end,
+                        ),
+                    ),
+                    return_type,
+                }
+            }
+            SyntaxKind::ReferenceType => TypeExpr::Reference {
+                ampersand: node.token(TokenKind::Ampersand)?.span(),
+                mutable: node.token(TokenKind::Mut).is_some(),
+                inner: Box::new(self.type_expr(children.first()?)?),
+            },
+            _ => return None,
+        })
+    }
+}
+
+fn token_kind(token: &SyntaxToken) -> Option<TokenKind> {
+    match token.kind() {
+        SyntaxKind::Token(kind) => Some(kind),
+        _ => None,
+    }
+}
@@ -0,0 +1,38 @@
+use std::rc::Rc;
+
+use crate::{
+    common::{
+        error::{Error, Result},
+        intern::Interner,
+        source::Source,
+    },
+    lexer::Lexer,
+    parser::{cursor::Cursor, Parser},
+};
+
+use self::{kind::SyntaxKind, red::SyntaxNode};
+
+pub mod builder;
+pub mod green;
+pub mod incremental;
+pub mod kind;
+pub mod lower;
+pub mod red;
+
+pub fn parse<'a>(source: Source<'a>, names: &'a Interner) -> Result<'a, SyntaxNode> {
+    match recover(source, names) {
+        (tree, None) => Ok(tree),
+        (_, Some(err)) => Err(err),
+    }
+}
+pub fn recover<'a>(
+    source: Source<'a>,
+    names: &'a Interner,
+) -> (SyntaxNode, Option<Box<Error<'a>>>) {
+    let cursor = Cursor::new(Lexer::new(source).peekable(), source).with_syntax();
+    let mut parser = Parser::new(source, cursor, names);
+    let error = parser.parse_program().err();
+    let builder = parser.syntax().expect("syntax recording is enabled");
+    let tree = SyntaxNode::new_root(Rc::new(builder.finish(SyntaxKind::Program)));
+    (tree, error)
+}
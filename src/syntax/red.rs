@@ -0,0 +1,246 @@
+use std::{
+    fmt::{self, Debug, Display},
+    rc::Rc,
+};
+
+use crate::common::{
+    json::{Json, ToJson},
+    span::Span,
+};
+
+use super::{
+    green::{GreenElement, GreenNode, GreenToken},
+    kind::SyntaxKind,
+};
+
+#[derive(Clone)]
+pub struct SyntaxNode {
+    data: Rc<NodeData>,
+}
+struct NodeData {
+    green: Rc<GreenNode>,
+    offset: usize,
+    index: usize,
+    parent: Option<SyntaxNode>,
+}
+#[derive(Clone)]
+pub struct SyntaxToken {
+    green: Rc<GreenToken>,
+    offset: usize,
+    index: usize,
+    parent: SyntaxNode,
+}
+#[derive(Clone, Debug)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+impl SyntaxNode {
+    pub fn new_root(green: Rc<GreenNode>) -> Self {
+        Self::new(green, 0, 0, None)
+    }
+    fn new(green: Rc<GreenNode>, offset: usize, index: usize, parent: Option<SyntaxNode>) -> Self {
+        Self {
+            data: Rc::new(NodeData {
+                green,
+                offset,
+                index,
+                parent,
+            }),
+        }
+    }
+    pub fn kind(&self) -> SyntaxKind {
+        self.data.green.kind()
+    }
+    pub fn green(&self) -> &Rc<GreenNode> {
+        &self.data.green
+    }
+    pub fn parent(&self) -> Option<&SyntaxNode> {
+        self.data.parent.as_ref()
+    }
+    pub fn index(&self) -> usize {
+        self.data.index
+    }
+    pub fn span(&self) -> Span {
+        Span::new(self.data.offset, self.data.offset + self.data.green.width())
+    }
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        self.data.green.write_text(&mut text);
+        text
+    }
+    pub fn children_with_tokens(&self) -> Vec<SyntaxElement> {
+        let mut offset = self.data.offset;
+        let mut children = vec![];
+        for (index, child) in self.data.green.children().iter().enumerate() {
+            children.push(match child {
+                GreenElement::Node(node) => SyntaxElement::Node(SyntaxNode::new(
+                    node.clone(),
+                    offset,
+                    index,
+                    Some(self.clone()),
+                )),
+                GreenElement::Token(token) => SyntaxElement::Token(SyntaxToken {
+                    green: token.clone(),
+                    offset,
+                    index,
+                    parent: self.clone(),
+                }),
+            });
+            offset += child.width();
+        }
+        children
+    }
+    pub fn children(&self) -> Vec<SyntaxNode> {
+        self.children_with_tokens()
+            .into_iter()
+            .filter_map(|child| match child {
+                SyntaxElement::Node(node) => Some(node),
+                SyntaxElement::Token(_) => None,
+            })
+            .collect()
+    }
+    pub fn tokens(&self) -> Vec<SyntaxToken> {
+        self.children_with_tokens()
+            .into_iter()
+            .filter_map(|child| match child {
+                SyntaxElement::Token(token) if !token.kind().is_trivia() => Some(token),
+                _ => None,
+            })
+            .collect()
+    }
+    pub fn token(&self, kind: impl Into<SyntaxKind>) -> Option<SyntaxToken> {
+        let kind = kind.into();
+        self.tokens().into_iter().find(|token| token.kind() == kind)
+    }
+    pub fn descendants(&self) -> Vec<SyntaxNode> {
+        let mut nodes = vec![self.clone()];
+        for child in self.children() {
+            nodes.extend(child.descendants());
+        }
+        nodes
+    }
+    pub fn descendant_tokens(&self) -> Vec<SyntaxToken> {
+        let mut tokens = vec![];
+        for child in self.children_with_tokens() {
+            match child {
+                SyntaxElement::Node(node) => tokens.extend(node.descendant_tokens()),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+        tokens
+    }
+    pub fn covering_node(&self, span: Span) -> SyntaxNode {
+        self.children()
+            .into_iter()
+            .find(|child| child.span().start <= span.start && span.end <= child.span().end)
+            .map_or_else(|| self.clone(), |child| child.covering_node(span))
+    }
+    fn write_tree(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let span = self.span();
+        writeln!(
+            f,
+            "{}{}@{}..{}",
+            "  ".repeat(depth),
+            self.kind(),
+            span.start,
+            span.end
+        )?;
+        for child in self.children_with_tokens() {
+            match child {
+                SyntaxElement::Node(node) => node.write_tree(f, depth + 1)?,
+                SyntaxElement::Token(token) => {
+                    writeln!(f, "{}{:?}", "  ".repeat(depth + 1), token)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+impl SyntaxToken {
+    pub fn kind(&self) -> SyntaxKind {
+        self.green.kind()
+    }
+    pub fn text(&self) -> &str {
+        self.green.text()
+    }
+    pub fn span(&self) -> Span {
+        Span::new(self.offset, self.offset + self.green.width())
+    }
+    pub fn green(&self) -> &Rc<GreenToken> {
+        &self.green
+    }
+    pub fn parent(&self) -> &SyntaxNode {
+        &self.parent
+    }
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+impl SyntaxElement {
+    pub fn kind(&self) -> SyntaxKind {
+        match self {
+            SyntaxElement::Node(node) => node.kind(),
+            SyntaxElement::Token(token) => token.kind(),
+        }
+    }
+    pub fn span(&self) -> Span {
+        match self {
+            SyntaxElement::Node(node) => node.span(),
+            SyntaxElement::Token(token) => token.span(),
+        }
+    }
+}
+impl Debug for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_tree(f, 0)
+    }
+}
+impl Display for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text())
+    }
+}
+impl Debug for SyntaxToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = self.span();
+        write!(
+            f,
+            "{}@{}..{} {:?}",
+            self.kind(),
+            span.start,
+            span.end,
+            self.text()
+        )
+    }
+}
+impl ToJson for SyntaxNode {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("kind", self.kind().to_string().to_json()),
+            ("span", self.span().to_json()),
+            (
+                "children",
+                Json::Array(
+                    self.children_with_tokens()
+                        .iter()
+                        .map(|child| match child {
+                            SyntaxElement::Node(node) => node.to_json(),
+                            SyntaxElement::Token(token) => token.to_json(),
+                        })
+                        .collect(),
+                ),
+            ),
+        ])
+    }
+}
+impl ToJson for SyntaxToken {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("kind", self.kind().to_string().to_json()),
+            ("text", self.text().to_json()),
+            ("span", self.span().to_json()),
+        ])
+    }
+}
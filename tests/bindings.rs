@@ -0,0 +1,154 @@
+mod common;
+
+use lang::common::json::Json;
+
+use common::{field, fixture, json_lines, lang, span};
+
+fn diagnostics(name: &str) -> Vec<Json> {
+    let path = fixture("bindings", name);
+    json_lines(&lang([
+        path.as_os_str(),
+        "--format".as_ref(),
+        "json".as_ref(),
+    ]))
+}
+
+fn kinds(diagnostics: &[Json]) -> Vec<(&str, &str, (usize, usize))> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| {
+            (
+                field(diagnostic, "severity"),
+                field(diagnostic, "kind"),
+                span(diagnostic),
+            )
+        })
+        .collect()
+}
+
+fn output(name: &str) -> String {
+    lang([fixture("bindings", name)])
+}
+
+#[test]
+fn assigning_an_immutable_let_suggests_mut() {
+    let diagnostics = diagnostics("immutable_let.lang");
+    assert_eq!(
+        kinds(&diagnostics),
+        [("error", "ImmutableAssignment", (41, 58))]
+    );
+    assert_eq!(
+        diagnostics[0].get("labels").expect("labels").to_string(),
+        r#"[{"span":{"start":22,"end":36},"message":"`count` is declared immutable here"}]"#
+    );
+    assert_eq!(
+        diagnostics[0]
+            .get("suggestions")
+            .expect("suggestions")
+            .to_string(),
+        r#"[{"message":"make the binding mutable","span":{"start":25,"end":25},"replacement":" mut"}]"#
+    );
+}
+
+#[test]
+fn assigning_an_argument_points_at_the_argument() {
+    let diagnostics = diagnostics("immutable_argument.lang");
+    assert_eq!(
+        kinds(&diagnostics),
+        [("error", "ImmutableAssignment", (39, 56))]
+    );
+    assert_eq!(
+        diagnostics[0].get("labels").expect("labels").to_string(),
+        r#"[{"span":{"start":14,"end":24},"message":"`value` is declared immutable here"}]"#
+    );
+    assert_eq!(
+        diagnostics[0].get("suggestions").expect("suggestions"),
+        &Json::Array(vec![])
+    );
+}
+
+#[test]
+fn assignment_on_both_branches_initializes() {
+    assert_eq!(kinds(&diagnostics("deferred_if.lang")), []);
+    assert_eq!(output("deferred_if.lang"), "-1\n");
+}
+
+#[test]
+fn assignment_on_one_branch_does_not_initialize() {
+    assert_eq!(
+        kinds(&diagnostics("uninitialized_if.lang")),
+        [("error", "UninitializedVariable", (86, 90))]
+    );
+}
+
+#[test]
+fn a_branch_that_returns_does_not_need_to_initialize() {
+    assert_eq!(kinds(&diagnostics("returning_branch.lang")), []);
+    assert_eq!(output("returning_branch.lang"), "1\n");
+}
+
+#[test]
+fn assignment_in_a_loop_body_does_not_initialize() {
+    assert_eq!(
+        kinds(&diagnostics("uninitialized_while.lang")),
+        [("error", "UninitializedVariable", (130, 134))]
+    );
+}
+
+#[test]
+fn assignment_in_a_loop_body_may_happen_twice() {
+    let diagnostics = diagnostics("immutable_while.lang");
+    assert_eq!(
+        kinds(&diagnostics),
+        [("error", "ImmutableAssignment", (79, 87))]
+    );
+    assert_eq!(
+        diagnostics[0].get("labels").expect("labels").to_string(),
+        r#"[{"span":{"start":22,"end":31},"message":"`last` is declared immutable here"}]"#
+    );
+}
+
+#[test]
+fn for_bindings_are_immutable_and_their_bodies_may_not_run() {
+    let diagnostics = diagnostics("for_binding.lang");
+    assert_eq!(
+        kinds(&diagnostics),
+        [
+            ("error", "ImmutableAssignment", (64, 73)),
+            ("error", "UninitializedVariable", (111, 115)),
+        ]
+    );
+    assert_eq!(
+        diagnostics[0].get("labels").expect("labels").to_string(),
+        r#"[{"span":{"start":44,"end":45},"message":"`i` is declared immutable here"}]"#
+    );
+}
+
+#[test]
+fn mut_bindings_never_reassigned_are_reported_per_scope() {
+    let diagnostics = diagnostics("unused_mut.lang");
+    assert_eq!(
+        kinds(&diagnostics),
+        [
+            ("warning", "UnusedMut", (150, 173)),
+            ("warning", "UnusedMut", (22, 40)),
+        ]
+    );
+    let removals = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            diagnostic
+                .get("suggestions")
+                .expect("suggestions")
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        removals,
+        [
+            r#"[{"message":"remove `mut`","span":{"start":153,"end":157},"replacement":""}]"#,
+            r#"[{"message":"remove `mut`","span":{"start":25,"end":29},"replacement":""}]"#,
+        ]
+    );
+    assert!(output("unused_mut.lang").ends_with("done\n3\n"));
+}
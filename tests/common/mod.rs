@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use lang::common::json::Json;
+
+pub fn fixture(directory: &str, name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join(directory)
+        .join(name)
+}
+
+pub fn command() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_lang"))
+}
+
+pub fn run(command: &mut Command) -> Output {
+    command.output().expect("compiler should run")
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).expect("output should be utf-8")
+}
+
+pub fn lang<I, S>(arguments: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    stdout(&run(command().args(arguments)))
+}
+
+pub fn json_lines(output: &str) -> Vec<Json> {
+    output
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .map(|line| Json::parse(line).unwrap_or_else(|err| panic!("{}\n{}", err, line)))
+        .collect()
+}
+
+pub fn field<'j>(json: &'j Json, key: &str) -> &'j str {
+    json.get(key)
+        .and_then(Json::as_str)
+        .unwrap_or_else(|| panic!("missing {} in {}", key, json))
+}
+
+pub fn span(json: &Json) -> (usize, usize) {
+    let number = |key: &str| match json.get("span").and_then(|span| span.get(key)) {
+        Some(Json::Number(number)) => *number as usize,
+        _ => panic!("missing span in {}", json),
+    };
+    (number("start"), number("end"))
+}
+
+pub struct Random(pub u64);
+
+impl Random {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
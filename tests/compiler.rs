@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+
+use lang::{
+    common::{diagnostic::Severity, error::ErrorKind, span::Span},
+    lexer::token::TokenKind,
+    linter::config::Config,
+    optimizer::OptimizationLevel,
+    syntax::incremental::{Reparse, TextEdit},
+    Compiler,
+};
+
+const PROGRAM: &str = "function main() {
+    let x = add::<int>(1, 2);
+}
+function add<T>(a: T, b: T) -> T {
+    return a + b;
+}
+";
+
+fn compiler_for(code: &str) -> Compiler {
+    let mut compiler = Compiler::new();
+    compiler
+        .add(PathBuf::from("main.lang"), code.to_string())
+        .expect("program has no imports");
+    compiler
+}
+
+#[test]
+fn stages_produce_typed_results() {
+    let compiler = compiler_for(PROGRAM);
+    let mut session = compiler.session();
+    let files = session.tokenize().expect("program should lex");
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].1[0].kind, TokenKind::Function);
+    let modules = session.parse().expect("program should parse");
+    assert_eq!(modules[0].program.functions.len(), 2);
+    let program = session.check(&modules).expect("program should check");
+    let lowered = session.lower(&program).expect("program should lower");
+    assert!(lowered
+        .functions
+        .iter()
+        .all(|function| function.generics.is_empty()));
+    let module = session.build(&lowered).expect("program should build");
+    assert!(!module.functions.is_empty());
+    assert!(session.run(&lowered).is_some());
+    assert!(session.diagnostics().is_empty());
+}
+
+#[test]
+fn failing_stages_leave_diagnostics() {
+    let compiler = compiler_for("function main() {\n    let x = 1;\n    println(y);\n}\n");
+    let mut session = compiler.session();
+    let modules = session.parse().expect("program should parse");
+    assert!(session.check(&modules).is_none());
+    let kinds = session
+        .take_diagnostics()
+        .into_iter()
+        .map(|diagnostic| (diagnostic.severity, diagnostic.kind))
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        kinds.as_slice(),
+        [(Severity::Error, ErrorKind::UndefinedVariable)]
+    ));
+    assert!(session.diagnostics().is_empty());
+
+    let compiler = compiler_for("function main() {\n    println(1)\n}\n");
+    let mut session = compiler.session();
+    assert!(session.parse().is_none());
+    assert_eq!(session.diagnostics()[0].kind.code(), "E0005");
+}
+
+#[test]
+fn overrides_apply_to_every_session() {
+    let code = "function main() {\n    return;\n    println(1);\n}\n";
+    let mut compiler = compiler_for(code);
+    let mut session = compiler.session();
+    let modules = session.parse().expect("program should parse");
+    assert!(session.check(&modules).is_some());
+    assert_eq!(session.diagnostics()[0].severity, Severity::Warning);
+    compiler.set("UnreachableCode", Severity::Error);
+    let mut session = compiler.session();
+    let modules = session.parse().expect("program should parse");
+    assert!(session.check(&modules).is_none());
+}
+
+#[test]
+fn overrides_only_change_lints() {
+    let code = "function main() {\n    let x;\n    println(x);\n}\n";
+    let mut compiler = compiler_for(code);
+    compiler.set("E0015", Severity::Warning);
+    compiler.set("warnings", Severity::Allow);
+    let mut session = compiler.session();
+    let modules = session.parse().expect("program should parse");
+    assert!(session.check(&modules).is_none());
+    assert_eq!(
+        session.diagnostics()[0].kind,
+        ErrorKind::UninitializedVariable
+    );
+    assert_eq!(session.diagnostics()[0].severity, Severity::Error);
+}
+
+#[test]
+fn backend_stages_produce_typed_results() {
+    let compiler = compiler_for(PROGRAM);
+    let mut session = compiler.session();
+    let modules = session.parse().expect("program should parse");
+    let program = session.check(&modules).expect("program should check");
+    let lowered = session.lower(&program).expect("program should lower");
+    let module = session.build(&lowered).expect("program should build");
+    let mut passes = vec![];
+    let module = session
+        .optimize(module, OptimizationLevel::Full, |pass, _| {
+            passes.push(pass.to_string())
+        })
+        .expect("program should optimize");
+    assert_eq!(passes, ["propagate", "cse", "licm", "propagate", "dce"]);
+    let assembly = session.emit_asm(&module).expect("program should compile");
+    assert!(assembly.contains("main:"));
+    assert!(session.diagnostics().is_empty());
+}
+
+#[test]
+fn backend_failures_are_error_diagnostics() {
+    let compiler = compiler_for("function main() {\n    let f = |x: int| x;\n    println(f);\n}\n");
+    let mut session = compiler.session();
+    let modules = session.parse().expect("program should parse");
+    let program = session.check(&modules).expect("program should check");
+    let lowered = session.lower(&program).expect("program should lower");
+    let module = session.build(&lowered).expect("program should build");
+    let module = session
+        .optimize(module, OptimizationLevel::None, |_, _| {})
+        .expect("program should optimize");
+    assert!(session.emit_asm(&module).is_none());
+    assert!(session.has_errors());
+    assert!(matches!(
+        session.diagnostics()[0].kind,
+        ErrorKind::CodegenFailed { .. }
+    ));
+}
+
+#[test]
+fn lint_and_syntax_stages_report_through_the_session() {
+    let compiler = compiler_for(PROGRAM);
+    let mut session = compiler.session();
+    let modules = session.parse().expect("program should parse");
+    session.lint(&modules, &Config::default());
+    assert_eq!(session.diagnostics()[0].kind.name(), "UnusedVariable");
+    assert_eq!(session.unreported().len(), 1);
+    assert!(session.unreported().is_empty());
+
+    let edit = TextEdit {
+        span: Span::new(4, 4),
+        replacement: "@".to_string(),
+    };
+    let files = session.syntax(&[edit]);
+    assert_eq!(files[0].reparses, [Reparse::Full]);
+    assert!(files[0].source.code.starts_with("func@tion"));
+    let errors = session.unreported();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind.name(), "UnexpectedToken");
+    assert_eq!(errors[0].source.code, files[0].source.code);
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use std::path::Path;
+
+use lang::common::json::Json;
+
+use common::{fixture, json_lines};
+
+fn lang(arguments: &[&str]) -> String {
+    let path = fixture("diagnostics", "bindings.lang");
+    common::lang(
+        [path.as_path()]
+            .into_iter()
+            .chain(arguments.iter().map(Path::new)),
+    )
+}
+
+fn diagnostics(arguments: &[&str]) -> Vec<Json> {
+    let mut arguments = arguments.to_vec();
+    arguments.extend(["--format", "json"]);
+    json_lines(&lang(&arguments))
+}
+
+fn severities(diagnostics: &[Json]) -> Vec<(String, String)> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let field = |key| diagnostic.get(key).and_then(Json::as_str).unwrap_or("");
+            (field("kind").to_string(), field("severity").to_string())
+        })
+        .collect()
+}
+
+fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    expected
+        .iter()
+        .map(|(kind, severity)| (kind.to_string(), severity.to_string()))
+        .collect()
+}
+
+#[test]
+fn passes_collect_warnings_and_errors_with_labels_and_suggestions() {
+    let diagnostics = diagnostics(&[]);
+    assert_eq!(
+        severities(&diagnostics),
+        pairs(&[
+            ("UnreachableCode", "warning"),
+            ("ImmutableAssignment", "error"),
+            ("UnusedMut", "warning"),
+        ])
+    );
+    let unreachable = diagnostics[0].get("labels").expect("labels");
+    assert_eq!(
+        unreachable.to_string(),
+        r#"[{"span":{"start":87,"end":94},"message":"any code following this statement is unreachable"}]"#
+    );
+    let immutable = &diagnostics[1];
+    assert_eq!(
+        immutable.get("labels").expect("labels").to_string(),
+        r#"[{"span":{"start":41,"end":51},"message":"`b` is declared immutable here"}]"#
+    );
+    assert_eq!(
+        immutable
+            .get("suggestions")
+            .expect("suggestions")
+            .to_string(),
+        r#"[{"message":"make the binding mutable","span":{"start":44,"end":44},"replacement":" mut"}]"#
+    );
+    let text = lang(&[]);
+    assert!(
+        text.contains("error[E0014]: ImmutableAssignment at "),
+        "{}",
+        text
+    );
+    assert!(text.contains("    41.51: `b` is declared immutable here\n"));
+    assert!(text.contains("    help: remove `mut`: remove 25.29"));
+    assert!(!text.contains("\n4\n"), "errors should stop the program");
+}
+
+#[test]
+fn command_line_overrides_change_lint_severities() {
+    assert_eq!(
+        severities(&diagnostics(&["-D", "warnings", "-W", "UnusedMut"])),
+        pairs(&[
+            ("UnreachableCode", "error"),
+            ("ImmutableAssignment", "error"),
+            ("UnusedMut", "warning"),
+        ])
+    );
+    assert_eq!(
+        severities(&diagnostics(&["-D", "E0016"])),
+        pairs(&[
+            ("UnreachableCode", "warning"),
+            ("ImmutableAssignment", "error"),
+            ("UnusedMut", "error"),
+        ])
+    );
+    assert_eq!(
+        lang(&["-D", "unused"]),
+        "unknown diagnostic `unused` for `-D`\n"
+    );
+}
+
+#[test]
+fn errors_cannot_be_downgraded() {
+    for name in ["E0014", "ImmutableAssignment"] {
+        let output = common::run(
+            common::command()
+                .arg(fixture("diagnostics", "bindings.lang"))
+                .args(["-W", name]),
+        );
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(
+            common::stdout(&output),
+            "`ImmutableAssignment` is an error and cannot be changed by `-W`\n"
+        );
+    }
+}
+
+#[test]
+fn errors_set_the_exit_status() {
+    let run = |path: &Path, arguments: &[&str]| {
+        common::run(common::command().arg(path).args(arguments))
+            .status
+            .code()
+    };
+    let bindings = fixture("diagnostics", "bindings.lang");
+    assert_eq!(run(&bindings, &[]), Some(1));
+    assert_eq!(run(&bindings, &["--emit-ir"]), Some(1));
+    let program = fixture("flow", "if_else.lang");
+    assert_eq!(run(&program, &[]), Some(0));
+    assert_eq!(run(&program, &["-D", "warnings"]), Some(0));
+    assert_eq!(run(&program, &["--format", "xml"]), Some(1));
+    assert_eq!(run(&fixture("flow", "missing.lang"), &[]), Some(1));
+    let builtin = fixture("golden", "builtin_type.lang");
+    assert_eq!(run(&builtin, &["--emit-asm"]), Some(1));
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use std::collections::HashSet;
+
+use lang::{
+    common::{error::ErrorKind, explain::EXPLANATIONS, json::Json},
+    lexer::token::TokenKind,
+};
+
+use common::{field, lang};
+
+#[test]
+fn every_code_has_an_explanation() {
+    let listing = lang(["explain", "--format", "json"]);
+    let mut codes = HashSet::new();
+    for line in listing.lines() {
+        let entry = Json::parse(line).expect("explanation should be json");
+        let code = field(&entry, "code").to_string();
+        assert!(!field(&entry, "explanation").is_empty(), "{}", code);
+        let text = lang(["explain", &code.to_lowercase()]);
+        assert!(
+            text.starts_with(&format!("{} {}\n\n", code, field(&entry, "name"))),
+            "{}",
+            text
+        );
+        assert!(codes.insert(code));
+    }
+    assert_eq!(codes.len(), EXPLANATIONS.len());
+    assert!(lang(["explain", "E9999"]).starts_with("unknown error code"));
+}
+
+fn sample(kind: &ErrorKind) -> usize {
+    match kind {
+        ErrorKind::UnknownCharacter { .. } => 0,
+        ErrorKind::MalformedNumber => 1,
+        ErrorKind::InvalidIdentifierStart { .. } => 2,
+        ErrorKind::UnterminatedString => 3,
+        ErrorKind::AssignmentInCondition => 4,
+        ErrorKind::UnexpectedToken { .. } => 5,
+        ErrorKind::UnexpectedEndOfInput => 6,
+        ErrorKind::UnknownEnum => 7,
+        ErrorKind::UnknownVariant => 8,
+        ErrorKind::WrongNumberOfFields { .. } => 9,
+        ErrorKind::NonExhaustiveMatch { .. } => 10,
+        ErrorKind::UnreachablePattern => 11,
+        ErrorKind::MissingReturn => 12,
+        ErrorKind::UnreachableCode => 13,
+        ErrorKind::ImmutableAssignment => 14,
+        ErrorKind::UninitializedVariable => 15,
+        ErrorKind::UnusedMut => 16,
+        ErrorKind::UndefinedVariable => 17,
+        ErrorKind::UndefinedFunction => 18,
+        ErrorKind::TypeMismatch => 19,
+        ErrorKind::WrongNumberOfArguments { .. } => 20,
+        ErrorKind::IndexOutOfBounds { .. } => 21,
+        ErrorKind::InvalidAssignmentTarget => 22,
+        ErrorKind::DivisionByZero => 23,
+        ErrorKind::IntegerOverflow => 24,
+        ErrorKind::WrongNumberOfTypeArguments { .. } => 25,
+        ErrorKind::CannotInferTypeArguments => 26,
+        ErrorKind::ModuleNotFound => 27,
+        ErrorKind::ModuleNotImported => 28,
+        ErrorKind::ImportCycle { .. } => 29,
+        ErrorKind::DuplicateModule { .. } => 30,
+        ErrorKind::PrivateFunction => 31,
+        ErrorKind::InvalidConversion => 32,
+        ErrorKind::AssertionFailed => 33,
+        ErrorKind::Io { .. } => 34,
+        ErrorKind::UnusedVariable { .. } => 35,
+        ErrorKind::UnusedFunction { .. } => 36,
+        ErrorKind::UnusedArgument { .. } => 37,
+        ErrorKind::NonSnakeCaseName { .. } => 38,
+        ErrorKind::RedundantParentheses => 39,
+        ErrorKind::SelfComparison { .. } => 40,
+        ErrorKind::EmptyBlock { .. } => 41,
+        ErrorKind::MalformedSyntaxTree => 42,
+        ErrorKind::InvalidIr { .. } => 43,
+        ErrorKind::CodegenFailed { .. } => 44,
+        ErrorKind::RangeTooLarge { .. } => 45,
+        ErrorKind::InstantiationTooDeep { .. } => 46,
+        ErrorKind::NestingTooDeep => 47,
+    }
+}
+
+#[test]
+fn every_kind_has_a_distinct_explained_code() {
+    let kinds = [
+        ErrorKind::UnknownCharacter { char: '@' },
+        ErrorKind::MalformedNumber,
+        ErrorKind::InvalidIdentifierStart { char: '2' },
+        ErrorKind::UnterminatedString,
+        ErrorKind::AssignmentInCondition,
+        ErrorKind::UnexpectedToken {
+            expected: vec![TokenKind::Semicolon],
+            received: TokenKind::Comma,
+        },
+        ErrorKind::UnexpectedEndOfInput,
+        ErrorKind::UnknownEnum,
+        ErrorKind::UnknownVariant,
+        ErrorKind::WrongNumberOfFields {
+            expected: 0,
+            received: 0,
+        },
+        ErrorKind::NonExhaustiveMatch { missing: vec![] },
+        ErrorKind::UnreachablePattern,
+        ErrorKind::MissingReturn,
+        ErrorKind::UnreachableCode,
+        ErrorKind::ImmutableAssignment,
+        ErrorKind::UninitializedVariable,
+        ErrorKind::UnusedMut,
+        ErrorKind::UndefinedVariable,
+        ErrorKind::UndefinedFunction,
+        ErrorKind::TypeMismatch,
+        ErrorKind::WrongNumberOfArguments {
+            expected: 0,
+            received: 0,
+        },
+        ErrorKind::IndexOutOfBounds {
+            index: 0,
+            length: 0,
+        },
+        ErrorKind::InvalidAssignmentTarget,
+        ErrorKind::DivisionByZero,
+        ErrorKind::IntegerOverflow,
+        ErrorKind::WrongNumberOfTypeArguments {
+            expected: 0,
+            received: 0,
+        },
+        ErrorKind::CannotInferTypeArguments,
+        ErrorKind::ModuleNotFound,
+        ErrorKind::ModuleNotImported,
+        ErrorKind::ImportCycle { chain: vec![] },
+        ErrorKind::DuplicateModule {
+            name: String::new(),
+            first: String::new(),
+        },
+        ErrorKind::PrivateFunction,
+        ErrorKind::InvalidConversion,
+        ErrorKind::AssertionFailed,
+        ErrorKind::Io {
+            message: String::new(),
+        },
+        ErrorKind::UnusedVariable {
+            name: String::new(),
+        },
+        ErrorKind::UnusedFunction {
+            name: String::new(),
+        },
+        ErrorKind::UnusedArgument {
+            name: String::new(),
+        },
+        ErrorKind::NonSnakeCaseName {
+            name: String::new(),
+            suggestion: String::new(),
+        },
+        ErrorKind::RedundantParentheses,
+        ErrorKind::SelfComparison {
+            name: String::new(),
+        },
+        ErrorKind::EmptyBlock {
+            block: String::new(),
+        },
+        ErrorKind::MalformedSyntaxTree,
+        ErrorKind::InvalidIr {
+            message: String::new(),
+        },
+        ErrorKind::CodegenFailed {
+            message: String::new(),
+        },
+        ErrorKind::RangeTooLarge { start: 0, end: 0 },
+        ErrorKind::InstantiationTooDeep {
+            name: String::new(),
+        },
+        ErrorKind::NestingTooDeep,
+    ];
+    assert_eq!(kinds.len(), EXPLANATIONS.len());
+    let mut codes = HashSet::new();
+    for (index, kind) in kinds.iter().enumerate() {
+        assert_eq!(sample(kind), index, "{:?}", kind);
+        let code = kind.code();
+        assert!(codes.insert(code), "{} is used twice", code);
+        let explained = EXPLANATIONS
+            .iter()
+            .find(|(known, _, _)| *known == code)
+            .unwrap_or_else(|| panic!("{:?} has no explanation", kind));
+        assert_eq!(explained.1, kind.name());
+    }
+}
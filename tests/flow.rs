@@ -0,0 +1,102 @@
+mod common;
+
+use lang::common::json::Json;
+
+use common::{field, fixture, json_lines, lang, span};
+
+fn diagnostics(name: &str) -> Vec<(String, String, (usize, usize))> {
+    let path = fixture("flow", name);
+    json_lines(&lang([
+        path.as_os_str(),
+        "--format".as_ref(),
+        "json".as_ref(),
+    ]))
+    .iter()
+    .map(|diagnostic| {
+        (
+            field(diagnostic, "severity").to_string(),
+            field(diagnostic, "kind").to_string(),
+            span(diagnostic),
+        )
+    })
+    .collect()
+}
+
+fn diagnostic(
+    severity: &str,
+    kind: &str,
+    span: (usize, usize),
+) -> (String, String, (usize, usize)) {
+    (severity.to_string(), kind.to_string(), span)
+}
+
+#[test]
+fn if_without_alternative_can_fall_off_the_end() {
+    assert_eq!(
+        diagnostics("if_without_else.lang"),
+        [diagnostic("error", "MissingReturn", (81, 82))]
+    );
+}
+
+#[test]
+fn if_else_returning_on_both_branches_completes() {
+    assert_eq!(diagnostics("if_else.lang"), []);
+    assert_eq!(lang([fixture("flow", "if_else.lang")]), "-1\n");
+}
+
+#[test]
+fn if_else_returning_on_one_branch_is_missing_a_return() {
+    assert_eq!(
+        diagnostics("if_else_partial.lang"),
+        [diagnostic("error", "MissingReturn", (118, 119))]
+    );
+}
+
+#[test]
+fn while_body_return_does_not_count() {
+    assert_eq!(
+        diagnostics("while.lang"),
+        [diagnostic("error", "MissingReturn", (103, 104))]
+    );
+}
+
+#[test]
+fn match_returning_from_every_arm_completes() {
+    assert_eq!(diagnostics("match_arms.lang"), []);
+    assert_eq!(lang([fixture("flow", "match_arms.lang")]), "zero\n");
+}
+
+#[test]
+fn match_with_an_arm_that_falls_through_is_missing_a_return() {
+    assert_eq!(
+        diagnostics("match_partial.lang"),
+        [diagnostic("error", "MissingReturn", (126, 127))]
+    );
+}
+
+#[test]
+fn code_after_return_is_reported_once_per_block() {
+    let path = fixture("flow", "after_return.lang");
+    let diagnostics = json_lines(&lang([
+        path.as_os_str(),
+        "--format".as_ref(),
+        "json".as_ref(),
+    ]));
+    let reported = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let label = match diagnostic.get("labels") {
+                Some(Json::Array(labels)) => &labels[0],
+                _ => panic!("missing labels in {}", diagnostic),
+            };
+            (field(diagnostic, "kind"), span(diagnostic), span(label))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        reported,
+        [
+            ("UnreachableCode", (62, 76), (40, 57)),
+            ("UnreachableCode", (159, 169), (143, 150)),
+        ]
+    );
+}
@@ -0,0 +1,203 @@
+// Runs every `tests/golden/*.lang` program and compares each stage with its expectation:
+// `//@ check-tokens` and `//@ check-ast` compare `--emit-tokens` and `--emit-ast` with the
+// `.tokens` and `.ast` files, `//@ emit-ir` compares `--emit-ir -O2 --print-passes` with the
+// `.ir` file, program output is compared with the `.stdout` file, and every
+// warning or error must be announced by a `//~ ERROR text` annotation on its line (`//~^`
+// points one line up per `^`). `cargo test --test golden -- --bless` rewrites the files.
+mod common;
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use lang::common::json::Json;
+
+use common::{command, run, stdout};
+
+#[derive(Debug)]
+struct Annotation {
+    line: usize,
+    severity: String,
+    message: String,
+}
+
+struct Golden {
+    directory: PathBuf,
+    bless: bool,
+}
+
+impl Golden {
+    fn lang(&self, name: &str, arguments: &[&str]) -> String {
+        let output = run(command()
+            .current_dir(&self.directory)
+            .arg(name)
+            .args(arguments));
+        stdout(&output).replace(&format!("{}/", self.directory.display()), "")
+    }
+    fn compare(&self, name: &str, actual: &str, failures: &mut Vec<String>) {
+        let path = self.directory.join(name);
+        let expected = fs::read_to_string(&path).unwrap_or_default();
+        if expected == actual {
+            return;
+        }
+        if self.bless {
+            match actual.is_empty() {
+                true => fs::remove_file(&path).expect("stale expectation should be removable"),
+                false => fs::write(&path, actual).expect("expectation should be writable"),
+            }
+            return;
+        }
+        failures.push(format!(
+            "{} differs\n--- expected\n{}\n+++ actual\n{}",
+            name, expected, actual
+        ));
+    }
+    fn run(&self, name: &str) -> Vec<String> {
+        let code = fs::read_to_string(self.directory.join(name)).expect("readable program");
+        let stem = name.trim_end_matches(".lang");
+        let directives = code
+            .lines()
+            .filter_map(|line| line.strip_prefix("//@"))
+            .map(str::trim)
+            .collect::<Vec<_>>();
+        let mut failures = vec![];
+        if directives.contains(&"check-tokens") {
+            let tokens = self.lang(name, &["--emit-tokens"]);
+            self.compare(&format!("{}.tokens", stem), &tokens, &mut failures);
+        }
+        if directives.contains(&"check-ast") {
+            let ast = self.lang(name, &["--emit-ast", "--format", "json"]);
+            self.compare(&format!("{}.ast", stem), &ast, &mut failures);
+        }
+        if directives.contains(&"emit-ir") {
+            let ir = self.lang(name, &["--emit-ir", "-O2", "--print-passes"]);
+            self.compare(&format!("{}.ir", stem), &ir, &mut failures);
+        }
+        let mut stdout = String::new();
+        let mut diagnostics = vec![];
+        for line in self.lang(name, &["--format", "json"]).lines() {
+            match Json::parse(line) {
+                Ok(json) if json.get("severity").is_some() && json.get("code").is_some() => {
+                    diagnostics.push(json)
+                }
+                _ => {
+                    stdout.push_str(line);
+                    stdout.push('\n');
+                }
+            }
+        }
+        self.compare(&format!("{}.stdout", stem), &stdout, &mut failures);
+        check_annotations(&code, &diagnostics, &mut failures);
+        failures
+    }
+}
+
+fn annotations(code: &str) -> Vec<Annotation> {
+    let mut annotations = vec![];
+    for (index, line) in code.lines().enumerate() {
+        let Some((_, annotation)) = line.split_once("//~") else {
+            continue;
+        };
+        let carets = annotation.chars().take_while(|char| *char == '^').count();
+        let annotation = annotation[carets..].trim();
+        let (severity, message) = annotation.split_once(' ').unwrap_or((annotation, ""));
+        annotations.push(Annotation {
+            line: (index + 1).saturating_sub(carets),
+            severity: severity.to_lowercase(),
+            message: message.trim().to_string(),
+        });
+    }
+    annotations
+}
+
+fn text<'j>(json: &'j Json, key: &str) -> &'j str {
+    json.get(key).and_then(Json::as_str).unwrap_or_default()
+}
+
+fn check_annotations(code: &str, diagnostics: &[Json], failures: &mut Vec<String>) {
+    let mut annotations = annotations(code);
+    for diagnostic in diagnostics {
+        let severity = text(diagnostic, "severity");
+        if severity != "error" && severity != "warning" {
+            continue;
+        }
+        let start = match diagnostic.get("span").and_then(|span| span.get("start")) {
+            Some(Json::Number(start)) => *start as usize,
+            _ => 0,
+        };
+        let line = 1 + code
+            .chars()
+            .take(start)
+            .filter(|char| *char == '\n')
+            .count();
+        let summary = format!("{} {}", text(diagnostic, "code"), text(diagnostic, "kind"));
+        let found = annotations.iter().position(|annotation| {
+            annotation.line == line
+                && annotation.severity == severity
+                && summary.contains(&annotation.message)
+        });
+        match found {
+            Some(index) => {
+                annotations.remove(index);
+            }
+            None => failures.push(format!(
+                "unexpected {} `{}` on line {}",
+                severity, summary, line
+            )),
+        }
+    }
+    for annotation in annotations {
+        failures.push(format!(
+            "expected {} `{}` on line {} was not reported",
+            annotation.severity, annotation.message, annotation.line
+        ));
+    }
+}
+
+fn main() {
+    let arguments = env::args().skip(1).collect::<Vec<_>>();
+    let golden = Golden {
+        directory: Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden"),
+        bless: arguments.iter().any(|argument| argument == "--bless"),
+    };
+    let filters = arguments
+        .iter()
+        .filter(|argument| !argument.starts_with('-'))
+        .collect::<Vec<_>>();
+    let mut names = fs::read_dir(&golden.directory)
+        .expect("golden directory should exist")
+        .map(|entry| entry.expect("readable entry").file_name())
+        .filter_map(|name| name.into_string().ok())
+        .filter(|name| name.ends_with(".lang"))
+        .filter(|name| filters.is_empty() || filters.iter().any(|filter| name.contains(*filter)))
+        .collect::<Vec<_>>();
+    names.sort();
+    let mut failed = vec![];
+    for name in &names {
+        let failures = golden.run(name);
+        println!(
+            "test golden/{} ... {}",
+            name,
+            if failures.is_empty() { "ok" } else { "FAILED" }
+        );
+        if !failures.is_empty() {
+            failed.push((name, failures));
+        }
+    }
+    for (name, failures) in &failed {
+        println!("\n---- golden/{} ----", name);
+        for failure in failures {
+            println!("{}", failure);
+        }
+    }
+    println!(
+        "\ngolden result: {} passed; {} failed",
+        names.len() - failed.len(),
+        failed.len()
+    );
+    if !failed.is_empty() {
+        process::exit(1);
+    }
+}
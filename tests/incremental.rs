@@ -0,0 +1,142 @@
+mod common;
+
+use std::{env, fs, path::Path};
+
+use common::{fixture, Random};
+
+const SNIPPETS: [&str; 18] = [
+    "",
+    "x",
+    "_",
+    "7",
+    "2.5",
+    " ",
+    "\n",
+    "// note\n",
+    "\"text\"",
+    ";",
+    "(",
+    ")",
+    "{",
+    "}",
+    "a + 1",
+    "let y = 2;",
+    "println(a);",
+    "if(a == 1) { a = 2; }",
+];
+
+fn lang(arguments: &[String], path: &Path) -> String {
+    common::lang(
+        [path.as_os_str()]
+            .into_iter()
+            .chain(arguments.iter().map(|argument| argument.as_ref())),
+    )
+}
+
+fn sample(directory: &str, name: &str) -> String {
+    fs::read_to_string(fixture(directory, name)).expect("sample should be readable")
+}
+
+fn apply(text: &str, start: usize, end: usize, replacement: &str) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut output = chars[..start].iter().collect::<String>();
+    output.push_str(replacement);
+    output.extend(&chars[end..]);
+    output
+}
+
+fn random_edit(random: &mut Random, text: &str) -> (usize, usize, &'static str) {
+    // Edits stay before the final `}` so the program never ends mid-token.
+    let limit = text.chars().count() - 1;
+    let start = random.below(limit);
+    let end = (start + random.below(6)).min(limit);
+    (start, end, SNIPPETS[random.below(SNIPPETS.len())])
+}
+
+fn split(output: &str) -> (Vec<&str>, String) {
+    let reparses = output
+        .lines()
+        .filter_map(|line| line.strip_prefix("; reparse: "))
+        .collect();
+    let tree = output
+        .lines()
+        .filter(|line| !line.starts_with("; reparse: "))
+        .collect::<Vec<_>>()
+        .join("\n");
+    (reparses, tree)
+}
+
+#[test]
+fn incremental_reparse_matches_full_reparse() {
+    let directory = env::temp_dir().join(format!("lang-incremental-{}", std::process::id()));
+    fs::create_dir_all(&directory).expect("temporary directory");
+    let mut random = Random(0x2545_f491_4f6c_dd1d);
+    let mut seen = vec![];
+    for (folder, name) in [("json", "sample.lang"), ("syntax", "trivia.lang")] {
+        let original = sample(folder, name);
+        let input = directory.join("input.lang");
+        let edited = directory.join("edited.lang");
+        fs::write(&input, &original).expect("writable input");
+        for case in 0..40 {
+            let mut text = original.clone();
+            let mut arguments = vec!["--emit-cst".to_string()];
+            for _ in 0..1 + case % 3 {
+                let (start, end, replacement) = random_edit(&mut random, &text);
+                arguments.push("--edit".to_string());
+                arguments.push(format!("{}..{}={}", start, end, replacement));
+                text = apply(&text, start, end, replacement);
+            }
+            fs::write(&edited, &text).expect("writable edit");
+            let output = lang(&arguments, &input);
+            let (reparses, incremental) = split(&output);
+            let (_, full) = split(&lang(&["--emit-cst".to_string()], &edited));
+            assert_eq!(
+                incremental.replace("input.lang", "edited.lang"),
+                full,
+                "{} with {:?}",
+                name,
+                arguments
+            );
+            seen.extend(reparses.into_iter().map(str::to_string));
+        }
+    }
+    fs::remove_dir_all(&directory).ok();
+    for kind in ["Token", "Node(Block)", "Full"] {
+        assert!(
+            seen.iter().any(|reparse| reparse == kind),
+            "no {} reparse",
+            kind
+        );
+    }
+}
+
+#[test]
+fn edits_reparse_the_smallest_enclosing_region() {
+    let path = fixture("json", "sample.lang");
+    let text = sample("json", "sample.lang");
+    let offset = |needle: &str| text[..text.find(needle).expect("needle")].chars().count();
+    let reparses = |edit: String| {
+        let output = lang(
+            &["--emit-cst".to_string(), "--edit".to_string(), edit],
+            &path,
+        );
+        split(&output).0.join(",")
+    };
+    let count = offset("let mut a = 0;") + "let mut ".len();
+    assert_eq!(reparses(format!("{}..{}=b", count, count + 1)), "Token");
+    let body = offset("a = a + 1;");
+    assert_eq!(
+        reparses(format!("{}..{}=println(a); ", body, body)),
+        "Node(Block)"
+    );
+    let signature = offset("a: int, b: int) -> int {\n    return a + b;");
+    assert_eq!(
+        reparses(format!("{}..{}=c: int, ", signature, signature)),
+        "Node(FunctionDefinition)"
+    );
+    let item = offset("function max");
+    assert_eq!(
+        reparses(format!("{}..{}=function f() {{}}\n", item, item)),
+        "Full"
+    );
+}
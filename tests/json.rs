@@ -0,0 +1,119 @@
+mod common;
+
+use std::path::Path;
+
+use lang::common::json::Json;
+
+use common::{fixture, lang, span};
+
+fn run(arguments: &[&str], file: &str) -> String {
+    let path = fixture("json", file);
+    lang(arguments.iter().map(Path::new).chain([path.as_path()]))
+}
+
+fn round_trip(text: &str) -> Json {
+    let value = Json::parse(text).unwrap_or_else(|err| panic!("{}\n{}", err, text));
+    assert_eq!(
+        value.pretty(),
+        text.trim_end(),
+        "pretty output should round trip"
+    );
+    assert_eq!(
+        Json::parse(&value.to_string()).as_ref(),
+        Ok(&value),
+        "compact output should round trip"
+    );
+    value
+}
+
+fn assert_spanned(value: &Json) {
+    match value {
+        Json::Object(fields) => {
+            if value.get("kind").is_some() {
+                let (start, end) = span(value);
+                assert!(start <= end, "inverted span in {}", value);
+            }
+            fields.iter().for_each(|(_, value)| assert_spanned(value));
+        }
+        Json::Array(elements) => elements.iter().for_each(assert_spanned),
+        _ => {}
+    }
+}
+
+#[test]
+fn tokens_round_trip_and_match_source() {
+    let output = run(&["--format", "json", "--emit-tokens"], "sample.lang");
+    let files = round_trip(&output);
+    let Json::Array(files) = files else {
+        panic!("expected an array of files");
+    };
+    let Some(Json::Array(tokens)) = files[0].get("tokens") else {
+        panic!("expected tokens");
+    };
+    let source =
+        std::fs::read_to_string(fixture("json", "sample.lang")).expect("sample should be readable");
+    let chars = source.chars().collect::<Vec<_>>();
+    assert!(!tokens.is_empty());
+    for token in tokens {
+        let (start, end) = span(token);
+        let text = token.get("text").and_then(Json::as_str);
+        let expected = chars[start..end].iter().collect::<String>();
+        assert_eq!(
+            text,
+            Some(expected.as_str()),
+            "token text should match its span"
+        );
+    }
+}
+
+#[test]
+fn ast_round_trips_with_spans_on_every_node() {
+    let output = run(&["--format", "json", "--emit-ast"], "sample.lang");
+    let program = round_trip(&output);
+    let Some(Json::Array(functions)) = program.get("functions") else {
+        panic!("expected functions");
+    };
+    let names = functions
+        .iter()
+        .filter_map(|function| function.get("name").and_then(Json::as_str))
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["main", "max", "apply", "sum"]);
+    assert_spanned(&program);
+}
+
+#[test]
+fn errors_are_reported_as_diagnostics() {
+    let output = run(&["--format", "json"], "error.lang");
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 1, "{}", output);
+    let diagnostic = Json::parse(lines[0]).expect("diagnostic should be json");
+    assert_eq!(diagnostic.to_string(), lines[0]);
+    assert_eq!(
+        diagnostic.get("severity").and_then(Json::as_str),
+        Some("error")
+    );
+    assert_eq!(
+        diagnostic.get("kind").and_then(Json::as_str),
+        Some("UnexpectedToken")
+    );
+    assert!(diagnostic
+        .get("path")
+        .and_then(Json::as_str)
+        .is_some_and(|path| path.ends_with("error.lang")));
+    assert_eq!(span(&diagnostic), (30, 31));
+}
+
+#[test]
+fn strings_escape_and_parse_back() {
+    let value = Json::Object(vec![(
+        "text".to_string(),
+        Json::String("quote \" slash \\ newline \n tab \t bell \u{7}".to_string()),
+    )]);
+    assert_eq!(
+        value.to_string(),
+        r#"{"text":"quote \" slash \\ newline \n tab \t bell \u0007"}"#
+    );
+    assert_eq!(Json::parse(&value.to_string()), Ok(value));
+    assert!(Json::parse("[1, 2").is_err());
+    assert!(Json::parse("{} extra").is_err());
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use lang::common::json::Json;
+
+use common::{field, fixture, lang, span};
+
+fn diagnostic(name: &str) -> Json {
+    let path = fixture("lexer", name);
+    let output = lang([path.to_str().expect("utf-8 path"), "--format", "json"]);
+    Json::parse(output.trim()).unwrap_or_else(|err| panic!("{}\n{}", err, output))
+}
+
+#[test]
+fn lexer_failures_have_distinct_kinds_and_codes() {
+    let cases = [
+        (
+            "unknown_character.lang",
+            "E0001",
+            "UnknownCharacter",
+            (32, 33),
+        ),
+        (
+            "malformed_number.lang",
+            "E0002",
+            "MalformedNumber",
+            (30, 35),
+        ),
+        (
+            "invalid_identifier_start.lang",
+            "E0003",
+            "InvalidIdentifierStart",
+            (26, 29),
+        ),
+        (
+            "unterminated_string.lang",
+            "E0004",
+            "UnterminatedString",
+            (30, 39),
+        ),
+    ];
+    for (name, code, kind, (start, end)) in cases {
+        let diagnostic = diagnostic(name);
+        assert_eq!(field(&diagnostic, "code"), code, "{}", name);
+        assert_eq!(field(&diagnostic, "kind"), kind, "{}", name);
+        assert_eq!(span(&diagnostic), (start, end), "{}", name);
+    }
+    let details = diagnostic("unknown_character.lang");
+    assert_eq!(field(details.get("details").expect("details"), "char"), "@");
+    let details = diagnostic("invalid_identifier_start.lang");
+    assert_eq!(field(details.get("details").expect("details"), "char"), "2");
+}
+
+#[test]
+fn errors_at_the_start_of_a_file_are_reported_in_text() {
+    let path = fixture("lexer", "leading_character.lang");
+    let output = common::run(common::command().arg(&path));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("panicked"), "{}", stderr);
+    let text = common::stdout(&output);
+    assert!(
+        text.starts_with("error[E0001]: UnknownCharacter { char: '@' } at "),
+        "{}",
+        text
+    );
+    assert!(text.contains(":0.1\n@\nfunc"), "{}", text);
+}
+
+#[test]
+fn lexer_errors_between_items_are_not_dropped() {
+    let diagnostic = diagnostic("trailing_character.lang");
+    assert_eq!(field(&diagnostic, "code"), "E0001");
+    assert_eq!(span(&diagnostic), (36, 37));
+}
+
+fn tokens(name: &str) -> Vec<String> {
+    let path = fixture("lexer", name);
+    lang([path.to_str().expect("utf-8 path"), "--emit-tokens"])
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn comments_and_carriage_returns_are_skipped() {
+    let tokens = tokens("comments.lang");
+    assert_eq!(
+        tokens.first().map(String::as_str),
+        Some(r#"Function "function" 20.28"#)
+    );
+    assert!(
+        tokens.contains(&r#"Divide "/" 65.66"#.to_string()),
+        "{:?}",
+        tokens
+    );
+    assert!(
+        tokens.contains(&r#"Let "let" 55.58"#.to_string()),
+        "{:?}",
+        tokens
+    );
+    assert_eq!(
+        tokens.last().map(String::as_str),
+        Some(r#"RightCurlyBrace "}" 109.110"#)
+    );
+    assert_eq!(tokens.len(), 20);
+    let output = lang([fixture("lexer", "comments.lang")]);
+    assert_eq!(output, "a // b 3\n");
+}
+
+#[test]
+fn comment_markers_inside_strings_are_kept() {
+    assert!(tokens("comments.lang").contains(&r#"String "\"a // b\"" 94.102"#.to_string()));
+}
+
+#[test]
+fn bare_carriage_returns_separate_tokens_and_a_trailing_comment_ends_the_file() {
+    assert_eq!(
+        tokens("edges.lang"),
+        [
+            r#"Identifier "a" 0.1"#,
+            r#"Identifier "b" 2.3"#,
+            r#"Divide "/" 4.5"#,
+            r#"Identifier "c" 6.7"#,
+        ]
+    );
+}
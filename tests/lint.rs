@@ -0,0 +1,262 @@
+mod common;
+
+use std::{env, fs, path::PathBuf};
+
+use lang::common::json::Json;
+
+use common::{command, field, fixture, json_lines, run, span, stdout};
+
+type Reported = (String, String, (usize, usize), String);
+
+fn lint(path: PathBuf) -> Vec<Json> {
+    json_lines(&stdout(&run(command()
+        .arg("lint")
+        .arg(path)
+        .args(["--format", "json"]))))
+}
+
+fn reported(name: &str) -> Vec<Reported> {
+    lint(fixture("lint", name))
+        .iter()
+        .map(|lint| {
+            (
+                field(lint, "severity").to_string(),
+                field(lint, "kind").to_string(),
+                span(lint),
+                lint.get("details").expect("details").to_string(),
+            )
+        })
+        .collect()
+}
+
+fn lint_entry(severity: &str, kind: &str, span: (usize, usize), details: &str) -> Reported {
+    (
+        severity.to_string(),
+        kind.to_string(),
+        span,
+        details.to_string(),
+    )
+}
+
+fn fixes(name: &str) -> Vec<String> {
+    lint(fixture("lint", name))
+        .iter()
+        .map(|lint| lint.get("suggestions").expect("suggestions").to_string())
+        .collect()
+}
+
+fn fixed(name: &str) -> String {
+    let path = env::temp_dir().join(format!("lang-lint-{}-{}", std::process::id(), name));
+    fs::copy(fixture("lint", name), &path).expect("fixture should copy");
+    run(command().arg("lint").arg(&path).arg("--fix"));
+    let code = fs::read_to_string(&path).expect("fixed file should be readable");
+    let _ = fs::remove_file(&path);
+    code
+}
+
+fn expected_fix(name: &str) -> String {
+    let path = fixture("lint", name).with_extension("fixed");
+    fs::read_to_string(path).expect("expected fix should exist")
+}
+
+#[test]
+fn unused_variables_skip_underscored_names() {
+    assert_eq!(
+        reported("unused_variables.lang"),
+        [lint_entry(
+            "warning",
+            "UnusedVariable",
+            (30, 35),
+            r#"{"name":"total"}"#
+        )]
+    );
+    assert_eq!(
+        fixes("unused_variables.lang"),
+        [
+            r#"[{"message":"prefix the name with `_`","span":{"start":30,"end":30},"replacement":"_"}]"#
+        ]
+    );
+}
+
+#[test]
+fn unused_functions_count_member_calls_and_skip_pub() {
+    assert_eq!(
+        reported("unused_functions.lang"),
+        [lint_entry(
+            "warning",
+            "UnusedFunction",
+            (9, 15),
+            r#"{"name":"helper"}"#
+        )]
+    );
+    assert_eq!(fixes("unused_functions.lang"), ["[]"]);
+}
+
+#[test]
+fn unused_arguments_cover_functions_and_lambdas() {
+    assert_eq!(
+        reported("unused_arguments.lang"),
+        [
+            lint_entry("warning", "UnusedArgument", (23, 24), r#"{"name":"b"}"#),
+            lint_entry("warning", "UnusedArgument", (94, 95), r#"{"name":"y"}"#),
+        ]
+    );
+}
+
+#[test]
+fn snake_case_fixes_private_functions_only() {
+    assert_eq!(
+        reported("snake_case.lang"),
+        [
+            lint_entry(
+                "warning",
+                "NonSnakeCaseName",
+                (9, 15),
+                r#"{"name":"addOne","suggestion":"add_one"}"#
+            ),
+            lint_entry(
+                "warning",
+                "NonSnakeCaseName",
+                (74, 82),
+                r#"{"name":"timesTwo","suggestion":"times_two"}"#
+            ),
+        ]
+    );
+    let rename = |start, end| {
+        format!(
+            r#"{{"message":"rename to `add_one`","span":{{"start":{},"end":{}}},"replacement":"add_one"}}"#,
+            start, end
+        )
+    };
+    assert_eq!(
+        fixes("snake_case.lang"),
+        [
+            format!(
+                "[{},{},{}]",
+                rename(9, 15),
+                rename(175, 181),
+                rename(190, 196)
+            ),
+            "[]".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn snake_case_does_not_fix_functions_used_from_other_modules() {
+    let lints = lint(fixture("lint", "modules/main.lang"));
+    assert_eq!(lints.len(), 1);
+    assert_eq!(field(&lints[0], "kind"), "NonSnakeCaseName");
+    assert!(field(&lints[0], "path").ends_with("util.lang"));
+    assert_eq!(lints[0].get("suggestions"), Some(&Json::Array(vec![])));
+}
+
+#[test]
+fn redundant_parentheses_keep_the_required_pair() {
+    assert_eq!(
+        reported("redundant_parentheses.lang"),
+        [
+            lint_entry("warning", "RedundantParentheses", (30, 37), "{}"),
+            lint_entry("warning", "RedundantParentheses", (66, 69), "{}"),
+            lint_entry("warning", "RedundantParentheses", (79, 86), "{}"),
+        ]
+    );
+}
+
+#[test]
+fn self_comparison_is_denied_by_default() {
+    assert_eq!(
+        reported("self_comparison.lang"),
+        [lint_entry(
+            "error",
+            "SelfComparison",
+            (41, 47),
+            r#"{"name":"a"}"#
+        )]
+    );
+    let output = run(command()
+        .arg("lint")
+        .arg(fixture("lint", "self_comparison.lang")));
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn empty_blocks_are_reported_and_empty_else_is_removed() {
+    assert_eq!(
+        reported("empty_block.lang"),
+        [
+            lint_entry(
+                "warning",
+                "EmptyBlock",
+                (19, 21),
+                r#"{"block":"function body"}"#
+            ),
+            lint_entry(
+                "warning",
+                "EmptyBlock",
+                (117, 119),
+                r#"{"block":"else branch"}"#
+            ),
+            lint_entry(
+                "warning",
+                "EmptyBlock",
+                (138, 140),
+                r#"{"block":"loop body"}"#
+            ),
+        ]
+    );
+    assert_eq!(
+        fixes("empty_block.lang"),
+        [
+            "[]",
+            r#"[{"message":"remove the else branch","span":{"start":111,"end":119},"replacement":""}]"#,
+            "[]",
+        ]
+    );
+}
+
+#[test]
+fn allow_comments_apply_to_their_own_or_the_next_line_but_not_strings() {
+    assert_eq!(
+        reported("allow.lang"),
+        [
+            lint_entry("warning", "UnusedVariable", (72, 77), r#"{"name":"third"}"#),
+            lint_entry(
+                "warning",
+                "UnusedVariable",
+                (222, 228),
+                r#"{"name":"fourth"}"#
+            ),
+        ]
+    );
+}
+
+#[test]
+fn lint_toml_changes_rule_severities() {
+    assert_eq!(
+        reported("config/main.lang"),
+        [lint_entry(
+            "error",
+            "EmptyBlock",
+            (53, 55),
+            r#"{"block":"if body"}"#
+        )]
+    );
+    let output = run(command()
+        .arg("lint")
+        .arg(fixture("lint", "config/main.lang")));
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn fix_rewrites_files() {
+    for name in [
+        "unused_variables.lang",
+        "unused_arguments.lang",
+        "snake_case.lang",
+        "redundant_parentheses.lang",
+        "empty_block.lang",
+    ] {
+        assert_eq!(fixed(name), expected_fix(name), "{}", name);
+    }
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use std::{io, path::PathBuf, ptr};
+
+use lang::{
+    common::{error::ErrorKind, intern::Interner, json::Json},
+    Compiler,
+};
+
+use common::{field, fixture, json_lines, lang, span};
+
+fn main_file(directory: &str) -> PathBuf {
+    fixture("modules", directory).join("main.lang")
+}
+
+fn diagnostic(directory: &str) -> Json {
+    let path = main_file(directory);
+    let output = lang([path.to_str().expect("utf-8 path"), "--format", "json"]);
+    let mut diagnostics = json_lines(&output);
+    assert_eq!(diagnostics.len(), 1, "{}", output);
+    diagnostics.remove(0)
+}
+
+#[test]
+fn imports_are_loaded_relative_to_the_importing_file() {
+    assert_eq!(lang([main_file("program")]), "8 6\n");
+}
+
+#[test]
+fn each_loaded_file_is_parsed_into_one_module() {
+    let mut compiler = Compiler::new();
+    compiler
+        .load(&main_file("program"))
+        .expect("program should load");
+    let mut session = compiler.session();
+    let modules = session.parse().expect("program should parse");
+    let names = modules.iter().map(|module| module.name).collect::<Vec<_>>();
+    assert_eq!(names, ["main", "util", "geometry"]);
+    assert_eq!(modules[0].imports, [1, 2]);
+    assert_eq!(modules[2].imports, [1]);
+}
+
+#[test]
+fn modules_with_the_same_file_stem_collide() {
+    let diagnostic = diagnostic("collision");
+    assert_eq!(field(&diagnostic, "kind"), "DuplicateModule");
+    assert_eq!(field(&diagnostic, "code"), "E0035");
+    assert!(field(&diagnostic, "path").ends_with("collision/main.lang"));
+    assert_eq!(span(&diagnostic), (30, 41));
+    let details = diagnostic.get("details").expect("details");
+    assert_eq!(field(details, "name"), "math");
+    assert!(field(details, "first").ends_with("collision/a/math.lang"));
+}
+
+#[test]
+fn missing_imports_point_at_the_import() {
+    let diagnostic = diagnostic("missing");
+    assert_eq!(field(&diagnostic, "kind"), "ModuleNotFound");
+    assert_eq!(span(&diagnostic), (8, 19));
+}
+
+#[test]
+fn parse_errors_in_imported_files_are_reported_in_that_file() {
+    let diagnostic = diagnostic("broken");
+    assert_eq!(field(&diagnostic, "kind"), "UnexpectedToken");
+    assert!(field(&diagnostic, "path").ends_with("broken/syntax.lang"));
+}
+
+#[test]
+fn import_cycles_list_the_chain() {
+    let diagnostic = diagnostic("cycle");
+    assert_eq!(field(&diagnostic, "kind"), "ImportCycle");
+    assert!(field(&diagnostic, "path").ends_with("cycle/second.lang"));
+}
+
+#[test]
+fn unreadable_imports_fail_to_load() {
+    let mut compiler = Compiler::new();
+    let err = compiler
+        .load(&main_file("unreadable"))
+        .expect_err("an import that is not utf-8 should not load");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("latin1.lang"), "{}", err);
+    let output = lang([main_file("unreadable")]);
+    assert!(output.contains("latin1.lang: stream did not contain valid UTF-8"));
+    assert!(!output.contains(&ErrorKind::ModuleNotFound.name()));
+}
+
+#[test]
+fn qualified_names_are_interned_once() {
+    let names = Interner::new();
+    let first = names.intern("math::sum".to_string());
+    let second = names.intern(format!("{}::{}", "math", "sum"));
+    assert!(ptr::eq(first, second));
+    assert_eq!(names.intern("math::max".to_string()), "math::max");
+    assert_eq!(names.len(), 2);
+}
+
+#[test]
+fn enums_are_qualified_by_their_module() {
+    assert_eq!(lang([main_file("enums")]), "9.0 0.0\n3.0\n1\nsharp round\n");
+    let path = main_file("enums");
+    let ir = lang([path.to_str().expect("utf-8 path"), "--emit-ir"]);
+    assert!(ir.contains("variant shapes::Shape::Empty()"), "{}", ir);
+    assert!(ir.contains("variant tones::Shape::Sharp()"), "{}", ir);
+    assert!(ir.contains("variant Shape::Point()"), "{}", ir);
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use common::{command, fixture, run, stdout};
+
+fn available(tool: &str) -> bool {
+    Command::new(tool).arg("--version").output().is_ok()
+}
+
+fn cases() -> Vec<PathBuf> {
+    let mut cases = fs::read_dir(fixture("golden", ""))
+        .expect("tests/golden should exist")
+        .map(|entry| entry.expect("readable entry").path())
+        .filter(|path| path.with_extension("stdout").exists())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "lang")
+        })
+        .filter(|path| {
+            !fs::read_to_string(path)
+                .expect("case should be readable")
+                .contains("//~")
+        })
+        .collect::<Vec<_>>();
+    cases.sort();
+    cases
+}
+
+fn executable(case: &Path) -> PathBuf {
+    let stem = case.file_stem().expect("case has a name").to_string_lossy();
+    env::temp_dir().join(format!("lang-native-{}-{}", std::process::id(), stem))
+}
+
+#[test]
+fn native_executables_match_the_interpreter() {
+    if !available("as") || !available("ld") {
+        eprintln!("skipping: `as` and `ld` are required to link executables");
+        return;
+    }
+    let mut failures = vec![];
+    for case in cases() {
+        let expected = stdout(&run(command().arg(&case)));
+        let binary = executable(&case);
+        let compiled = run(command().arg("-o").arg(&binary).arg(&case));
+        if !binary.exists() {
+            failures.push(format!(
+                "{}: failed to compile\n{}{}",
+                case.display(),
+                stdout(&compiled),
+                String::from_utf8_lossy(&compiled.stderr)
+            ));
+            continue;
+        }
+        let actual = stdout(&run(&mut Command::new(&binary)));
+        let _ = fs::remove_file(&binary);
+        if actual != expected {
+            failures.push(format!(
+                "{}\n--- interpreter\n{}\n--- native\n{}",
+                case.display(),
+                expected,
+                actual
+            ));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "native executables differ from the interpreter:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn the_sample_program_compiles() {
+    if !available("as") || !available("ld") {
+        eprintln!("skipping: `as` and `ld` are required to link executables");
+        return;
+    }
+    let expected = stdout(&run(&mut command()));
+    let binary = env::temp_dir().join(format!("lang-native-{}-sample", std::process::id()));
+    let compiled = run(command().arg("-o").arg(&binary));
+    assert!(binary.exists(), "{}", stdout(&compiled));
+    let actual = stdout(&run(&mut Command::new(&binary)));
+    let _ = fs::remove_file(&binary);
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,162 @@
+use std::{fs, path::Path};
+
+use lang::{
+    common::{intern::Interner, source::Source},
+    lexer::{token::TokenKind, Lexer},
+    parser::{
+        ast::{expression::Expression, node::Node, statement::Statement},
+        cursor::Cursor,
+        operator::{Associativity, Fixity, OperatorTable},
+        Parser,
+    },
+};
+
+fn render(node: &Node) -> String {
+    match node {
+        Node::Integer(value) | Node::Identifier(value) => value.value.to_string(),
+        Node::Expression(Expression::Infix { operator, lhs, rhs }) => {
+            format!("({} {} {})", render(lhs), operator, render(rhs))
+        }
+        Node::Expression(Expression::Prefix { operator, value }) => {
+            format!("({}{})", operator.value, render(value))
+        }
+        Node::Expression(Expression::Call {
+            callee, arguments, ..
+        }) => format!(
+            "{}({})",
+            render(callee),
+            arguments
+                .value
+                .iter()
+                .map(render)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Node::Expression(Expression::Ternary {
+            test,
+            consequent,
+            alternative,
+        }) => format!(
+            "({} ? {} : {})",
+            render(test),
+            render(consequent),
+            render(alternative)
+        ),
+        Node::Expression(Expression::Range {
+            start,
+            end,
+            inclusive,
+        }) => format!(
+            "({}{}{})",
+            render(start),
+            if *inclusive { "..=" } else { ".." },
+            render(end)
+        ),
+        other => panic!("unexpected node {:?}", other),
+    }
+}
+
+fn parse(expression: &str, operators: OperatorTable) -> String {
+    let code = format!("function main() {{\n    let value = {};\n}}\n", expression);
+    let source = Source {
+        code: &code,
+        path: "operators.lang",
+    };
+    let names = Interner::new();
+    let cursor = Cursor::new(Lexer::new(source).peekable(), source);
+    let program = Parser::new(source, cursor, &names)
+        .with_operators(operators)
+        .parse_program()
+        .expect("expression should parse");
+    let Node::Block(body) = &program.functions[0].body else {
+        panic!("function body should be a block");
+    };
+    match &body.value[0] {
+        Node::Statement(Statement::Let(statement)) => {
+            render(statement.value.init.as_ref().expect("initializer"))
+        }
+        other => panic!("unexpected statement {:?}", other),
+    }
+}
+
+#[test]
+fn table_decides_precedence_and_associativity() {
+    let cases = [
+        ("1 + 2 * 3", "(1 + (2 * 3))"),
+        ("1 - 2 - 3", "((1 - 2) - 3)"),
+        ("a = b = c", "(a = (b = c))"),
+        ("-a * b", "((-a) * b)"),
+        ("-f(1) + 2", "((-f(1)) + 2)"),
+        ("a == b == c", "((a == b) == c)"),
+        ("a ? b : c ? d : e", "(a ? b : (c ? d : e))"),
+        ("a ? b ? c : d : e", "(a ? (b ? c : d) : e)"),
+        ("a = b < c ? b : c", "(a = ((b < c) ? b : c))"),
+        ("0..n - 1", "(0..(n - 1))"),
+        ("a + 1..=b * 2", "((a + 1)..=(b * 2))"),
+        ("c ? 0..1 : 2..3", "(c ? (0..1) : (2..3))"),
+    ];
+    for (expression, expected) in cases {
+        assert_eq!(
+            parse(expression, OperatorTable::default()),
+            expected,
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn precedence_can_be_redefined() {
+    let mut operators = OperatorTable::default();
+    assert!(operators.set_precedence(TokenKind::Plus, Fixity::Infix, 5, Associativity::Left));
+    assert!(operators.set_precedence(TokenKind::Minus, Fixity::Infix, 4, Associativity::Right));
+    assert!(!operators.set_precedence(TokenKind::Comma, Fixity::Infix, 1, Associativity::Left));
+    assert_eq!(parse("1 + 2 * 3", operators.clone()), "((1 + 2) * 3)");
+    assert_eq!(parse("1 - 2 - 3", operators), "(1 - (2 - 3))");
+}
+
+#[test]
+fn ranges_lex_apart_from_numbers() {
+    let source = Source {
+        code: "1..2 3..=4 5.5",
+        path: "ranges.lang",
+    };
+    let kinds = Lexer::new(source)
+        .map(|token| token.expect("token").kind)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            TokenKind::Integer,
+            TokenKind::DotDot,
+            TokenKind::Integer,
+            TokenKind::Integer,
+            TokenKind::DotDotEqual,
+            TokenKind::Integer,
+            TokenKind::Float,
+        ]
+    );
+}
+
+#[test]
+fn ternary_without_alternative_is_an_error() {
+    let code = "function main() {\n    let value = a ? b;\n}\n";
+    let source = Source {
+        code,
+        path: "ternary.lang",
+    };
+    let cursor = Cursor::new(Lexer::new(source).peekable(), source);
+    let names = Interner::new();
+    assert!(Parser::new(source, cursor, &names).parse_program().is_err());
+}
+
+#[test]
+fn documentation_lists_the_operator_table() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("docs/operators.md");
+    let docs = fs::read_to_string(path).expect("operator docs should exist");
+    assert!(
+        docs.contains(&OperatorTable::default().markdown()),
+        "docs/operators.md is out of date:\n{}",
+        OperatorTable::default().markdown()
+    );
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use std::{fs, panic, path::Path};
+
+use lang::{
+    common::{error::ErrorKind, intern::Interner, source::Source},
+    lexer::Lexer,
+    parser::{cursor::Cursor, Parser, MAX_NESTING_DEPTH},
+    syntax::{self, lower::lower},
+};
+
+use common::Random;
+
+const FRAGMENTS: [&str; 40] = [
+    "function", "pub", "import", "module", "enum", "let", "mut", "if", "else", "while", "return",
+    "match", "main", "x", "_", "0", "1.5", "1.", "2nd", "\"s\"", "\"", "\\", "(", ")", "{", "}",
+    "[", "]", ",", ";", ":", "::", "->", "=>", "=", "==", "-", "<", ">", "@",
+];
+
+fn run(code: &str) -> bool {
+    let source = Source {
+        code,
+        path: "fuzz.lang",
+    };
+    let outcome = panic::catch_unwind(|| {
+        let names = Interner::new();
+        Lexer::new(source).for_each(drop);
+        let lossless = syntax::parse(source, &names)
+            .ok()
+            .map(|tree| lower(source, &names, &tree).is_some());
+        let cursor = Cursor::new(Lexer::new(source).peekable(), source);
+        let parsed = Parser::new(source, cursor, &names).parse_program().is_ok();
+        (lossless, parsed)
+    });
+    let (lossless, parsed) = outcome.unwrap_or_else(|_| panic!("panicked on {:?}", code));
+    assert_eq!(lossless, parsed.then_some(true), "{:?}", code);
+    parsed
+}
+
+#[test]
+fn regressions_do_not_panic() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/robustness");
+    let mut entries = fs::read_dir(directory)
+        .expect("regression directory should exist")
+        .map(|entry| entry.expect("readable entry").path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    assert!(!entries.is_empty());
+    for path in entries {
+        let parsed = run(&fs::read_to_string(&path).expect("regression should be readable"));
+        let truncated = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| stem.starts_with("trailing"));
+        assert_eq!(parsed, !truncated, "{}", path.display());
+    }
+}
+
+#[test]
+fn truncated_programs_do_not_panic() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    for directory in ["syntax", "json", "golden", "lexer"] {
+        for entry in fs::read_dir(root.join(directory)).expect("test directory should exist") {
+            let code = fs::read_to_string(entry.expect("readable entry").path())
+                .expect("program should be readable");
+            for (index, _) in code.match_indices('\n') {
+                run(&code[..index]);
+            }
+            for line in code.lines() {
+                for (index, _) in line.char_indices() {
+                    run(&format!("function main() {{ {}", &line[..index]));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn random_token_soup_does_not_panic() {
+    let mut random = Random(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..2000 {
+        let mut code = match random.below(2) {
+            0 => String::new(),
+            _ => "function main() { ".to_string(),
+        };
+        for _ in 0..random.below(24) {
+            code.push_str(FRAGMENTS[random.below(FRAGMENTS.len())]);
+            if random.below(3) == 0 {
+                code.push(' ');
+            }
+        }
+        run(&code);
+    }
+}
+
+#[test]
+fn deep_nesting_is_an_error_instead_of_a_stack_overflow() {
+    let nest = |depth: usize, open: &str, inner: &str, close: &str| {
+        format!("{}{}{}", open.repeat(depth), inner, close.repeat(depth))
+    };
+    let programs = |depth: usize| {
+        [
+            format!(
+                "function main() {{ let x = {}; }}",
+                nest(depth, "(", "1", ")")
+            ),
+            format!(
+                "function main() {{ let x = {}; }}",
+                nest(depth, "[", "1", "]")
+            ),
+            format!("function main() {{ let x = {}1; }}", "-".repeat(depth)),
+            format!("function main() {}", nest(depth, "{", "", "}")),
+            format!(
+                "function main() {{ {} }}",
+                nest(depth, "if (1 == 1) {", "", "}")
+            ),
+            format!(
+                "function main() {{ {}return; }}",
+                "if (1 == 1) ".repeat(depth)
+            ),
+            format!(
+                "function main() {{ let x: {} = 1; }}",
+                nest(depth, "[", "int", "]")
+            ),
+            format!(
+                "function main() {{ match (1) {{ {} => 1 }} }}",
+                nest(depth, "A::B(", "_", ")")
+            ),
+        ]
+    };
+    for code in programs(MAX_NESTING_DEPTH / 2 - 2) {
+        assert!(run(&code), "{:?}", code);
+    }
+    for code in programs(1000) {
+        let source = Source {
+            code: &code,
+            path: "nesting.lang",
+        };
+        let names = Interner::new();
+        let cursor = Cursor::new(Lexer::new(source).peekable(), source);
+        let err = Parser::new(source, cursor, &names)
+            .parse_program()
+            .expect_err("deep nesting should be rejected");
+        assert_eq!(err.kind, ErrorKind::NestingTooDeep, "{:?}", code);
+        assert!(!run(&code));
+    }
+}
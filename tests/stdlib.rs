@@ -0,0 +1,144 @@
+use lang::{
+    common::{error::ErrorKind, source::Source, span::Span},
+    interpreter::value::Value,
+    stdlib::{lookup, signature::Type, Builtin, BUILTINS},
+};
+
+const SOURCE: Source = Source {
+    code: "",
+    path: "stdlib.lang",
+};
+
+fn accepted(parameter: Type) -> Value<'static> {
+    match parameter {
+        Type::Int | Type::Number | Type::Any => Value::Integer(1),
+        Type::Float => Value::Float(1.0),
+        Type::Bool => Value::Boolean(true),
+        Type::String => Value::String("text".to_string()),
+        Type::Unit => Value::Unit,
+        Type::Array => Value::Array(vec![]),
+    }
+}
+
+fn rejected(parameter: Type) -> Option<Value<'static>> {
+    match parameter {
+        Type::Int | Type::Float | Type::Bool | Type::Number | Type::Array | Type::Unit => {
+            Some(Value::String("text".to_string()))
+        }
+        Type::String => Some(Value::Integer(1)),
+        Type::Any => None,
+    }
+}
+
+fn call(builtin: &Builtin, arguments: Vec<Value<'static>>) -> ErrorKind {
+    match builtin.call(arguments, Span::new(0, 0), SOURCE) {
+        Ok(value) => panic!("`{}` returned {}", builtin.name, value),
+        Err(err) => err.kind,
+    }
+}
+
+#[test]
+fn every_builtin_checks_its_arity() {
+    for builtin in BUILTINS {
+        let parameters = builtin.signature.parameters;
+        let mut arguments = parameters.iter().copied().map(accepted).collect::<Vec<_>>();
+        if builtin.signature.variadic.is_none() {
+            arguments.push(Value::Unit);
+            assert_eq!(
+                call(builtin, arguments.clone()),
+                ErrorKind::WrongNumberOfArguments {
+                    expected: parameters.len(),
+                    received: parameters.len() + 1,
+                },
+                "{}",
+                builtin.name
+            );
+            arguments.pop();
+        }
+        if arguments.pop().is_some() {
+            assert_eq!(
+                call(builtin, arguments),
+                ErrorKind::WrongNumberOfArguments {
+                    expected: parameters.len(),
+                    received: parameters.len() - 1,
+                },
+                "{}",
+                builtin.name
+            );
+        }
+    }
+}
+
+#[test]
+fn every_typed_parameter_rejects_other_types() {
+    let mut checked = 0;
+    for builtin in BUILTINS {
+        let parameters = builtin.signature.parameters;
+        for (index, parameter) in parameters.iter().copied().enumerate() {
+            let Some(value) = rejected(parameter) else {
+                continue;
+            };
+            let mut arguments = parameters.iter().copied().map(accepted).collect::<Vec<_>>();
+            arguments[index] = value;
+            assert_eq!(
+                call(builtin, arguments),
+                ErrorKind::TypeMismatch,
+                "argument {} of `{}`",
+                index,
+                builtin.name
+            );
+            checked += 1;
+        }
+        if let Some(variadic) = builtin.signature.variadic.and_then(rejected) {
+            let mut arguments = parameters.iter().copied().map(accepted).collect::<Vec<_>>();
+            arguments.push(variadic);
+            assert_eq!(call(builtin, arguments), ErrorKind::TypeMismatch);
+        }
+    }
+    assert!(checked >= 20, "only {} parameters checked", checked);
+}
+
+#[test]
+fn untyped_parameters_reject_values_the_implementation_cannot_use() {
+    let cases = [
+        ("len", vec![Value::Integer(3)], ErrorKind::TypeMismatch),
+        (
+            "slice",
+            vec![Value::Integer(3), Value::Integer(0), Value::Integer(1)],
+            ErrorKind::TypeMismatch,
+        ),
+        (
+            "to_int",
+            vec![Value::Array(vec![])],
+            ErrorKind::InvalidConversion,
+        ),
+        (
+            "to_float",
+            vec![Value::Array(vec![])],
+            ErrorKind::InvalidConversion,
+        ),
+        (
+            "to_int",
+            vec![Value::String("1.5x".to_string())],
+            ErrorKind::InvalidConversion,
+        ),
+    ];
+    for (name, arguments, kind) in cases {
+        let builtin = lookup(name).expect("builtin should exist");
+        assert_eq!(call(builtin, arguments), kind, "{}", name);
+    }
+}
+
+#[test]
+fn builtins_are_registered_once() {
+    for (index, builtin) in BUILTINS.iter().enumerate() {
+        assert!(
+            BUILTINS[..index]
+                .iter()
+                .all(|other| other.name != builtin.name),
+            "{}",
+            builtin.name
+        );
+        assert!(std::ptr::eq(lookup(builtin.name).unwrap(), builtin));
+    }
+}
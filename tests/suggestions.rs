@@ -0,0 +1,87 @@
+mod common;
+
+use std::{env, fs, path::Path};
+
+use lang::common::json::Json;
+
+use common::{fixture, json_lines, span};
+
+fn lang(path: &Path) -> String {
+    common::lang([path, Path::new("--format"), Path::new("json")])
+}
+
+fn suggestions(path: &Path) -> Vec<(String, usize, usize, String)> {
+    let mut suggestions = vec![];
+    for diagnostic in json_lines(&lang(path)) {
+        if diagnostic.get("severity").and_then(Json::as_str) != Some("error") {
+            continue;
+        }
+        let Some(Json::Array(list)) = diagnostic.get("suggestions") else {
+            panic!("missing suggestions in {}", diagnostic);
+        };
+        assert!(!list.is_empty(), "no suggestion for {}", diagnostic);
+        for suggestion in list {
+            let text = |key| {
+                suggestion
+                    .get(key)
+                    .and_then(Json::as_str)
+                    .expect("suggestion text")
+                    .to_string()
+            };
+            let (start, end) = span(suggestion);
+            suggestions.push((text("message"), start, end, text("replacement")));
+        }
+    }
+    suggestions
+}
+
+fn apply(code: &str, suggestions: &[(String, usize, usize, String)]) -> String {
+    let mut chars = code.chars().collect::<Vec<_>>();
+    let mut edits = suggestions.to_vec();
+    edits.sort_by_key(|(_, start, _, _)| *start);
+    for (_, start, end, replacement) in edits.iter().rev() {
+        chars.splice(start..end, replacement.chars());
+    }
+    chars.into_iter().collect()
+}
+
+#[test]
+fn mistakes_come_with_targeted_suggestions() {
+    let cases: [(&str, &[&str]); 5] = [
+        ("keyword.lang", &["did you mean `function`"]),
+        ("missing_semicolon.lang", &["add the missing `;`"]),
+        ("assignment_condition.lang", &["compare with `==`"]),
+        (
+            "missing_parenthesis.lang",
+            &["close the condition with `)`"],
+        ),
+        (
+            "names.lang",
+            &[
+                "did you mean `count`",
+                "did you mean `println`",
+                "did you mean `return`",
+            ],
+        ),
+    ];
+    let fixed = env::temp_dir().join(format!("lang-suggestions-{}.lang", std::process::id()));
+    for (name, messages) in cases {
+        let path = fixture("suggestions", name);
+        let suggestions = suggestions(&path);
+        let found = suggestions
+            .iter()
+            .map(|(message, _, _, _)| message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(found, messages, "{}", name);
+        let code = fs::read_to_string(&path).expect("fixture should be readable");
+        fs::write(&fixed, apply(&code, &suggestions)).expect("writable fix");
+        let output = lang(&fixed);
+        assert!(
+            !output.contains("\"severity\":\"error\""),
+            "{} still fails after applying suggestions:\n{}",
+            name,
+            output
+        );
+    }
+    fs::remove_file(&fixed).ok();
+}
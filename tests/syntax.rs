@@ -0,0 +1,115 @@
+mod common;
+
+use std::{fs, path::Path};
+
+use lang::common::json::Json;
+
+use common::{fixture, span};
+
+fn lang(arguments: &[&str], path: &Path) -> String {
+    common::lang(arguments.iter().map(Path::new).chain([path]))
+}
+
+fn programs() -> Vec<std::path::PathBuf> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let mut programs = vec![];
+    for directory in ["syntax", "json", "golden"] {
+        for entry in fs::read_dir(root.join(directory)).expect("test directory should exist") {
+            let path = entry.expect("readable entry").path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "lang")
+                && path.file_stem().is_some_and(|stem| stem != "error")
+            {
+                programs.push(path);
+            }
+        }
+    }
+    programs.sort();
+    programs
+}
+
+fn collect_text(element: &Json, position: &mut usize, text: &mut String) {
+    assert_eq!(span(element).0, *position, "gap before {}", element);
+    match element.get("children") {
+        Some(Json::Array(children)) => {
+            for child in children {
+                collect_text(child, position, text);
+            }
+        }
+        _ => {
+            let leaf = element
+                .get("text")
+                .and_then(Json::as_str)
+                .expect("token text");
+            text.push_str(leaf);
+            *position += leaf.chars().count();
+        }
+    }
+    assert_eq!(span(element).1, *position, "span of {}", element);
+}
+
+#[test]
+fn syntax_tree_reproduces_the_source_exactly() {
+    for path in programs() {
+        let output = lang(&["--format", "json", "--emit-cst"], &path);
+        let files = Json::parse(&output).unwrap_or_else(|err| panic!("{}\n{}", err, output));
+        let Json::Array(files) = files else {
+            panic!("expected an array of files");
+        };
+        let tree = files[0].get("tree").expect("tree");
+        let mut text = String::new();
+        collect_text(tree, &mut 0, &mut text);
+        let source = fs::read_to_string(&path).expect("program should be readable");
+        assert_eq!(text, source, "{}", path.display());
+    }
+}
+
+#[test]
+fn syntax_tree_keeps_trivia_and_unlexable_text() {
+    let tree = lang(&["--emit-cst"], &fixture("syntax", "trivia.lang"));
+    assert!(tree.contains("Comment@0..19 \"// leading comment\\r\""));
+    assert!(tree.contains("Parenthesized@"));
+    assert!(tree.contains("ParenthesizedType@"));
+    assert!(tree.ends_with("Comment@577..601 \"// no newline at the end\"\n"));
+    let tree = lang(&["--emit-cst"], &fixture("syntax", "lexer_error.lang"));
+    assert!(
+        tree.ends_with("Error@36..46 \"@ unknown\\n\"\n"),
+        "{}",
+        tree
+    );
+}
+
+#[test]
+fn ast_derived_from_syntax_tree_matches_parser() {
+    for path in programs() {
+        let parsed = lang(&["--format", "json", "--emit-ast"], &path);
+        let lowered = lang(&["--format", "json", "--emit-ast", "--lossless"], &path);
+        assert!(parsed.starts_with('{'), "{}: {}", path.display(), parsed);
+        assert_eq!(parsed, lowered, "{}", path.display());
+    }
+}
+
+#[test]
+fn spaced_paths_and_negative_patterns_are_built_from_tokens() {
+    let path = fixture("golden", "patterns.lang");
+    for arguments in [
+        &["--format", "json", "--emit-ast"][..],
+        &["--format", "json", "--emit-ast", "--lossless"][..],
+    ] {
+        let ast = lang(arguments, &path);
+        for value in [
+            "\"Shape::Circle\"",
+            "\"Shape::Square\"",
+            "\"Shape::Empty\"",
+            "\"-1\"",
+        ] {
+            assert!(ast.contains(value), "{} missing from {}", value, ast);
+        }
+        assert!(
+            !ast.contains("Shape ::") && !ast.contains("\"- 1\""),
+            "{}",
+            ast
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use lang::ir::{
+    verify::verify, Block, BlockId, Constant, Function, Instruction, InstructionKind, Module, Phi,
+    Terminator, Type, ValueId,
+};
+
+fn instruction<'a>(
+    value: usize,
+    value_type: Type<'a>,
+    kind: InstructionKind<'a>,
+) -> Instruction<'a> {
+    Instruction {
+        value: ValueId(value),
+        value_type,
+        kind,
+    }
+}
+
+fn unit(value: usize) -> Instruction<'static> {
+    instruction(value, Type::Unit, InstructionKind::Constant(Constant::Unit))
+}
+
+fn block(id: usize, instructions: Vec<Instruction>, terminator: Terminator) -> Block {
+    Block {
+        id: BlockId(id),
+        phis: vec![],
+        instructions,
+        terminator,
+    }
+}
+
+fn int(value: usize, integer: i64) -> Instruction<'static> {
+    instruction(
+        value,
+        Type::Int,
+        InstructionKind::Constant(Constant::Int(integer)),
+    )
+}
+
+fn diamond<'a>(merge: Block<'a>) -> Module<'a> {
+    module(vec![
+        block(
+            0,
+            vec![
+                unit(0),
+                instruction(
+                    1,
+                    Type::Bool,
+                    InstructionKind::Constant(Constant::Bool(true)),
+                ),
+            ],
+            Terminator::Branch {
+                condition: ValueId(1),
+                consequent: BlockId(1),
+                alternative: BlockId(2),
+            },
+        ),
+        block(1, vec![int(2, 1)], Terminator::Jump(BlockId(3))),
+        block(2, vec![int(3, 2)], Terminator::Jump(BlockId(3))),
+        merge,
+    ])
+}
+
+fn merge<'a>(incoming: Vec<(usize, usize)>, instructions: Vec<Instruction<'a>>) -> Block<'a> {
+    Block {
+        id: BlockId(3),
+        phis: vec![Phi {
+            value: ValueId(4),
+            value_type: Type::Int,
+            incoming: incoming
+                .into_iter()
+                .map(|(block, value)| (BlockId(block), ValueId(value)))
+                .collect(),
+        }],
+        instructions,
+        terminator: Terminator::Return(ValueId(0)),
+    }
+}
+
+fn module(blocks: Vec<Block>) -> Module {
+    Module {
+        functions: vec![Function {
+            name: "main",
+            parameters: vec![],
+            return_type: Type::Unit,
+            blocks,
+        }],
+    }
+}
+
+fn message(module: &Module) -> String {
+    verify(module)
+        .expect_err("module should be rejected")
+        .message
+}
+
+#[test]
+fn function_references_must_resolve() {
+    let call = |function| {
+        module(vec![block(
+            0,
+            vec![
+                unit(0),
+                instruction(
+                    1,
+                    Type::Unit,
+                    InstructionKind::Call {
+                        function,
+                        arguments: vec![ValueId(0)],
+                    },
+                ),
+            ],
+            Terminator::Return(ValueId(0)),
+        )])
+    };
+    assert!(verify(&call("println")).is_ok());
+    assert!(verify(&call("main")).is_ok());
+    assert_eq!(
+        message(&call("missing")),
+        "reference to unknown function @missing"
+    );
+    let value = module(vec![block(
+        0,
+        vec![
+            unit(0),
+            instruction(
+                1,
+                Type::Dynamic,
+                InstructionKind::Constant(Constant::Function("larger")),
+            ),
+        ],
+        Terminator::Return(ValueId(0)),
+    )]);
+    assert_eq!(message(&value), "reference to unknown function @larger");
+}
+
+#[test]
+fn well_formed_diamonds_verify() {
+    let module = diamond(merge(
+        vec![(1, 2), (2, 3)],
+        vec![instruction(
+            5,
+            Type::Int,
+            InstructionKind::Negate(ValueId(4)),
+        )],
+    ));
+    assert!(verify(&module).is_ok());
+}
+
+#[test]
+fn uses_must_be_dominated_by_their_definitions() {
+    let merged = diamond(merge(
+        vec![(1, 2), (2, 3)],
+        vec![instruction(
+            5,
+            Type::Int,
+            InstructionKind::Negate(ValueId(2)),
+        )],
+    ));
+    assert_eq!(
+        message(&merged),
+        "definition of %2 does not dominate its use in bb3"
+    );
+    let late = module(vec![block(
+        0,
+        vec![
+            unit(0),
+            instruction(1, Type::Int, InstructionKind::Negate(ValueId(2))),
+            int(2, 1),
+        ],
+        Terminator::Return(ValueId(0)),
+    )]);
+    assert_eq!(
+        message(&late),
+        "definition of %2 does not dominate its use in bb0"
+    );
+}
+
+#[test]
+fn phis_must_match_the_predecessors_of_their_block() {
+    let missing = diamond(merge(vec![(1, 2)], vec![]));
+    assert_eq!(
+        message(&missing),
+        "phi %4 in bb3 does not match the block's predecessors"
+    );
+    let foreign = diamond(merge(vec![(1, 2), (0, 1)], vec![]));
+    assert_eq!(
+        message(&foreign),
+        "phi %4 in bb3 does not match the block's predecessors"
+    );
+}
+
+#[test]
+fn jumps_must_target_defined_blocks() {
+    let module = module(vec![block(0, vec![unit(0)], Terminator::Jump(BlockId(7)))]);
+    assert_eq!(message(&module), "bb0 jumps to undefined block bb7");
+}